@@ -0,0 +1,310 @@
+use std::sync::{Arc, Mutex};
+
+use polars::sql::keywords::{all_functions, all_keywords};
+use polars::sql::SQLContext;
+use reedline::{Completer, Span, Suggestion};
+use sqlparser::dialect::GenericDialect;
+use sqlparser::keywords::Keyword;
+use sqlparser::tokenizer::{Token, Tokenizer};
+
+use crate::interactive::PolarsCommand;
+
+/// The tables of the context and their columns. This is shared with the completer and
+/// must be updated when tables are registered or dropped.
+#[derive(Clone, Default)]
+pub(crate) struct CompletionTables(Arc<Mutex<Vec<(String, Vec<String>)>>>);
+
+impl CompletionTables {
+    pub(crate) fn update(&self, ctx: &SQLContext) {
+        let table_map = ctx.get_table_map();
+        let tables = ctx
+            .get_tables()
+            .into_iter()
+            .map(|name| {
+                // a table whose schema cannot be resolved has no columns to complete
+                let columns = table_map
+                    .get(&name)
+                    .and_then(|lf| lf.schema().ok())
+                    .map(|schema| schema.iter_names().map(|c| c.to_string()).collect())
+                    .unwrap_or_default();
+                (name, columns)
+            })
+            .collect();
+        *self.0.lock().unwrap() = tables;
+    }
+
+    /// Update the tables after `sql` ran, unless it is a query. Queries don't change the
+    /// tables, and an update resolves the schema of every table.
+    pub(crate) fn update_after(&self, ctx: &SQLContext, sql: &str) {
+        if !is_query(sql) {
+            self.update(ctx)
+        }
+    }
+}
+
+/// Whether `sql` is a query, which doesn't register or drop tables.
+fn is_query(sql: &str) -> bool {
+    let Ok(tokens) = Tokenizer::new(&GenericDialect, sql).tokenize() else {
+        return false;
+    };
+    match tokens.iter().find(|t| !matches!(t, Token::Whitespace(_))) {
+        Some(Token::Word(w)) => matches!(
+            w.keyword,
+            Keyword::SELECT | Keyword::WITH | Keyword::EXPLAIN | Keyword::SHOW
+        ),
+        Some(Token::LParen) => true,
+        _ => false,
+    }
+}
+
+/// Completes the registered tables and their columns, SQL keywords and functions,
+/// dot-commands and the file paths of the `read_*('...')` table functions.
+pub(crate) struct SQLCompleter {
+    tables: CompletionTables,
+}
+
+impl SQLCompleter {
+    pub(crate) fn new(tables: CompletionTables) -> Self {
+        Self { tables }
+    }
+
+    fn complete_word(&self, line: &str, start: usize, pos: usize) -> Vec<Suggestion> {
+        let word = &line[start..pos];
+        let mut candidates: Vec<(String, String)> = vec![];
+
+        if start == 0 && word.starts_with('.') {
+            candidates.extend(
+                PolarsCommand::keywords()
+                    .into_iter()
+                    .map(|cmd| (format!(".{cmd}"), "command".to_string())),
+            );
+        } else {
+            let tables = self.tables.0.lock().unwrap();
+            if let Some((table, column)) = word.split_once('.') {
+                // a qualified column
+                let columns = tables
+                    .iter()
+                    .filter(|(name, _)| name == table)
+                    .flat_map(|(name, columns)| {
+                        columns
+                            .iter()
+                            .map(move |c| (c.clone(), format!("column of {name}")))
+                    })
+                    .collect();
+                return suggestions(columns, start + table.len() + 1, pos, column);
+            }
+            for (name, columns) in tables.iter() {
+                candidates.push((name.clone(), "table".to_string()));
+                candidates.extend(
+                    columns
+                        .iter()
+                        .map(|c| (c.clone(), format!("column of {name}"))),
+                );
+            }
+            let functions = all_functions();
+            candidates.extend(functions.iter().map(|f| (f.to_string(), "function".into())));
+            candidates.extend(
+                all_keywords()
+                    .into_iter()
+                    .filter(|k| !functions.contains(k))
+                    .map(|k| (k.to_string(), "keyword".into())),
+            );
+        }
+        suggestions(candidates, start, pos, word)
+    }
+
+    /// Complete the path inside `read_*('` that starts at `start`.
+    fn complete_path(&self, line: &str, start: usize, pos: usize) -> Vec<Suggestion> {
+        let partial = &line[start..pos];
+        let (dir, prefix) = match partial.rfind('/') {
+            Some(i) => (&partial[..=i], &partial[i + 1..]),
+            None => ("", partial),
+        };
+        let entries = match std::fs::read_dir(if dir.is_empty() { "." } else { dir }) {
+            Ok(entries) => entries,
+            Err(_) => return vec![],
+        };
+
+        let mut suggestions = entries
+            .filter_map(|entry| {
+                let entry = entry.ok()?;
+                let name = entry.file_name().into_string().ok()?;
+                // don't show hidden files, unless asked for
+                if !name.starts_with(prefix) || (name.starts_with('.') && !prefix.starts_with('.'))
+                {
+                    return None;
+                }
+                let is_dir = entry.file_type().map_or(false, |t| t.is_dir());
+                Some(Suggestion {
+                    value: format!("{dir}{name}{}", if is_dir { "/" } else { "" }),
+                    description: None,
+                    extra: None,
+                    span: Span::new(start, pos),
+                    append_whitespace: false,
+                })
+            })
+            .collect::<Vec<_>>();
+        suggestions.sort_by(|a, b| a.value.cmp(&b.value));
+        suggestions
+    }
+}
+
+/// The candidates that start with `word`, ignoring case.
+fn suggestions(
+    candidates: Vec<(String, String)>,
+    start: usize,
+    pos: usize,
+    word: &str,
+) -> Vec<Suggestion> {
+    let word = word.to_lowercase();
+    let mut suggestions = candidates
+        .into_iter()
+        .filter(|(value, _)| value.to_lowercase().starts_with(&word))
+        .map(|(value, description)| Suggestion {
+            value,
+            description: Some(description),
+            extra: None,
+            span: Span::new(start, pos),
+            append_whitespace: false,
+        })
+        .collect::<Vec<_>>();
+    suggestions.sort_by(|a, b| a.value.cmp(&b.value));
+    suggestions.dedup_by(|a, b| a.value == b.value);
+    suggestions
+}
+
+/// The byte offset of the trailing characters of `s` that are part of a word.
+fn word_start(s: &str, is_word: impl Fn(char) -> bool) -> usize {
+    s.char_indices()
+        .rev()
+        .find(|(_, c)| !is_word(*c))
+        .map_or(0, |(i, c)| i + c.len_utf8())
+}
+
+/// The start of the path if `pos` is inside the quoted argument of a `read_*` function.
+fn path_start(line: &str) -> Option<usize> {
+    // an odd number of quotes means we are inside a string
+    if line.matches('\'').count() % 2 == 0 {
+        return None;
+    }
+    let quote = line.rfind('\'')?;
+    let function = line[..quote].trim_end().strip_suffix('(')?.trim_end();
+    let name_start = word_start(function, |c| c.is_alphanumeric() || c == '_');
+    function[name_start..]
+        .to_lowercase()
+        .starts_with("read_")
+        .then_some(quote + 1)
+}
+
+impl Completer for SQLCompleter {
+    fn complete(&mut self, line: &str, pos: usize) -> Vec<Suggestion> {
+        let line = &line[..pos];
+        if let Some(start) = path_start(line) {
+            return self.complete_path(line, start, pos);
+        }
+        let start = word_start(line, |c| c.is_alphanumeric() || c == '_' || c == '.');
+        if start == pos {
+            return vec![];
+        }
+        self.complete_word(line, start, pos)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn completer(tables: &[(&str, &[&str])]) -> SQLCompleter {
+        let tables = tables
+            .iter()
+            .map(|(name, columns)| {
+                let columns = columns.iter().map(|c| c.to_string()).collect();
+                (name.to_string(), columns)
+            })
+            .collect();
+        SQLCompleter::new(CompletionTables(Arc::new(Mutex::new(tables))))
+    }
+
+    fn values(suggestions: Vec<Suggestion>) -> Vec<String> {
+        suggestions.into_iter().map(|s| s.value).collect()
+    }
+
+    #[test]
+    fn test_word_start() {
+        let is_word = |c: char| c.is_alphanumeric() || c == '_' || c == '.';
+        assert_eq!(word_start("SELECT t.col", is_word), 7);
+        assert_eq!(word_start("SELECT ", is_word), 7);
+        assert_eq!(word_start("a_b", is_word), 0);
+        assert_eq!(word_start("SELECT é", is_word), 7);
+    }
+
+    #[test]
+    fn test_path_start() {
+        assert_eq!(path_start("SELECT * FROM read_csv('data/"), Some(24));
+        assert_eq!(path_start("SELECT * FROM READ_PARQUET ( 'f"), Some(30));
+        // the string is closed
+        assert_eq!(path_start("SELECT * FROM read_csv('a.csv')"), None);
+        // not the argument of a `read_*` function
+        assert_eq!(path_start("SELECT * FROM t WHERE a = 'x"), None);
+        assert_eq!(path_start("SELECT concat('x"), None);
+    }
+
+    #[test]
+    fn test_suggestions() {
+        let candidates = vec![
+            ("SELECT".to_string(), "keyword".to_string()),
+            ("sum".to_string(), "function".to_string()),
+            ("sum".to_string(), "column of t".to_string()),
+            ("avg".to_string(), "function".to_string()),
+        ];
+        let out = suggestions(candidates, 0, 1, "S");
+        // case is ignored and duplicates are removed
+        assert_eq!(values(out), ["SELECT", "sum"]);
+    }
+
+    #[test]
+    fn test_complete_columns() {
+        let mut completer = completer(&[("t", &["alpha", "beta"][..]), ("u", &["another"][..])]);
+        let out = completer.complete("SELECT t.a", 10);
+        assert_eq!(values(out.clone()), ["alpha"]);
+        // only the column after the qualifier is replaced
+        assert_eq!(out[0].span, Span::new(9, 10));
+
+        let out = completer.complete("SELECT anot", 11);
+        assert_eq!(values(out), ["another"]);
+        let out = completer.complete("SELECT * FROM ", 14);
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn test_complete_path() {
+        let dir = std::env::temp_dir().join(format!("polars-cli-complete-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("sub")).unwrap();
+        std::fs::write(dir.join("a.csv"), "").unwrap();
+        std::fs::write(dir.join(".hidden.csv"), "").unwrap();
+
+        let mut completer = completer(&[]);
+        let dir_name = dir.display();
+        let line = format!("SELECT * FROM read_csv('{dir_name}/");
+        let out = values(completer.complete(&line, line.len()));
+        let line = format!("SELECT * FROM read_csv('{dir_name}/.");
+        let hidden = values(completer.complete(&line, line.len()));
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        // hidden files are only shown if asked for
+        assert_eq!(
+            out,
+            [format!("{dir_name}/a.csv"), format!("{dir_name}/sub/")]
+        );
+        assert_eq!(hidden, [format!("{dir_name}/.hidden.csv")]);
+    }
+
+    #[test]
+    fn test_is_query() {
+        assert!(is_query("SELECT * FROM t"));
+        assert!(is_query("  with x AS (SELECT 1) SELECT * FROM x"));
+        assert!(is_query("SHOW TABLES"));
+        assert!(!is_query("CREATE TABLE t AS SELECT 1"));
+        assert!(!is_query("DROP TABLE t"));
+    }
+}
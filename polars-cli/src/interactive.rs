@@ -1,18 +1,23 @@
 use std::env;
 use std::io::Cursor;
 use std::path::PathBuf;
+use std::time::Instant;
 
 use clap::crate_version;
 use once_cell::sync::Lazy;
 use polars::df;
 use polars::prelude::{DataFrame, *};
 use polars::sql::SQLContext;
-use reedline::{FileBackedHistory, Reedline, Signal};
+use reedline::{
+    default_emacs_keybindings, ColumnarMenu, Emacs, FileBackedHistory, KeyCode, KeyModifiers,
+    Reedline, ReedlineEvent, ReedlineMenu, Signal,
+};
 
+use crate::completer::{CompletionTables, SQLCompleter};
 #[cfg(feature = "highlight")]
 use crate::highlighter::SQLHighlighter;
 use crate::prompt::SQLPrompt;
-use crate::{OutputMode, SerializableContext};
+use crate::{tables, OutputMode, SerializableContext};
 
 fn get_home_dir() -> PathBuf {
     match env::var("HOME") {
@@ -38,6 +43,11 @@ static HELP_DF: Lazy<DataFrame> = Lazy::new(|| {
             ".quit",
             ".save FILE",
             ".open FILE",
+            ".register NAME PATH",
+            ".unregister NAME",
+            ".tables",
+            ".schema NAME",
+            ".timer on|off",
             ".help",
       ],
         "description" => [
@@ -45,6 +55,11 @@ static HELP_DF: Lazy<DataFrame> = Lazy::new(|| {
             "Exit this program (alias for .exit)",
             "Save the current state of the database to FILE",
             "Open a database from FILE",
+            "Register the file at PATH as table NAME",
+            "Remove table NAME",
+            "List the registered tables",
+            "Show the columns and data types of table NAME",
+            "Print the time each query takes",
             "Display this help.",
       ]
     }
@@ -64,11 +79,23 @@ fn print_help() {
     println!("{}", df);
 }
 
+fn print_df(df: PolarsResult<DataFrame>) -> std::io::Result<()> {
+    let df = df.map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?;
+    let _tmp = tmp_env::set_var("POLARS_FMT_TABLE_HIDE_DATAFRAME_SHAPE_INFORMATION", "1");
+    println!("{df}");
+    Ok(())
+}
+
 pub(super) enum PolarsCommand {
     Help,
     Exit,
     Save(PathBuf),
     Open(PathBuf),
+    Register(String, PathBuf),
+    Unregister(String),
+    Tables,
+    Schema(String),
+    Timer(bool),
     Unknown(String),
 }
 
@@ -115,6 +142,22 @@ impl PolarsCommand {
                 *ctx = serializable_ctx.into();
                 Ok(())
             }
+            PolarsCommand::Register(name, path) => tables::register_file(ctx, name, path)
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e)),
+            PolarsCommand::Unregister(name) => {
+                if !ctx.get_tables().contains(name) {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::NotFound,
+                        format!("no table named '{name}'"),
+                    ));
+                }
+                ctx.unregister(name);
+                Ok(())
+            }
+            PolarsCommand::Tables => print_df(tables::tables_df(ctx)),
+            PolarsCommand::Schema(name) => print_df(tables::schema_df(ctx, name)),
+            // handled by the repl, which owns the timer setting
+            PolarsCommand::Timer(_) => Ok(()),
             PolarsCommand::Unknown(cmd) => {
                 println!(r#"Unknown command: "{cmd}".  Enter ".help" for help"#);
                 Ok(())
@@ -123,6 +166,10 @@ impl PolarsCommand {
     }
 }
 
+fn usage(usage: &str) -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidInput, format!("usage: {usage}"))
+}
+
 impl TryFrom<(&str, &str)> for PolarsCommand {
     type Error = std::io::Error;
 
@@ -134,18 +181,57 @@ impl TryFrom<(&str, &str)> for PolarsCommand {
             ".exit" | ".quit" => Ok(PolarsCommand::Exit),
             ".save" => Ok(PolarsCommand::Save(arg.trim().into())),
             ".open" => Ok(PolarsCommand::Open(arg.trim().into())),
+            ".register" => match arg.trim().split_once(char::is_whitespace) {
+                Some((name, path)) => Ok(PolarsCommand::Register(
+                    name.to_string(),
+                    path.trim().into(),
+                )),
+                None => Err(usage(".register NAME PATH")),
+            },
+            ".unregister" => match arg.trim() {
+                "" => Err(usage(".unregister NAME")),
+                name => Ok(PolarsCommand::Unregister(name.to_string())),
+            },
+            ".tables" => Ok(PolarsCommand::Tables),
+            ".schema" => match arg.trim() {
+                "" => Err(usage(".schema NAME")),
+                name => Ok(PolarsCommand::Schema(name.to_string())),
+            },
+            ".timer" => match arg.trim() {
+                "on" => Ok(PolarsCommand::Timer(true)),
+                "off" => Ok(PolarsCommand::Timer(false)),
+                _ => Err(usage(".timer on|off")),
+            },
             unknown => Ok(PolarsCommand::Unknown(unknown.to_string())),
         }
     }
 }
 
-pub(super) fn run_tty(output_mode: OutputMode) -> std::io::Result<()> {
+pub(super) fn run_tty(output_mode: OutputMode, mut context: SQLContext) -> std::io::Result<()> {
     let history = Box::new(
         FileBackedHistory::with_file(100, get_history_path())
             .expect("Error configuring history with file"),
     );
 
-    let mut line_editor = Reedline::create().with_history(history);
+    // complete with tab, the completions are shown in a menu
+    let completion_tables = CompletionTables::default();
+    completion_tables.update(&context);
+    let completion_menu = ColumnarMenu::default().with_name("completion_menu");
+    let mut keybindings = default_emacs_keybindings();
+    keybindings.add_binding(
+        KeyModifiers::NONE,
+        KeyCode::Tab,
+        ReedlineEvent::UntilFound(vec![
+            ReedlineEvent::Menu("completion_menu".to_string()),
+            ReedlineEvent::MenuNext,
+        ]),
+    );
+
+    let mut line_editor = Reedline::create()
+        .with_history(history)
+        .with_completer(Box::new(SQLCompleter::new(completion_tables.clone())))
+        .with_menu(ReedlineMenu::EngineCompleter(Box::new(completion_menu)))
+        .with_edit_mode(Box::new(Emacs::new(keybindings)));
 
     #[cfg(feature = "highlight")]
     {
@@ -153,13 +239,12 @@ pub(super) fn run_tty(output_mode: OutputMode) -> std::io::Result<()> {
         line_editor = line_editor.with_highlighter(Box::new(sql_highlighter));
     }
 
-    let mut context = SQLContext::new();
-
     println!("Polars CLI v{}", crate_version!());
     println!("Type .help for help.");
 
     let prompt = SQLPrompt {};
     let mut is_exit_cmd = false;
+    let mut timer = false;
     let mut scratch = String::with_capacity(1024);
 
     loop {
@@ -169,15 +254,28 @@ pub(super) fn run_tty(output_mode: OutputMode) -> std::io::Result<()> {
                 is_exit_cmd = false;
                 match buffer.as_str() {
                     special_cmd if buffer.starts_with('.') => {
-                        let cmd: PolarsCommand = special_cmd
+                        let cmd: PolarsCommand = match special_cmd
                             .split_at(special_cmd.find(' ').unwrap_or(special_cmd.len()))
-                            .try_into()?;
-
-                        if let PolarsCommand::Exit = cmd {
-                            break;
+                            .try_into()
+                        {
+                            Ok(cmd) => cmd,
+                            Err(e) => {
+                                eprintln!("Error: {}", e);
+                                continue;
+                            }
                         };
 
-                        cmd.execute_and_print(&mut context)
+                        match cmd {
+                            PolarsCommand::Exit => break,
+                            PolarsCommand::Timer(on) => {
+                                timer = on;
+                                continue;
+                            }
+                            _ => {}
+                        }
+
+                        cmd.execute_and_print(&mut context);
+                        completion_tables.update(&context);
                     }
                     _ => {
                         let mut parts = buffer.splitn(2, ';');
@@ -186,7 +284,12 @@ pub(super) fn run_tty(output_mode: OutputMode) -> std::io::Result<()> {
 
                         let second = parts.next();
                         if second.is_some() {
+                            let start = Instant::now();
                             output_mode.execute_query(&scratch, &mut context);
+                            if timer {
+                                println!("Run Time: {:.3}s", start.elapsed().as_secs_f64());
+                            }
+                            completion_tables.update_after(&context, &scratch);
                             scratch.clear();
                         } else {
                             scratch.push(' ');
@@ -211,6 +314,17 @@ pub(super) fn run_tty(output_mode: OutputMode) -> std::io::Result<()> {
 
 impl PolarsCommand {
     pub(super) fn keywords() -> Vec<&'static str> {
-        vec!["exit", "quit", "save", "open", "help"]
+        vec![
+            "exit",
+            "quit",
+            "save",
+            "open",
+            "register",
+            "unregister",
+            "tables",
+            "schema",
+            "timer",
+            "help",
+        ]
     }
 }
@@ -1,7 +1,10 @@
+mod completer;
 #[cfg(feature = "highlight")]
 mod highlighter;
 mod interactive;
 mod prompt;
+mod script;
+mod tables;
 
 #[cfg(target_os = "linux")]
 use jemallocator::Jemalloc;
@@ -12,12 +15,13 @@ use serde::{Deserialize, Serialize};
 #[cfg(target_os = "linux")]
 static ALLOC: Jemalloc = Jemalloc;
 
-use std::io::{self, BufRead};
+use std::io::{self, BufRead, Read};
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
 use clap::{Parser, ValueEnum};
 use interactive::run_tty;
-use polars::sql::SQLContext;
+use polars::sql::{sink_or_collect, SQLContext};
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about)]
@@ -30,6 +34,17 @@ struct Args {
     #[arg(short = 'o')]
     /// Optional output mode. Defaults to 'table'
     output_mode: Option<OutputMode>,
+    /// Register the file at PATH as table NAME, the format is inferred from the extension.
+    /// Can be given multiple times
+    #[arg(long = "table", value_name = "NAME=PATH", value_parser = tables::parse_table_arg)]
+    tables: Vec<(String, PathBuf)>,
+    /// Run the `;` separated statements of the SQL script FILE in order and exit
+    #[arg(short = 'f', value_name = "FILE")]
+    file: Option<PathBuf>,
+    /// Write the query results to FILE instead of stdout. The format is given by the output
+    /// mode or inferred from the extension
+    #[arg(long = "output", value_name = "FILE")]
+    output: Option<PathBuf>,
 }
 
 #[derive(ValueEnum, Debug, Default, Clone)]
@@ -45,56 +60,114 @@ enum OutputMode {
 }
 
 impl OutputMode {
-    fn execute_query(&self, query: &str, ctx: &mut SQLContext) {
-        let mut execute_inner = || {
-            let mut df = ctx
-                .execute(query)
-                .and_then(|lf| {
-                    if matches!(self, OutputMode::Table | OutputMode::Markdown) {
-                        let max_rows = std::env::var("POLARS_FMT_MAX_ROWS")
-                            .unwrap_or("20".into())
-                            .parse::<IdxSize>()
-                            .unwrap_or(20);
-                        lf.limit(max_rows).collect()
-                    } else {
-                        lf.collect()
-                    }
-                })
-                .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
-
-            let w = io::stdout();
-            let mut w = io::BufWriter::new(w);
+    /// The mode to write a file at `path` in, inferred from its extension.
+    fn from_extension(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "csv" => Some(OutputMode::Csv),
+            "json" | "ndjson" | "jsonl" => Some(OutputMode::Json),
+            "parquet" | "pq" => Some(OutputMode::Parquet),
+            "arrow" | "ipc" | "feather" => Some(OutputMode::Arrow),
+            _ => None,
+        }
+    }
+
+    fn sink_query(&self, query: &str, ctx: &mut SQLContext, path: &Path) {
+        match self.try_sink_query(query, ctx, path) {
+            Ok(_) => {}
+            Err(e) => {
+                eprintln!("Error: {}", e);
+            }
+        }
+    }
+
+    /// Write the result of `query` to the file at `path`. The result is streamed into the
+    /// file if the query can be run in streaming fashion, otherwise it is collected first.
+    fn try_sink_query(&self, query: &str, ctx: &mut SQLContext, path: &Path) -> PolarsResult<()> {
+        let sink = |lf: LazyFrame| {
+            let path = path.to_path_buf();
             match self {
-                OutputMode::Csv => CsvWriter::new(&mut w).finish(&mut df),
-                OutputMode::Json => JsonWriter::new(&mut w).finish(&mut df),
-                OutputMode::Parquet => ParquetWriter::new(&mut w).finish(&mut df).map(|_| ()),
-                OutputMode::Arrow => IpcWriter::new(w).finish(&mut df),
-                OutputMode::Table => {
-                    let _tmp =
-                        tmp_env::set_var("POLARS_FMT_TABLE_HIDE_DATAFRAME_SHAPE_INFORMATION", "1");
-
-                    use std::io::Write;
-                    return write!(&mut w, "{df}");
-                }
-                OutputMode::Markdown => {
-                    let _tmp_env = (
-                        tmp_env::set_var("POLARS_FMT_TABLE_FORMATTING", "ASCII_MARKDOWN"),
-                        tmp_env::set_var("POLARS_FMT_TABLE_HIDE_DATAFRAME_SHAPE_INFORMATION", "1"),
-                    );
-                    use std::io::Write;
-                    return write!(&mut w, "{df}");
+                OutputMode::Csv => lf.sink_csv(
+                    path,
+                    CsvWriterOptions {
+                        maintain_order: true,
+                        ..Default::default()
+                    },
+                ),
+                OutputMode::Json => lf.sink_ndjson(
+                    path,
+                    JsonWriterOptions {
+                        maintain_order: true,
+                    },
+                ),
+                OutputMode::Parquet => lf.sink_parquet(
+                    path,
+                    ParquetWriteOptions {
+                        maintain_order: true,
+                        ..Default::default()
+                    },
+                ),
+                OutputMode::Arrow => lf.sink_ipc(
+                    path,
+                    IpcWriterOptions {
+                        maintain_order: true,
+                        ..Default::default()
+                    },
+                ),
+                OutputMode::Table | OutputMode::Markdown => {
+                    polars_bail!(ComputeError: "output mode {:?} cannot be written to a file", self)
                 }
             }
-            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
         };
+        let lf = ctx.execute(query)?;
+        sink_or_collect(lf, sink)
+    }
 
-        match execute_inner() {
+    fn execute_query(&self, query: &str, ctx: &mut SQLContext) {
+        match self.try_execute_query(query, ctx) {
             Ok(_) => {}
             Err(e) => {
                 eprintln!("Error: {}", e);
             }
         }
     }
+
+    fn try_execute_query(&self, query: &str, ctx: &mut SQLContext) -> PolarsResult<()> {
+        let lf = ctx.execute(query)?;
+        let mut df = if matches!(self, OutputMode::Table | OutputMode::Markdown) {
+            let max_rows = std::env::var("POLARS_FMT_MAX_ROWS")
+                .unwrap_or("20".into())
+                .parse::<IdxSize>()
+                .unwrap_or(20);
+            lf.limit(max_rows).collect()?
+        } else {
+            lf.collect()?
+        };
+
+        let w = io::stdout();
+        let mut w = io::BufWriter::new(w);
+        match self {
+            OutputMode::Csv => CsvWriter::new(&mut w).finish(&mut df),
+            OutputMode::Json => JsonWriter::new(&mut w).finish(&mut df),
+            OutputMode::Parquet => ParquetWriter::new(&mut w).finish(&mut df).map(|_| ()),
+            OutputMode::Arrow => IpcWriter::new(w).finish(&mut df),
+            OutputMode::Table => {
+                let _tmp =
+                    tmp_env::set_var("POLARS_FMT_TABLE_HIDE_DATAFRAME_SHAPE_INFORMATION", "1");
+
+                use std::io::Write;
+                Ok(write!(&mut w, "{df}")?)
+            }
+            OutputMode::Markdown => {
+                let _tmp_env = (
+                    tmp_env::set_var("POLARS_FMT_TABLE_FORMATTING", "ASCII_MARKDOWN"),
+                    tmp_env::set_var("POLARS_FMT_TABLE_HIDE_DATAFRAME_SHAPE_INFORMATION", "1"),
+                );
+                use std::io::Write;
+                Ok(write!(&mut w, "{df}")?)
+            }
+        }
+    }
 }
 
 impl FromStr for OutputMode {
@@ -145,25 +218,101 @@ impl From<SerializableContext> for SQLContext {
 
 pub fn main() -> io::Result<()> {
     let args = Args::parse();
-    let output_mode = args.output_mode.unwrap_or_default();
 
-    if let Some(query) = args.command {
-        let mut context = SQLContext::new();
-        output_mode.execute_query(&query, &mut context);
-        Ok(())
-    } else if let Some(query) = args.query {
-        let mut context = SQLContext::new();
+    let mut context = SQLContext::new();
+    for (name, path) in &args.tables {
+        tables::register_file(&mut context, name, path)
+            .map_err(|e| io::Error::new(io::ErrorKind::Other, e))?;
+    }
+
+    let query = args.command.or(args.query);
+    let output = match args.output {
+        Some(path) => {
+            let output_mode = args
+                .output_mode
+                .clone()
+                .or_else(|| OutputMode::from_extension(&path))
+                .ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!(
+                            "cannot infer the output mode of '{}'; pass it with -o",
+                            path.display()
+                        ),
+                    )
+                })?;
+            Some((output_mode, path))
+        }
+        None => None,
+    };
+
+    if let Some(script) = args.file {
+        let sql = std::fs::read_to_string(&script)?;
+        let output_mode = args.output_mode.unwrap_or_default();
+        run_script(
+            &script.display().to_string(),
+            &sql,
+            &mut context,
+            &output_mode,
+            output.as_ref(),
+        );
+        return Ok(());
+    }
+
+    if let Some((output_mode, path)) = &output {
+        return if let Some(query) = query {
+            output_mode.sink_query(&query, &mut context, path);
+            Ok(())
+        } else if atty::is(atty::Stream::Stdin) {
+            Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "--output requires a query; use COPY ... TO in interactive mode",
+            ))
+        } else {
+            // run the piped statements as a script, so that the file is only written once
+            let mut sql = String::new();
+            io::stdin().read_to_string(&mut sql)?;
+            run_script("<stdin>", &sql, &mut context, output_mode, output.as_ref());
+            Ok(())
+        };
+    }
+
+    let output_mode = args.output_mode.unwrap_or_default();
+    if let Some(query) = query {
         output_mode.execute_query(&query, &mut context);
         Ok(())
     } else if atty::is(atty::Stream::Stdin) {
-        run_tty(output_mode)
+        run_tty(output_mode, context)
     } else {
-        run_noninteractive(output_mode)
+        run_noninteractive(context, |query, ctx| output_mode.execute_query(query, ctx))
     }
 }
 
-fn run_noninteractive(output_mode: OutputMode) -> io::Result<()> {
-    let mut context = SQLContext::new();
+/// Run the statements of a script in order and exit with an error if one of them fails.
+/// If `output` is given, the result of the last statement is written to its file.
+fn run_script(
+    name: &str,
+    sql: &str,
+    context: &mut SQLContext,
+    output_mode: &OutputMode,
+    output: Option<&(OutputMode, PathBuf)>,
+) {
+    let res = script::run_script(sql, context, |query, ctx, is_last| match output {
+        Some((output_mode, path)) if is_last => output_mode.try_sink_query(query, ctx, path),
+        Some(_) => ctx.execute(query).map(|_| ()),
+        None => output_mode.try_execute_query(query, ctx),
+    });
+    if let Err(e) = res {
+        eprintln!("Error: {}:{}", name, e);
+        std::process::exit(1);
+    }
+}
+
+/// Run the `;` separated queries read from stdin.
+fn run_noninteractive<F>(mut context: SQLContext, execute_query: F) -> io::Result<()>
+where
+    F: Fn(&str, &mut SQLContext),
+{
     let mut input: Vec<u8> = Vec::with_capacity(1024);
     let stdin = std::io::stdin();
 
@@ -176,7 +325,7 @@ fn run_noninteractive(output_mode: OutputMode) -> io::Result<()> {
             break;
         }
 
-        output_mode.execute_query(query, &mut context);
+        execute_query(query, &mut context);
     }
 
     Ok(())
@@ -0,0 +1,374 @@
+use std::fmt::{Display, Formatter};
+
+use polars::prelude::*;
+use polars::sql::SQLContext;
+use sqlparser::dialect::GenericDialect;
+use sqlparser::keywords::Keyword;
+use sqlparser::tokenizer::{Location, Token, TokenWithLocation, Tokenizer};
+
+/// An error in a script, at the location of the statement that failed.
+pub(super) struct ScriptError {
+    location: Location,
+    message: String,
+}
+
+impl Display for ScriptError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}:{}: {}",
+            self.location.line, self.location.column, self.message
+        )
+    }
+}
+
+/// A statement of a script. The tokens are stored with their byte offset in the script.
+struct Statement<'a> {
+    sql: &'a str,
+    tokens: Vec<(usize, Token)>,
+    location: Location,
+    end: usize,
+}
+
+impl Statement<'_> {
+    /// The name and value of a `SET name = value` statement.
+    fn as_set_variable(&self) -> Option<(String, String)> {
+        let mut tokens = self.tokens.iter();
+        match &tokens.next()?.1 {
+            Token::Word(w) if w.keyword == Keyword::SET => {}
+            _ => return None,
+        }
+        let name = match &tokens.next()?.1 {
+            Token::Word(w) => w.value.clone(),
+            _ => return None,
+        };
+        if tokens.next()?.1 != Token::Eq {
+            return None;
+        }
+        let (start, _) = tokens.next()?;
+        Some((name, self.sql[*start..self.end].trim().to_string()))
+    }
+
+    /// The SQL of the statement, with the variables replaced by their values.
+    fn to_sql(&self, variables: &PlHashMap<String, String>) -> StatementSql {
+        let mut out = StatementSql {
+            sql: String::new(),
+            parts: vec![],
+        };
+        let mut pos = self.tokens[0].0;
+        for (offset, token) in &self.tokens {
+            if let Token::Placeholder(p) = token {
+                if let Some(value) = p.strip_prefix('$').and_then(|name| variables.get(name)) {
+                    out.parts.push((out.sql.len(), pos, true));
+                    out.sql.push_str(&self.sql[pos..*offset]);
+                    out.parts.push((out.sql.len(), *offset, false));
+                    out.sql.push_str(value);
+                    pos = offset + p.len();
+                }
+            }
+        }
+        out.parts.push((out.sql.len(), pos, true));
+        out.sql.push_str(&self.sql[pos..self.end]);
+        out
+    }
+}
+
+/// The SQL of a statement with its variables replaced by their values.
+struct StatementSql {
+    sql: String,
+    // the parts of `sql`: their offset in `sql`, their offset in the script and whether they
+    // are copied from the script, or are the value of the variable at that offset
+    parts: Vec<(usize, usize, bool)>,
+}
+
+impl StatementSql {
+    /// The byte offset in the script of the byte `offset` of the SQL. An offset in the value
+    /// of a variable is mapped to the variable.
+    fn script_offset(&self, offset: usize) -> usize {
+        let i = self.parts.partition_point(|(start, _, _)| *start <= offset);
+        let (start, script_start, copied) = self.parts[i.saturating_sub(1)];
+        if copied {
+            script_start + (offset - start)
+        } else {
+            script_start
+        }
+    }
+}
+
+/// The byte offset of every line of `sql`.
+fn line_starts(sql: &str) -> Vec<usize> {
+    std::iter::once(0)
+        .chain(sql.match_indices('\n').map(|(i, _)| i + 1))
+        .collect()
+}
+
+/// The byte offset of `location` in `sql`, `line_starts` holds the offset of every line.
+fn byte_offset(sql: &str, line_starts: &[usize], location: &Location) -> usize {
+    let line_start = line_starts[location.line as usize - 1];
+    sql[line_start..]
+        .char_indices()
+        .nth(location.column as usize - 1)
+        .map_or(sql.len(), |(i, _)| line_start + i)
+}
+
+/// The location in the script of the error `message` of `stmt`, which ran as `sql`. The SQL
+/// parser reports a location as `Line: l, Column c` counted from the start of `sql`, other
+/// errors are located at the start of the statement.
+fn error_location(stmt: &Statement, sql: &StatementSql, message: &str) -> Location {
+    let start = Location {
+        line: stmt.location.line,
+        column: stmt.location.column,
+    };
+    let relative = message.rfind("Line: ").and_then(|i| {
+        let (line, column) = message[i + "Line: ".len()..].split_once(", Column ")?;
+        let end = column
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(column.len());
+        Some((
+            line.parse::<u64>().ok()?,
+            column[..end].parse::<u64>().ok()?,
+        ))
+    });
+    let offset = match relative {
+        Some((line, column)) if line > 0 && column > 0 => {
+            let line_starts = line_starts(&sql.sql);
+            if line as usize > line_starts.len() {
+                return start;
+            }
+            byte_offset(&sql.sql, &line_starts, &Location { line, column })
+        }
+        _ => return start,
+    };
+    // the location of the offset in the script
+    let script = &stmt.sql[..sql.script_offset(offset)];
+    let line_start = script.rfind('\n').map_or(0, |i| i + 1);
+    Location {
+        line: script.matches('\n').count() as u64 + 1,
+        column: script[line_start..].chars().count() as u64 + 1,
+    }
+}
+
+/// Split `sql` into its `;` separated statements.
+fn split_statements(sql: &str) -> Result<Vec<Statement>, ScriptError> {
+    let dialect = GenericDialect;
+    let tokens = Tokenizer::new(&dialect, sql)
+        .tokenize_with_location()
+        .map_err(|e| ScriptError {
+            location: Location {
+                line: e.line,
+                column: e.col,
+            },
+            message: e.message,
+        })?;
+    let line_starts = line_starts(sql);
+
+    let mut statements = vec![];
+    let mut current: Option<Statement> = None;
+    for TokenWithLocation { token, location } in tokens {
+        let offset = byte_offset(sql, &line_starts, &location);
+        match token {
+            Token::SemiColon | Token::EOF => {
+                if let Some(mut stmt) = current.take() {
+                    stmt.end = offset;
+                    statements.push(stmt);
+                }
+            }
+            Token::Whitespace(_) => {}
+            token => current
+                .get_or_insert_with(|| Statement {
+                    sql,
+                    tokens: vec![],
+                    location,
+                    end: sql.len(),
+                })
+                .tokens
+                .push((offset, token)),
+        }
+    }
+    statements.extend(current);
+    Ok(statements)
+}
+
+/// Run the statements of the script `sql` in order. `SET name = value` assigns a variable
+/// that replaces `$name` in the statements that follow it. The script stops at the first
+/// statement that fails.
+///
+/// `execute` is called with the SQL of every statement and whether it is the last one.
+pub(super) fn run_script<F>(
+    sql: &str,
+    ctx: &mut SQLContext,
+    mut execute: F,
+) -> Result<(), ScriptError>
+where
+    F: FnMut(&str, &mut SQLContext, bool) -> PolarsResult<()>,
+{
+    let statements = split_statements(sql)?;
+    let mut variables = PlHashMap::new();
+
+    let last = statements
+        .iter()
+        .rposition(|stmt| stmt.as_set_variable().is_none());
+    for (i, stmt) in statements.into_iter().enumerate() {
+        if let Some((name, value)) = stmt.as_set_variable() {
+            variables.insert(name, value);
+            continue;
+        }
+        let query = stmt.to_sql(&variables);
+        execute(&query.sql, ctx, Some(i) == last).map_err(|e| {
+            let message = e.to_string();
+            ScriptError {
+                location: error_location(&stmt, &query, &message),
+                message,
+            }
+        })?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn location(line: u64, column: u64) -> Location {
+        Location { line, column }
+    }
+
+    #[test]
+    fn test_split_statements() {
+        let sql = "SELECT 1;\n  SELECT a,\n  b FROM t;\n\n;SELECT 'a;b' -- c;\n";
+        let statements = split_statements(sql).ok().unwrap();
+        let variables = PlHashMap::new();
+        let sql = statements
+            .iter()
+            .map(|stmt| stmt.to_sql(&variables).sql)
+            .collect::<Vec<_>>();
+        // the `;` in the string and the comment don't end a statement
+        assert_eq!(
+            sql,
+            ["SELECT 1", "SELECT a,\n  b FROM t", "SELECT 'a;b' -- c;\n"]
+        );
+        let locations = statements
+            .iter()
+            .map(|stmt| location(stmt.location.line, stmt.location.column))
+            .collect::<Vec<_>>();
+        assert_eq!(locations, [location(1, 1), location(2, 3), location(5, 2)]);
+    }
+
+    #[test]
+    fn test_set_variable() {
+        let sql =
+            "SET tbl = foods;\nSET n = 2 + 1;\nSELECT * FROM $tbl WHERE x > $n AND y = $other";
+        let statements = split_statements(sql).ok().unwrap();
+        assert_eq!(
+            statements[0].as_set_variable(),
+            Some(("tbl".to_string(), "foods".to_string()))
+        );
+        assert_eq!(
+            statements[1].as_set_variable(),
+            Some(("n".to_string(), "2 + 1".to_string()))
+        );
+        assert_eq!(statements[2].as_set_variable(), None);
+
+        let variables = statements[..2]
+            .iter()
+            .filter_map(|stmt| stmt.as_set_variable())
+            .collect::<PlHashMap<_, _>>();
+        // unknown variables are left to the query
+        assert_eq!(
+            statements[2].to_sql(&variables).sql,
+            "SELECT * FROM foods WHERE x > 2 + 1 AND y = $other"
+        );
+    }
+
+    #[test]
+    fn test_run_script_stops_at_first_error() {
+        let sql = "SET v = 1;\nSELECT $v;\n  SELECT 2;\nSELECT 3";
+        let mut ctx = SQLContext::new();
+        let mut executed = vec![];
+        let err = run_script(sql, &mut ctx, |query, _, is_last| {
+            executed.push((query.to_string(), is_last));
+            if query == "SELECT 2" {
+                polars_bail!(ComputeError: "failed")
+            }
+            Ok(())
+        })
+        .err()
+        .unwrap();
+        assert_eq!(
+            executed,
+            [
+                ("SELECT 1".to_string(), false),
+                ("SELECT 2".to_string(), false)
+            ]
+        );
+        assert_eq!(err.to_string(), "3:3: failed");
+    }
+
+    #[test]
+    fn test_error_location() {
+        let sql = "SELECT 1;\n  SELECT *\n  FROMM t";
+        let statements = split_statements(sql).ok().unwrap();
+        let stmt = &statements[1];
+        let query = stmt.to_sql(&PlHashMap::new());
+        // a location on the first line is shifted by the column of the statement
+        assert_eq!(
+            error_location(
+                stmt,
+                &query,
+                "Expected end of statement at Line: 1, Column 8"
+            ),
+            location(2, 10)
+        );
+        assert_eq!(
+            error_location(
+                stmt,
+                &query,
+                "Expected end of statement at Line: 2, Column 3"
+            ),
+            location(3, 3)
+        );
+        assert_eq!(
+            error_location(stmt, &query, "table 't' not found"),
+            location(2, 3)
+        );
+    }
+
+    #[test]
+    fn test_error_location_after_substitution() {
+        let sql = "SET v = 12345;\nSELECT $v + x FROM t";
+        let statements = split_statements(sql).ok().unwrap();
+        let variables = statements[..1]
+            .iter()
+            .filter_map(|stmt| stmt.as_set_variable())
+            .collect::<PlHashMap<_, _>>();
+        let stmt = &statements[1];
+        let query = stmt.to_sql(&variables);
+        assert_eq!(query.sql, "SELECT 12345 + x FROM t");
+        // after the value, the location is shifted by the difference in length
+        assert_eq!(
+            error_location(
+                stmt,
+                &query,
+                "Expected end of statement at Line: 1, Column 16"
+            ),
+            location(2, 13)
+        );
+        // a location in the value is the location of the variable
+        assert_eq!(
+            error_location(
+                stmt,
+                &query,
+                "Expected end of statement at Line: 1, Column 10"
+            ),
+            location(2, 8)
+        );
+        assert_eq!(
+            error_location(
+                stmt,
+                &query,
+                "Expected end of statement at Line: 1, Column 3"
+            ),
+            location(2, 3)
+        );
+    }
+}
@@ -0,0 +1,71 @@
+use std::path::{Path, PathBuf};
+
+use polars::prelude::*;
+use polars::sql::SQLContext;
+
+/// Lazily scan `path`, the format is inferred from the file extension.
+pub(super) fn scan_file(path: &Path) -> PolarsResult<LazyFrame> {
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_ascii_lowercase())
+        .unwrap_or_default();
+
+    match extension.as_str() {
+        "csv" => LazyCsvReader::new(path).finish(),
+        "tsv" => LazyCsvReader::new(path).with_delimiter(b'\t').finish(),
+        #[cfg(feature = "parquet")]
+        "parquet" | "pq" => LazyFrame::scan_parquet(path, Default::default()),
+        #[cfg(feature = "ipc")]
+        "ipc" | "arrow" | "feather" => LazyFrame::scan_ipc(path, Default::default()),
+        #[cfg(feature = "json")]
+        "ndjson" | "jsonl" => LazyJsonLineReader::new(path).finish(),
+        #[cfg(feature = "avro")]
+        "avro" => LazyAvroReader::new(path).finish(),
+        _ => polars_bail!(
+            ComputeError: "cannot infer the file format of '{}' from its extension",
+            path.display()
+        ),
+    }
+}
+
+/// Register the file at `path` as table `name`.
+pub(super) fn register_file(ctx: &mut SQLContext, name: &str, path: &Path) -> PolarsResult<()> {
+    let lf = scan_file(path)?;
+    ctx.register(name, lf);
+    Ok(())
+}
+
+/// Parse a `NAME=PATH` table argument.
+pub(super) fn parse_table_arg(arg: &str) -> Result<(String, PathBuf), String> {
+    match arg.split_once('=') {
+        Some((name, path)) if !name.is_empty() && !path.is_empty() => {
+            Ok((name.to_string(), path.into()))
+        }
+        _ => Err(format!("expected NAME=PATH, got '{arg}'")),
+    }
+}
+
+/// The names of the registered tables.
+pub(super) fn tables_df(ctx: &SQLContext) -> PolarsResult<DataFrame> {
+    df! {
+        "table" => ctx.get_tables(),
+    }
+}
+
+/// The columns and data types of table `name`.
+pub(super) fn schema_df(ctx: &SQLContext, name: &str) -> PolarsResult<DataFrame> {
+    let lf = ctx
+        .get_table_map()
+        .remove(name)
+        .ok_or_else(|| polars_err!(ComputeError: "no table named '{}'", name))?;
+    let schema = lf.schema()?;
+    let (columns, dtypes): (Vec<_>, Vec<_>) = schema
+        .iter()
+        .map(|(name, dtype)| (name.to_string(), dtype.to_string()))
+        .unzip();
+    df! {
+        "column" => columns,
+        "dtype" => dtypes,
+    }
+}
@@ -89,6 +89,7 @@ pub(crate) fn encode_rows_vertical(by: &[Series]) -> PolarsResult<BinaryChunked>
     let len = by[0].len();
     let splits = _split_offsets(len, n_threads);
     let descending = vec![false; by.len()];
+    let nulls_last = vec![false; by.len()];
 
     let chunks = splits
         .into_par_iter()
@@ -97,7 +98,7 @@ pub(crate) fn encode_rows_vertical(by: &[Series]) -> PolarsResult<BinaryChunked>
                 .iter()
                 .map(|s| s.slice(offset as i64, len))
                 .collect::<Vec<_>>();
-            let rows = _get_rows_encoded(&sliced, &descending, false)?;
+            let rows = _get_rows_encoded(&sliced, &descending, &nulls_last)?;
             Ok(Box::new(rows.into_array()) as ArrayRef)
         })
         .collect::<PolarsResult<_>>()?;
@@ -108,17 +109,18 @@ pub(crate) fn encode_rows_vertical(by: &[Series]) -> PolarsResult<BinaryChunked>
 pub fn _get_rows_encoded(
     by: &[Series],
     descending: &[bool],
-    nulls_last: bool,
+    nulls_last: &[bool],
 ) -> PolarsResult<RowsEncoded> {
     debug_assert_eq!(by.len(), descending.len());
+    debug_assert_eq!(by.len(), nulls_last.len());
     let mut cols = Vec::with_capacity(by.len());
     let mut fields = Vec::with_capacity(by.len());
-    for (by, descending) in by.iter().zip(descending) {
+    for ((by, descending), nulls_last) in by.iter().zip(descending).zip(nulls_last) {
         let arr = _get_rows_encoded_compat_array(by)?;
 
         let sort_field = SortField {
             descending: *descending,
-            nulls_last,
+            nulls_last: *nulls_last,
         };
         match arr.data_type() {
             // flatten the struct fields
@@ -142,7 +144,7 @@ pub fn _get_rows_encoded_ca(
     name: &str,
     by: &[Series],
     descending: &[bool],
-    nulls_last: bool,
+    nulls_last: &[bool],
 ) -> PolarsResult<BinaryChunked> {
     _get_rows_encoded(by, descending, nulls_last)
         .map(|rows| unsafe { BinaryChunked::from_chunks(name, vec![Box::new(rows.into_array())]) })
@@ -151,10 +153,11 @@ pub fn _get_rows_encoded_ca(
 pub(crate) fn argsort_multiple_row_fmt(
     by: &[Series],
     mut descending: Vec<bool>,
-    nulls_last: bool,
+    mut nulls_last: Vec<bool>,
     parallel: bool,
 ) -> PolarsResult<IdxCa> {
     _broadcast_descending(by.len(), &mut descending);
+    _broadcast_descending(by.len(), &mut nulls_last);
 
     let rows_encoded = _get_rows_encoded(by, &descending, nulls_last)?;
     let mut items: Vec<_> = rows_encoded.iter().enumerate_idx().collect();
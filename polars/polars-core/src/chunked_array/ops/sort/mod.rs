@@ -591,7 +591,7 @@ impl StructChunked {
             self.name(),
             &[self.clone().into_series()],
             &[options.descending],
-            options.nulls_last,
+            &[options.nulls_last],
         )
         .unwrap();
         bin.arg_sort(Default::default())
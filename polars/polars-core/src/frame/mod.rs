@@ -1846,17 +1846,27 @@ impl DataFrame {
         let by_column = self.select_series(by_column)?;
         let descending = descending.into_vec();
         self.columns = self
-            .sort_impl(by_column, descending, false, maintain_order, None, true)?
+            .sort_impl(
+                by_column,
+                descending,
+                vec![false],
+                maintain_order,
+                None,
+                true,
+            )?
             .columns;
         Ok(self)
     }
 
     /// This is the dispatch of Self::sort, and exists to reduce compile bloat by monomorphization.
+    ///
+    /// `descending` and `nulls_last` hold a value per column in `by_column`, or a single value
+    /// that applies to all of them.
     pub fn sort_impl(
         &self,
         by_column: Vec<Series>,
         descending: Vec<bool>,
-        nulls_last: bool,
+        nulls_last: Vec<bool>,
         maintain_order: bool,
         slice: Option<(i64, usize)>,
         parallel: bool,
@@ -1866,6 +1876,12 @@ impl DataFrame {
 
         // therefore when we try to set the first columns as sorted, we ignore the error
         // as expressions are not present (they are renamed to _POLARS_SORT_COLUMN_i.
+        polars_ensure!(
+            nulls_last.len() == 1 || nulls_last.len() == by_column.len(),
+            ComputeError:
+            "the length of `nulls_last` ({}) does not match the number of sort columns ({})",
+            nulls_last.len(), by_column.len(),
+        );
         let first_descending = descending[0];
         let first_by_column = by_column[0].name().to_string();
 
@@ -1912,7 +1928,7 @@ impl DataFrame {
                 let s = &by_column[0];
                 let options = SortOptions {
                     descending: descending[0],
-                    nulls_last,
+                    nulls_last: nulls_last[0],
                     multithreaded: parallel,
                     maintain_order,
                 };
@@ -1930,7 +1946,10 @@ impl DataFrame {
                 s.arg_sort(options)
             }
             _ => {
-                if nulls_last || has_struct || std::env::var("POLARS_ROW_FMT_SORT").is_ok() {
+                if nulls_last.iter().any(|nulls_last| *nulls_last)
+                    || has_struct
+                    || std::env::var("POLARS_ROW_FMT_SORT").is_ok()
+                {
                     argsort_multiple_row_fmt(&by_column, descending, nulls_last, parallel)?
                 } else {
                     let (first, other, descending) = prepare_arg_sort(by_column, descending)?;
@@ -1989,7 +2008,7 @@ impl DataFrame {
             .sort_impl(
                 by_column,
                 descending,
-                options.nulls_last,
+                vec![options.nulls_last],
                 options.maintain_order,
                 None,
                 options.multithreaded,
@@ -3628,6 +3647,25 @@ mod test {
         assert!(df.frame_equal(&valid));
     }
 
+    #[test]
+    fn test_sort_nulls_last_length() {
+        let df = df! {
+            "a" => [Some(1), None, Some(1)],
+            "b" => [Some(2), Some(1), None]
+        }
+        .unwrap();
+        let by = || df.get_columns().to_vec();
+        assert!(df
+            .sort_impl(by(), vec![false], vec![], false, None, false)
+            .is_err());
+        assert!(df
+            .sort_impl(by(), vec![false], vec![true; 3], false, None, false)
+            .is_err());
+        assert!(df
+            .sort_impl(by(), vec![false], vec![true, false], false, None, false)
+            .is_ok());
+    }
+
     #[test]
     fn test_vstack() {
         // check that it does not accidentally rechunks
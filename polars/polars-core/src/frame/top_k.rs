@@ -46,7 +46,7 @@ impl DataFrame {
     ) -> PolarsResult<DataFrame> {
         let by_column = self.select_series(by_column)?;
         let descending = descending.into_vec();
-        self.top_k_impl(k, descending, by_column, false, false)
+        self.top_k_impl(k, descending, by_column, vec![false], false)
     }
 
     pub(crate) fn top_k_impl(
@@ -54,10 +54,11 @@ impl DataFrame {
         k: usize,
         mut descending: Vec<bool>,
         by_column: Vec<Series>,
-        nulls_last: bool,
+        mut nulls_last: Vec<bool>,
         maintain_order: bool,
     ) -> PolarsResult<DataFrame> {
         _broadcast_descending(by_column.len(), &mut descending);
+        _broadcast_descending(by_column.len(), &mut nulls_last);
         let encoded = _get_rows_encoded(&by_column, &descending, nulls_last)?;
         let arr = encoded.into_array();
         let mut rows = arr
@@ -366,7 +366,7 @@ impl SeriesTrait for SeriesWrap<StructChunked> {
             .sort_impl(
                 df.columns.clone(),
                 desc,
-                options.nulls_last,
+                vec![options.nulls_last; df.width()],
                 options.maintain_order,
                 None,
                 options.multithreaded,
@@ -95,8 +95,29 @@ where
         self
     }
 
-    fn finish(mut self) -> PolarsResult<DataFrame> {
+    fn finish(self) -> PolarsResult<DataFrame> {
         let rechunk = self.rechunk;
+        let n_rows = self.n_rows;
+        let (avro_reader, projected_schema) = self.into_arrow_reader()?;
+
+        finish_reader(avro_reader, rechunk, n_rows, None, &projected_schema, None)
+    }
+}
+
+impl<R: Read + Seek> AvroReader<R> {
+    /// Create a [`BatchedAvroReader`] that reads the file one avro block at a time.
+    pub fn batched(self) -> PolarsResult<BatchedAvroReader<R>> {
+        let n_rows = self.n_rows;
+        let (reader, schema) = self.into_arrow_reader()?;
+        Ok(BatchedAvroReader {
+            reader,
+            schema,
+            n_rows,
+            rows_read: 0,
+        })
+    }
+
+    fn into_arrow_reader(mut self) -> PolarsResult<(read::Reader<R>, ArrowSchema)> {
         let metadata =
             avro::avro_schema::read::read_metadata(&mut self.reader).map_err(to_compute_err)?;
         let schema = read::infer_schema(&metadata.record)?;
@@ -115,16 +136,39 @@ where
             (None, schema.clone())
         };
 
-        let avro_reader =
-            avro::read::Reader::new(&mut self.reader, metadata, schema.fields, projection);
-
-        finish_reader(
-            avro_reader,
-            rechunk,
-            self.n_rows,
-            None,
-            &projected_schema,
-            None,
-        )
+        let avro_reader = avro::read::Reader::new(self.reader, metadata, schema.fields, projection);
+        Ok((avro_reader, projected_schema))
+    }
+}
+
+/// Reads an avro file in batches, see [`AvroReader::batched`].
+pub struct BatchedAvroReader<R> {
+    reader: read::Reader<R>,
+    schema: ArrowSchema,
+    n_rows: Option<usize>,
+    rows_read: usize,
+}
+
+impl<R: Read + Seek> BatchedAvroReader<R> {
+    /// Read the next `n` avro blocks. Returns `None` if the file is exhausted
+    /// or `n_rows` rows have been read.
+    pub fn next_batches(&mut self, n: usize) -> PolarsResult<Option<Vec<DataFrame>>> {
+        let mut dfs = Vec::with_capacity(n);
+        for batch in (&mut self.reader).take(n) {
+            let remaining_rows = match self.n_rows {
+                Some(n_rows) => n_rows - self.rows_read,
+                None => usize::MAX,
+            };
+            if remaining_rows == 0 {
+                break;
+            }
+            let mut df = DataFrame::try_from((batch?, self.schema.fields.as_slice()))?;
+            if df.height() > remaining_rows {
+                df = df.slice(0, remaining_rows);
+            }
+            self.rows_read += df.height();
+            dfs.push(df);
+        }
+        Ok(if dfs.is_empty() { None } else { Some(dfs) })
     }
 }
@@ -62,7 +62,8 @@ use rayon::prelude::*;
 pub use read::{CsvEncoding, CsvReader, NullValues};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
-pub use write::CsvWriter;
+pub use write::{BatchedWriter, CsvWriter};
+pub use write_impl::SerializeOptions;
 
 use crate::csv::read_impl::CoreReader;
 use crate::csv::utils::get_reader_bytes;
@@ -104,4 +104,42 @@ where
         self.options.null = null_value;
         self
     }
+
+    /// Create a [`BatchedWriter`] that writes the header (if set) and then
+    /// serializes [`DataFrame`]s one batch at a time.
+    pub fn batched(mut self, schema: &Schema) -> PolarsResult<BatchedWriter<W>> {
+        if self.header {
+            let names = schema
+                .iter_names()
+                .map(|name| name.as_str())
+                .collect::<Vec<_>>();
+            write_impl::write_header(&mut self.buffer, &names, &self.options)?;
+        }
+        Ok(BatchedWriter { writer: self })
+    }
+}
+
+pub struct BatchedWriter<W: Write> {
+    writer: CsvWriter<W>,
+}
+
+impl<W: Write> BatchedWriter<W> {
+    /// Write a batch to the csv writer.
+    ///
+    /// The header is written by [`CsvWriter::batched`], so the columns of `df`
+    /// must be in the order of the schema given there.
+    pub fn write_batch(&mut self, df: &DataFrame) -> PolarsResult<()> {
+        write_impl::write(
+            &mut self.writer.buffer,
+            df,
+            self.writer.batch_size,
+            &self.writer.options,
+        )
+    }
+
+    /// Flush the underlying writer.
+    pub fn finish(&mut self) -> PolarsResult<()> {
+        self.writer.buffer.flush()?;
+        Ok(())
+    }
 }
@@ -117,6 +117,45 @@ impl<W: Write> JsonWriter<W> {
         self.json_format = format;
         self
     }
+
+    /// Create a [`BatchedWriter`] that serializes [`DataFrame`]s as JSON Lines one batch
+    /// at a time. Only [`JsonFormat::JsonLines`] can be written in batches.
+    pub fn batched(self, _schema: &Schema) -> PolarsResult<BatchedWriter<W>> {
+        polars_ensure!(
+            matches!(self.json_format, JsonFormat::JsonLines),
+            ComputeError: "only the JSON Lines format can be written in batches"
+        );
+        Ok(BatchedWriter {
+            writer: self.buffer,
+        })
+    }
+}
+
+pub struct BatchedWriter<W: Write> {
+    writer: W,
+}
+
+impl<W: Write> BatchedWriter<W> {
+    /// Write a batch to the ndjson writer.
+    ///
+    /// # Panics
+    /// The caller must ensure the chunks in the given [`DataFrame`] are aligned.
+    pub fn write_batch(&mut self, df: &DataFrame) -> PolarsResult<()> {
+        let fields = df.iter().map(|s| s.field().to_arrow()).collect::<Vec<_>>();
+        let batches = df
+            .iter_chunks()
+            .map(|chunk| Ok(Box::new(chunk_to_struct(chunk, fields.clone())) as ArrayRef));
+        let serializer = arrow_ndjson::write::Serializer::new(batches, vec![]);
+        let writer = arrow_ndjson::write::FileWriter::new(&mut self.writer, serializer);
+        writer.collect::<ArrowResult<()>>()?;
+        Ok(())
+    }
+
+    /// Flush the underlying writer.
+    pub fn finish(&mut self) -> PolarsResult<()> {
+        self.writer.flush()?;
+        Ok(())
+    }
 }
 
 impl<W> SerWriter<W> for JsonWriter<W>
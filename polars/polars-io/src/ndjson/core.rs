@@ -95,6 +95,119 @@ impl<'a> JsonLineReader<'a, File> {
         let f = std::fs::File::open(&path)?;
         Ok(Self::new(f).with_path(Some(path)))
     }
+
+    /// Create a [`BatchedJsonLineReader`] that parses the file in batches of roughly
+    /// `chunk_size` rows. This does not rechunk and infers the schema if none was set.
+    pub fn batched(self) -> PolarsResult<BatchedJsonLineReader> {
+        let mmap = unsafe { memmap::Mmap::map(&self.reader)? };
+        let schema = resolve_schema(
+            &mmap,
+            self.schema,
+            self.schema_overwrite,
+            self.infer_schema_len,
+        )?;
+        let bytes_per_row = get_line_stats_json(&mmap, 1024)
+            .map(|(mean, _)| mean)
+            .filter(|mean| mean.is_finite() && *mean > 0.0)
+            .unwrap_or(128.0);
+
+        Ok(BatchedJsonLineReader {
+            mmap,
+            offset: 0,
+            schema,
+            chunk_size: self.chunk_size,
+            bytes_per_row,
+            n_rows: self.n_rows,
+            rows_read: 0,
+            ignore_errors: self.ignore_errors,
+        })
+    }
+}
+
+/// Reads a JSON Lines file in batches, see [`JsonLineReader::batched`].
+pub struct BatchedJsonLineReader {
+    mmap: memmap::Mmap,
+    offset: usize,
+    schema: SchemaRef,
+    chunk_size: usize,
+    bytes_per_row: f32,
+    n_rows: Option<usize>,
+    rows_read: usize,
+    ignore_errors: bool,
+}
+
+impl BatchedJsonLineReader {
+    pub fn schema(&self) -> &SchemaRef {
+        &self.schema
+    }
+
+    /// Parse the next `n` batches in parallel. Returns `None` if the file is exhausted
+    /// or `n_rows` rows have been read.
+    pub fn next_batches(&mut self, n: usize) -> PolarsResult<Option<Vec<DataFrame>>> {
+        let bytes: &[u8] = &self.mmap;
+        let remaining_rows = match self.n_rows {
+            Some(n_rows) => n_rows.saturating_sub(self.rows_read),
+            None => usize::MAX,
+        };
+        if self.offset >= bytes.len() || remaining_rows == 0 {
+            return Ok(None);
+        }
+
+        let bytes_per_chunk =
+            std::cmp::max((self.bytes_per_row * self.chunk_size as f32) as usize, 1);
+        let mut file_chunks = Vec::with_capacity(n);
+        while file_chunks.len() < n && self.offset < bytes.len() {
+            let search_pos = self.offset + bytes_per_chunk;
+            let end_pos = if search_pos >= bytes.len() {
+                bytes.len()
+            } else {
+                next_record_end(&bytes[search_pos..])
+                    .map(|pos| search_pos + pos)
+                    .unwrap_or(bytes.len())
+            };
+            file_chunks.push((self.offset, end_pos));
+            self.offset = end_pos;
+        }
+
+        let schema = &self.schema;
+        let capacity = self.chunk_size;
+        let ignore_errors = self.ignore_errors;
+        let dfs = POOL.install(|| {
+            file_chunks
+                .into_par_iter()
+                .map(|(start_pos, stop_at_nbytes)| {
+                    let mut buffers = init_buffers(schema, capacity, ignore_errors)?;
+                    parse_lines(&bytes[start_pos..stop_at_nbytes], &mut buffers)?;
+                    DataFrame::new(
+                        buffers
+                            .into_values()
+                            .map(|buf| buf.into_series())
+                            .collect::<_>(),
+                    )
+                })
+                .collect::<PolarsResult<Vec<_>>>()
+        })?;
+
+        // stop exactly at `n_rows`
+        let mut remaining_rows = remaining_rows;
+        let dfs = dfs
+            .into_iter()
+            .filter_map(|df| {
+                if remaining_rows == 0 {
+                    return None;
+                }
+                let df = if df.height() > remaining_rows {
+                    df.slice(0, remaining_rows)
+                } else {
+                    df
+                };
+                remaining_rows -= df.height();
+                Some(df)
+            })
+            .collect::<Vec<_>>();
+        self.rows_read += dfs.iter().map(|df| df.height()).sum::<usize>();
+        Ok(Some(dfs))
+    }
 }
 impl<'a, R> SerReader<R> for JsonLineReader<'a, R>
 where
@@ -164,24 +277,7 @@ impl<'a> CoreJsonReader<'a> {
         infer_schema_len: Option<usize>,
         ignore_errors: bool,
     ) -> PolarsResult<CoreJsonReader<'a>> {
-        let reader_bytes = reader_bytes;
-
-        let mut schema = match schema {
-            Some(schema) => schema,
-            None => {
-                let bytes: &[u8] = &reader_bytes;
-                let mut cursor = Cursor::new(bytes);
-
-                let data_type = polars_json::ndjson::infer(&mut cursor, infer_schema_len)?;
-                let schema = StructArray::get_fields(&data_type).iter().collect();
-
-                Arc::new(schema)
-            }
-        };
-        if let Some(overwriting_schema) = schema_overwrite {
-            let schema = Arc::make_mut(&mut schema);
-            overwrite_schema(schema, overwriting_schema)?;
-        }
+        let schema = resolve_schema(&reader_bytes, schema, schema_overwrite, infer_schema_len)?;
 
         Ok(CoreJsonReader {
             reader_bytes: Some(reader_bytes),
@@ -263,6 +359,31 @@ impl<'a> CoreJsonReader<'a> {
     }
 }
 
+/// Use the given schema or infer it from `bytes`, and apply the overwrites.
+fn resolve_schema(
+    bytes: &[u8],
+    schema: Option<SchemaRef>,
+    schema_overwrite: Option<&Schema>,
+    infer_schema_len: Option<usize>,
+) -> PolarsResult<SchemaRef> {
+    let mut schema = match schema {
+        Some(schema) => schema,
+        None => {
+            let mut cursor = Cursor::new(bytes);
+
+            let data_type = polars_json::ndjson::infer(&mut cursor, infer_schema_len)?;
+            let schema = StructArray::get_fields(&data_type).iter().collect();
+
+            Arc::new(schema)
+        }
+    };
+    if let Some(overwriting_schema) = schema_overwrite {
+        let schema = Arc::make_mut(&mut schema);
+        overwrite_schema(schema, overwriting_schema)?;
+    }
+    Ok(schema)
+}
+
 #[inline(always)]
 fn parse_impl(
     bytes: &[u8],
@@ -338,6 +459,24 @@ pub(crate) fn next_line_position_naive_json(input: &[u8]) -> Option<usize> {
     }
 }
 
+/// Find the position just past the next line that ends a record (`}\n` or `}\r\n`).
+/// Unlike [`next_line_position_naive_json`] this keeps searching past other newlines.
+fn next_record_end(input: &[u8]) -> Option<usize> {
+    let mut offset = 0;
+    while let Some(pos) = memchr::memchr(NEWLINE, &input[offset..]) {
+        let pos = offset + pos;
+        let line = match &input[..pos] {
+            [line @ .., RETURN] => line,
+            line => line,
+        };
+        if line.last() == Some(&CLOSING_BRACKET) {
+            return Some(pos + 1);
+        }
+        offset = pos + 1;
+    }
+    None
+}
+
 /// Get the mean and standard deviation of length of lines in bytes
 pub(crate) fn get_line_stats_json(bytes: &[u8], n_lines: usize) -> Option<(f32, f32)> {
     let mut lengths = Vec::with_capacity(n_lines);
@@ -4,18 +4,27 @@ use std::thread::JoinHandle;
 
 use crossbeam_channel::{bounded, Receiver, Sender};
 use polars_core::prelude::*;
+#[cfg(feature = "csv")]
+use polars_io::csv::CsvWriter;
+#[cfg(feature = "json")]
+use polars_io::json::{JsonFormat, JsonWriter};
 #[cfg(feature = "parquet")]
 use polars_io::parquet::ParquetWriter;
 #[cfg(feature = "ipc")]
 use polars_io::prelude::IpcWriter;
-#[cfg(feature = "ipc")]
+#[cfg(any(feature = "ipc", feature = "csv", feature = "json"))]
 use polars_io::SerWriter;
 use polars_plan::prelude::*;
 
 use crate::operators::{DataChunk, FinalizedSink, PExecutionContext, Sink, SinkResult};
 use crate::pipeline::morsels_per_sink;
 
-#[cfg(any(feature = "parquet", feature = "ipc"))]
+#[cfg(any(
+    feature = "parquet",
+    feature = "ipc",
+    feature = "csv",
+    feature = "json"
+))]
 trait SinkWriter {
     fn _write_batch(&mut self, df: &DataFrame) -> PolarsResult<()>;
     fn _finish(&mut self) -> PolarsResult<()>;
@@ -45,6 +54,30 @@ impl SinkWriter for polars_io::ipc::BatchedWriter<std::fs::File> {
     }
 }
 
+#[cfg(feature = "csv")]
+impl SinkWriter for polars_io::csv::BatchedWriter<std::fs::File> {
+    fn _write_batch(&mut self, df: &DataFrame) -> PolarsResult<()> {
+        self.write_batch(df)
+    }
+
+    fn _finish(&mut self) -> PolarsResult<()> {
+        self.finish()?;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "json")]
+impl SinkWriter for polars_io::json::BatchedWriter<std::fs::File> {
+    fn _write_batch(&mut self, df: &DataFrame) -> PolarsResult<()> {
+        self.write_batch(df)
+    }
+
+    fn _finish(&mut self) -> PolarsResult<()> {
+        self.finish()?;
+        Ok(())
+    }
+}
+
 #[cfg(feature = "parquet")]
 pub struct ParquetSink {}
 #[cfg(feature = "parquet")]
@@ -55,37 +88,30 @@ impl ParquetSink {
         options: ParquetWriteOptions,
         schema: &Schema,
     ) -> PolarsResult<FilesSink> {
-        let file = std::fs::File::create(path)?;
-        let writer = ParquetWriter::new(file)
-            .with_compression(options.compression)
-            .with_data_pagesize_limit(options.data_pagesize_limit)
-            .with_statistics(options.statistics)
-            .with_row_group_size(options.row_group_size)
-            // This is important! Otherwise we will deadlock
-            // See: #7074
-            .set_parallel(false)
-            .batched(schema)?;
-
+        let writer = create_parquet_writer(path, &options, schema)?;
         let writer = Box::new(writer) as Box<dyn SinkWriter + Send + Sync>;
-
-        let morsels_per_sink = morsels_per_sink();
-        let backpressure = morsels_per_sink * 2;
-        let (sender, receiver) = bounded(backpressure);
-
-        let io_thread_handle = Arc::new(Some(init_writer_thread(
-            receiver,
-            writer,
-            options.maintain_order,
-            morsels_per_sink,
-        )));
-
-        Ok(FilesSink {
-            sender,
-            io_thread_handle,
-        })
+        Ok(FilesSink::new(writer, options.maintain_order))
     }
 }
 
+#[cfg(feature = "parquet")]
+pub(super) fn create_parquet_writer(
+    path: &Path,
+    options: &ParquetWriteOptions,
+    schema: &Schema,
+) -> PolarsResult<polars_io::parquet::BatchedWriter<std::fs::File>> {
+    let file = std::fs::File::create(path)?;
+    ParquetWriter::new(file)
+        .with_compression(options.compression)
+        .with_data_pagesize_limit(options.data_pagesize_limit)
+        .with_statistics(options.statistics)
+        .with_row_group_size(options.row_group_size)
+        // This is important! Otherwise we will deadlock
+        // See: #7074
+        .set_parallel(false)
+        .batched(schema)
+}
+
 #[cfg(feature = "ipc")]
 pub struct IpcSink {}
 #[cfg(feature = "ipc")]
@@ -98,26 +124,61 @@ impl IpcSink {
             .batched(schema)?;
 
         let writer = Box::new(writer) as Box<dyn SinkWriter + Send + Sync>;
+        Ok(FilesSink::new(writer, options.maintain_order))
+    }
+}
 
-        let morsels_per_sink = morsels_per_sink();
-        let backpressure = morsels_per_sink * 2;
-        let (sender, receiver) = bounded(backpressure);
+#[cfg(feature = "csv")]
+pub struct CsvSink {}
+#[cfg(feature = "csv")]
+impl CsvSink {
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(path: &Path, options: CsvWriterOptions, schema: &Schema) -> PolarsResult<FilesSink> {
+        let file = std::fs::File::create(path)?;
+        let serialize_options = options.serialize_options;
+        let writer = CsvWriter::new(file)
+            .has_header(options.include_header)
+            .with_batch_size(options.batch_size)
+            .with_delimiter(serialize_options.delimiter)
+            .with_quoting_char(serialize_options.quote)
+            .with_null_value(serialize_options.null)
+            .with_date_format(serialize_options.date_format)
+            .with_time_format(serialize_options.time_format)
+            .with_datetime_format(serialize_options.datetime_format)
+            .with_float_precision(serialize_options.float_precision)
+            .batched(schema)?;
 
-        let io_thread_handle = Arc::new(Some(init_writer_thread(
-            receiver,
-            writer,
-            options.maintain_order,
-            morsels_per_sink,
-        )));
+        let writer = Box::new(writer) as Box<dyn SinkWriter + Send + Sync>;
+        Ok(FilesSink::new(writer, options.maintain_order))
+    }
+}
 
-        Ok(FilesSink {
-            sender,
-            io_thread_handle,
-        })
+#[cfg(feature = "json")]
+pub struct JsonSink {}
+#[cfg(feature = "json")]
+impl JsonSink {
+    #[allow(clippy::new_ret_no_self)]
+    pub fn new(
+        path: &Path,
+        options: JsonWriterOptions,
+        schema: &Schema,
+    ) -> PolarsResult<FilesSink> {
+        let file = std::fs::File::create(path)?;
+        let writer = JsonWriter::new(file)
+            .with_json_format(JsonFormat::JsonLines)
+            .batched(schema)?;
+
+        let writer = Box::new(writer) as Box<dyn SinkWriter + Send + Sync>;
+        Ok(FilesSink::new(writer, options.maintain_order))
     }
 }
 
-#[cfg(any(feature = "parquet", feature = "ipc"))]
+#[cfg(any(
+    feature = "parquet",
+    feature = "ipc",
+    feature = "csv",
+    feature = "json"
+))]
 fn init_writer_thread(
     receiver: Receiver<Option<DataChunk>>,
     mut writer: Box<dyn SinkWriter + Send + Sync>,
@@ -164,13 +225,49 @@ fn init_writer_thread(
 
 // Ensure the data is return in the order it was streamed
 #[derive(Clone)]
-#[cfg(any(feature = "parquet", feature = "ipc"))]
+#[cfg(any(
+    feature = "parquet",
+    feature = "ipc",
+    feature = "csv",
+    feature = "json"
+))]
 pub struct FilesSink {
     sender: Sender<Option<DataChunk>>,
     io_thread_handle: Arc<Option<JoinHandle<()>>>,
 }
 
-#[cfg(any(feature = "parquet", feature = "ipc"))]
+#[cfg(any(
+    feature = "parquet",
+    feature = "ipc",
+    feature = "csv",
+    feature = "json"
+))]
+impl FilesSink {
+    fn new(writer: Box<dyn SinkWriter + Send + Sync>, maintain_order: bool) -> Self {
+        let morsels_per_sink = morsels_per_sink();
+        let backpressure = morsels_per_sink * 2;
+        let (sender, receiver) = bounded(backpressure);
+
+        let io_thread_handle = Arc::new(Some(init_writer_thread(
+            receiver,
+            writer,
+            maintain_order,
+            morsels_per_sink,
+        )));
+
+        FilesSink {
+            sender,
+            io_thread_handle,
+        }
+    }
+}
+
+#[cfg(any(
+    feature = "parquet",
+    feature = "ipc",
+    feature = "csv",
+    feature = "json"
+))]
 impl Sink for FilesSink {
     fn sink(&mut self, _context: &PExecutionContext, chunk: DataChunk) -> PolarsResult<SinkResult> {
         // don't add empty dataframes
@@ -207,6 +304,6 @@ impl Sink for FilesSink {
         self
     }
     fn fmt(&self) -> &str {
-        "parquet_sink"
+        "file_sink"
     }
 }
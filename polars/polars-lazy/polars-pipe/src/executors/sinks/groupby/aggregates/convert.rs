@@ -1,12 +1,15 @@
 use std::any::Any;
 use std::sync::Arc;
 
+use polars_arrow::prelude::QuantileInterpolOptions;
 use polars_core::datatypes::Field;
 use polars_core::error::PolarsResult;
 use polars_core::prelude::{DataType, SchemaRef, Series, IDX_DTYPE};
 use polars_core::schema::Schema;
 use polars_plan::dsl::Expr;
-use polars_plan::logical_plan::{ArenaExprIter, Context};
+#[cfg(feature = "approx_unique")]
+use polars_plan::dsl::FunctionExpr;
+use polars_plan::logical_plan::{ArenaExprIter, Context, LiteralValue};
 use polars_plan::prelude::{AAggExpr, AExpr};
 use polars_utils::arena::{Arena, Node};
 use polars_utils::IdxSize;
@@ -16,7 +19,10 @@ use crate::executors::sinks::groupby::aggregates::first::FirstAgg;
 use crate::executors::sinks::groupby::aggregates::last::LastAgg;
 use crate::executors::sinks::groupby::aggregates::mean::MeanAgg;
 use crate::executors::sinks::groupby::aggregates::min_max::{new_max, new_min};
+use crate::executors::sinks::groupby::aggregates::n_unique::NUniqueAgg;
 use crate::executors::sinks::groupby::aggregates::null::NullAgg;
+use crate::executors::sinks::groupby::aggregates::quantile::QuantileAgg;
+use crate::executors::sinks::groupby::aggregates::var::VarAgg;
 use crate::executors::sinks::groupby::aggregates::{AggregateFunction, SumAgg};
 use crate::expressions::PhysicalPipedExpr;
 use crate::operators::DataChunk;
@@ -39,11 +45,59 @@ impl PhysicalPipedExpr for Count {
     }
 }
 
+#[cfg(feature = "approx_unique")]
+fn is_approx_unique(ae: &AExpr) -> bool {
+    matches!(
+        ae,
+        AExpr::Function {
+            function: FunctionExpr::ApproxUnique,
+            ..
+        }
+    )
+}
+
+#[cfg(not(feature = "approx_unique"))]
+fn is_approx_unique(_ae: &AExpr) -> bool {
+    false
+}
+
+/// The quantile must be a literal, as it cannot vary per group.
+fn quantile_literal(node: Node, expr_arena: &Arena<AExpr>) -> Option<f64> {
+    match expr_arena.get(node) {
+        AExpr::Literal(LiteralValue::Float64(q)) => Some(*q),
+        AExpr::Literal(LiteralValue::Float32(q)) => Some(*q as f64),
+        _ => None,
+    }
+    .filter(|q| (0.0..=1.0).contains(q))
+}
+
 pub fn can_convert_to_hash_agg(
     mut node: Node,
     expr_arena: &Arena<AExpr>,
     input_schema: &Schema,
 ) -> bool {
+    let input_dtype = |node: Node| {
+        expr_arena
+            .get(node)
+            .to_field(input_schema, Context::Default, expr_arena)
+            .map(|field| field.dtype)
+            .ok()
+    };
+    let numeric_input = |node: Node| {
+        input_dtype(node)
+            .map(|dtype| dtype.is_numeric())
+            .unwrap_or(false)
+    };
+    // the unique values are hashed as physical `AnyValue`s
+    let hashable_input = |node: Node| {
+        input_dtype(node)
+            .map(|dtype| {
+                let dtype = dtype.to_physical();
+                dtype.is_numeric() || matches!(dtype, DataType::Boolean | DataType::Utf8)
+            })
+            .unwrap_or(false)
+    };
+
     let mut can_run_partitioned = true;
     if expr_arena
         .iter(node)
@@ -57,13 +111,14 @@ pub fn can_convert_to_hash_agg(
                 | AExpr::BinaryExpr { .. }
                 | AExpr::Ternary { .. }
                 | AExpr::Alias(_, _) => {}
+                ae if is_approx_unique(ae) => {}
                 _ => {
                     can_run_partitioned = false;
                 }
             }
             ae
         })
-        .filter(|ae| matches!(ae, AExpr::Agg(_) | AExpr::Count))
+        .filter(|ae| matches!(ae, AExpr::Agg(_) | AExpr::Count) || is_approx_unique(ae))
         .count()
         == 1
         && can_run_partitioned
@@ -74,31 +129,41 @@ pub fn can_convert_to_hash_agg(
         }
         match expr_arena.get(node) {
             AExpr::Count => true,
-            ae @ AExpr::Agg(agg_fn) => {
-                matches!(
-                    agg_fn,
-                    AAggExpr::Sum(_)
-                        | AAggExpr::First(_)
-                        | AAggExpr::Last(_)
-                        | AAggExpr::Mean(_)
-                        | AAggExpr::Count(_)
-                ) || (matches!(
-                    agg_fn,
-                    AAggExpr::Max {
-                        propagate_nans: false,
-                        ..
-                    } | AAggExpr::Min {
-                        propagate_nans: false,
-                        ..
-                    }
-                ) && {
+            #[cfg(feature = "approx_unique")]
+            AExpr::Function {
+                input,
+                function: FunctionExpr::ApproxUnique,
+                ..
+            } => input.len() == 1 && hashable_input(input[0]),
+            ae @ AExpr::Agg(agg_fn) => match agg_fn {
+                AAggExpr::Sum(_)
+                | AAggExpr::First(_)
+                | AAggExpr::Last(_)
+                | AAggExpr::Mean(_)
+                | AAggExpr::Count(_) => true,
+                AAggExpr::Std(input, _) | AAggExpr::Var(input, _) | AAggExpr::Median(input) => {
+                    numeric_input(*input)
+                }
+                AAggExpr::Quantile { expr, quantile, .. } => {
+                    numeric_input(*expr) && quantile_literal(*quantile, expr_arena).is_some()
+                }
+                AAggExpr::NUnique(input) => hashable_input(*input),
+                AAggExpr::Max {
+                    propagate_nans: false,
+                    ..
+                }
+                | AAggExpr::Min {
+                    propagate_nans: false,
+                    ..
+                } => {
                     if let Ok(field) = ae.to_field(input_schema, Context::Default, expr_arena) {
                         field.dtype.to_physical().is_numeric()
                     } else {
                         false
                     }
-                })
-            }
+                }
+                _ => false,
+            },
             _ => false,
         }
     } else {
@@ -250,8 +315,69 @@ where
                     AggregateFunction::Count(CountAgg::new()),
                 )
             }
+            AAggExpr::Std(input, ddof) | AAggExpr::Var(input, ddof) => {
+                let is_std = matches!(agg, AAggExpr::Std(_, _));
+                let phys_expr = to_physical(*input, expr_arena, Some(schema)).unwrap();
+                let logical_dtype = phys_expr.field(schema).unwrap().dtype;
+                let agg_fn = match logical_dtype {
+                    DataType::Float32 => AggregateFunction::VarF32(VarAgg::new(*ddof, is_std)),
+                    _ => AggregateFunction::VarF64(VarAgg::new(*ddof, is_std)),
+                };
+                (logical_dtype, phys_expr, agg_fn)
+            }
+            AAggExpr::Median(input) => {
+                let phys_expr = to_physical(*input, expr_arena, Some(schema)).unwrap();
+                let logical_dtype = phys_expr.field(schema).unwrap().dtype;
+                let interpol = QuantileInterpolOptions::Linear;
+                let agg_fn = match logical_dtype {
+                    DataType::Float32 => {
+                        AggregateFunction::QuantileF32(QuantileAgg::new(0.5, interpol))
+                    }
+                    _ => AggregateFunction::QuantileF64(QuantileAgg::new(0.5, interpol)),
+                };
+                (logical_dtype, phys_expr, agg_fn)
+            }
+            AAggExpr::Quantile {
+                expr,
+                quantile,
+                interpol,
+            } => {
+                let phys_expr = to_physical(*expr, expr_arena, Some(schema)).unwrap();
+                let logical_dtype = phys_expr.field(schema).unwrap().dtype;
+                let quantile = quantile_literal(*quantile, expr_arena).unwrap();
+                let agg_fn = match logical_dtype {
+                    DataType::Float32 => {
+                        AggregateFunction::QuantileF32(QuantileAgg::new(quantile, *interpol))
+                    }
+                    _ => AggregateFunction::QuantileF64(QuantileAgg::new(quantile, *interpol)),
+                };
+                (logical_dtype, phys_expr, agg_fn)
+            }
+            AAggExpr::NUnique(input) => {
+                let phys_expr = to_physical(*input, expr_arena, Some(schema)).unwrap();
+                let logical_dtype = phys_expr.field(schema).unwrap().dtype;
+                (
+                    logical_dtype,
+                    phys_expr,
+                    AggregateFunction::NUnique(NUniqueAgg::new()),
+                )
+            }
             agg => panic!("{agg:?} not yet implemented."),
         },
+        #[cfg(feature = "approx_unique")]
+        AExpr::Function {
+            input,
+            function: FunctionExpr::ApproxUnique,
+            ..
+        } => {
+            let phys_expr = to_physical(input[0], expr_arena, Some(schema)).unwrap();
+            let logical_dtype = phys_expr.field(schema).unwrap().dtype;
+            (
+                logical_dtype,
+                phys_expr,
+                AggregateFunction::NUnique(NUniqueAgg::new_approx()),
+            )
+        }
         _ => todo!(),
     }
 }
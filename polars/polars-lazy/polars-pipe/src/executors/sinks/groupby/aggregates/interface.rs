@@ -10,7 +10,10 @@ use crate::executors::sinks::groupby::aggregates::first::FirstAgg;
 use crate::executors::sinks::groupby::aggregates::last::LastAgg;
 use crate::executors::sinks::groupby::aggregates::mean::MeanAgg;
 use crate::executors::sinks::groupby::aggregates::min_max::MinMaxAgg;
+use crate::executors::sinks::groupby::aggregates::n_unique::NUniqueAgg;
 use crate::executors::sinks::groupby::aggregates::null::NullAgg;
+use crate::executors::sinks::groupby::aggregates::quantile::QuantileAgg;
+use crate::executors::sinks::groupby::aggregates::var::VarAgg;
 use crate::executors::sinks::groupby::aggregates::SumAgg;
 use crate::operators::IdxSize;
 
@@ -82,6 +85,11 @@ pub(crate) enum AggregateFunction {
     SumI64(SumAgg<i64>),
     MeanF32(MeanAgg<f32>),
     MeanF64(MeanAgg<f64>),
+    VarF32(VarAgg<f32>),
+    VarF64(VarAgg<f64>),
+    QuantileF32(QuantileAgg<f32>),
+    QuantileF64(QuantileAgg<f64>),
+    NUnique(NUniqueAgg),
     Null(NullAgg),
     MinMaxF32(MinMaxAgg<f32, fn(&f32, &f32) -> Ordering>),
     MinMaxF64(MinMaxAgg<f64, fn(&f64, &f64) -> Ordering>),
@@ -109,6 +117,11 @@ impl AggregateFunction {
             SumI64(_) => SumI64(SumAgg::new()),
             MeanF32(_) => MeanF32(MeanAgg::new()),
             MeanF64(_) => MeanF64(MeanAgg::new()),
+            VarF32(inner) => VarF32(inner.split()),
+            VarF64(inner) => VarF64(inner.split()),
+            QuantileF32(inner) => QuantileF32(inner.split()),
+            QuantileF64(inner) => QuantileF64(inner.split()),
+            NUnique(inner) => NUnique(inner.split()),
             Count(_) => Count(CountAgg::new()),
             Null(a) => Null(a.clone()),
             MinMaxF32(inner) => MinMaxF32(inner.split()),
@@ -5,8 +5,11 @@ mod interface;
 mod last;
 mod mean;
 mod min_max;
+mod n_unique;
 mod null;
+mod quantile;
 mod sum;
+mod var;
 
 pub use convert::*;
 pub(crate) use interface::{AggregateFn, AggregateFunction};
@@ -0,0 +1,130 @@
+use std::any::Any;
+use std::hash::BuildHasher;
+
+use hashbrown::hash_map::RawEntryMut;
+#[cfg(feature = "approx_unique")]
+use polars_core::export::ahash::RandomState;
+use polars_core::prelude::*;
+#[cfg(feature = "approx_unique")]
+use polars_ops::prelude::HyperLogLog;
+use polars_utils::unwrap::UnwrapUncheckedRelease;
+
+use super::*;
+use crate::operators::IdxSize;
+
+/// Fixed seeds, the approximate counts of different threads
+/// are merged, so the values must hash the same everywhere.
+#[cfg(feature = "approx_unique")]
+const APPROX_SEED: RandomState = RandomState::with_seeds(
+    0x5bd1e9955bd1e995,
+    0x9e3779b97f4a7c15,
+    0xc2b2ae3d27d4eb4f,
+    0x165667b19e3779f9,
+);
+
+enum NUniqueState {
+    Exact(PlHashMap<AnyValue<'static>, ()>),
+    #[cfg(feature = "approx_unique")]
+    Approx(Box<HyperLogLog<u64>>),
+}
+
+/// Counts the unique values of a group, `null` is counted as a value.
+///
+/// The exact count keeps all unique values of the group in a hash set. The approximate
+/// count (`approx_unique`) uses HyperLogLog++ and has a fixed memory footprint.
+pub(crate) struct NUniqueAgg {
+    state: NUniqueState,
+}
+
+impl NUniqueAgg {
+    pub(crate) fn new() -> Self {
+        NUniqueAgg {
+            state: NUniqueState::Exact(Default::default()),
+        }
+    }
+
+    #[cfg(feature = "approx_unique")]
+    pub(crate) fn new_approx() -> Self {
+        NUniqueAgg {
+            state: NUniqueState::Approx(Box::new(HyperLogLog::new())),
+        }
+    }
+
+    pub(crate) fn split(&self) -> Self {
+        match &self.state {
+            NUniqueState::Exact(_) => Self::new(),
+            #[cfg(feature = "approx_unique")]
+            NUniqueState::Approx(_) => Self::new_approx(),
+        }
+    }
+
+    fn insert(&mut self, av: AnyValue) {
+        // floats are compared by their bit representation, so that NaN's are counted once
+        let av = match av {
+            AnyValue::Float32(v) => AnyValue::UInt32(v.to_bits()),
+            AnyValue::Float64(v) => AnyValue::UInt64(v.to_bits()),
+            av => av,
+        };
+        match &mut self.state {
+            NUniqueState::Exact(values) => {
+                let h = values.hasher().hash_one(&av);
+                let entry = values.raw_entry_mut().from_hash(h, |key| *key == av);
+                if let RawEntryMut::Vacant(entry) = entry {
+                    // only allocate if we see a new value
+                    entry.insert_hashed_nocheck(h, av.into_static().unwrap(), ());
+                }
+            }
+            #[cfg(feature = "approx_unique")]
+            NUniqueState::Approx(hll) => hll.add(&APPROX_SEED.hash_one(&av)),
+        }
+    }
+}
+
+impl AggregateFn for NUniqueAgg {
+    fn pre_agg(&mut self, _chunk_idx: IdxSize, item: &mut dyn ExactSizeIterator<Item = AnyValue>) {
+        let item = unsafe { item.next().unwrap_unchecked_release() };
+        self.insert(item)
+    }
+
+    fn pre_agg_ordered(
+        &mut self,
+        _chunk_idx: IdxSize,
+        offset: IdxSize,
+        length: IdxSize,
+        values: &Series,
+    ) {
+        let values = values.slice(offset as i64, length as usize);
+        for av in values.phys_iter() {
+            self.insert(av)
+        }
+    }
+
+    fn dtype(&self) -> DataType {
+        IDX_DTYPE
+    }
+
+    fn combine(&mut self, other: &dyn Any) {
+        let other = unsafe { other.downcast_ref::<Self>().unwrap_unchecked_release() };
+        match (&mut self.state, &other.state) {
+            (NUniqueState::Exact(values), NUniqueState::Exact(other)) => {
+                values.extend(other.iter().map(|(av, _)| (av.clone(), ())))
+            }
+            #[cfg(feature = "approx_unique")]
+            (NUniqueState::Approx(hll), NUniqueState::Approx(other)) => hll.merge(other),
+            #[cfg(feature = "approx_unique")]
+            _ => unreachable!(),
+        }
+    }
+
+    fn finalize(&mut self) -> AnyValue<'static> {
+        let count = match &self.state {
+            NUniqueState::Exact(values) => values.len(),
+            #[cfg(feature = "approx_unique")]
+            NUniqueState::Approx(hll) => hll.count(),
+        };
+        AnyValue::from(count as IdxSize)
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
@@ -0,0 +1,135 @@
+use std::any::Any;
+use std::marker::PhantomData;
+
+use polars_arrow::export::arrow::array::PrimitiveArray;
+use polars_core::export::arrow::datatypes::PrimitiveType;
+use polars_core::export::num::ToPrimitive;
+use polars_core::prelude::*;
+use polars_utils::unwrap::UnwrapUncheckedRelease;
+
+use super::*;
+use crate::operators::{ArrowDataType, IdxSize};
+
+/// Computes an exact quantile (or the median) of a group.
+///
+/// The values of the group are collected and only selected at `finalize`. The memory
+/// usage is bounded by the out-of-core groupby, which spills the input by partition.
+/// `K` is the output type, the values are always collected as `f64`.
+pub struct QuantileAgg<K: NumericNative> {
+    values: Vec<f64>,
+    quantile: f64,
+    interpol: QuantileInterpolOptions,
+    phantom: PhantomData<K>,
+}
+
+impl<K: NumericNative> QuantileAgg<K> {
+    pub(crate) fn new(quantile: f64, interpol: QuantileInterpolOptions) -> Self {
+        QuantileAgg {
+            values: vec![],
+            quantile,
+            interpol,
+            phantom: PhantomData,
+        }
+    }
+
+    pub(crate) fn split(&self) -> Self {
+        Self::new(self.quantile, self.interpol)
+    }
+
+    #[inline]
+    fn pre_agg_primitive<T: ToPrimitive>(&mut self, item: Option<T>) {
+        if let Some(val) = item {
+            self.values
+                .push(unsafe { val.to_f64().unwrap_unchecked_release() })
+        }
+    }
+}
+
+impl<K> AggregateFn for QuantileAgg<K>
+where
+    K: NumericNative,
+{
+    fn has_physical_agg(&self) -> bool {
+        true
+    }
+    fn pre_agg_i8(&mut self, _chunk_idx: IdxSize, item: Option<i8>) {
+        self.pre_agg_primitive(item)
+    }
+    fn pre_agg_u8(&mut self, _chunk_idx: IdxSize, item: Option<u8>) {
+        self.pre_agg_primitive(item)
+    }
+    fn pre_agg_i16(&mut self, _chunk_idx: IdxSize, item: Option<i16>) {
+        self.pre_agg_primitive(item)
+    }
+    fn pre_agg_u16(&mut self, _chunk_idx: IdxSize, item: Option<u16>) {
+        self.pre_agg_primitive(item)
+    }
+    fn pre_agg_i32(&mut self, _chunk_idx: IdxSize, item: Option<i32>) {
+        self.pre_agg_primitive(item)
+    }
+    fn pre_agg_i64(&mut self, _chunk_idx: IdxSize, item: Option<i64>) {
+        self.pre_agg_primitive(item)
+    }
+    fn pre_agg_u32(&mut self, _chunk_idx: IdxSize, item: Option<u32>) {
+        self.pre_agg_primitive(item)
+    }
+    fn pre_agg_u64(&mut self, _chunk_idx: IdxSize, item: Option<u64>) {
+        self.pre_agg_primitive(item)
+    }
+    fn pre_agg_f32(&mut self, _chunk_idx: IdxSize, item: Option<f32>) {
+        self.pre_agg_primitive(item)
+    }
+    fn pre_agg_f64(&mut self, _chunk_idx: IdxSize, item: Option<f64>) {
+        self.pre_agg_primitive(item)
+    }
+
+    fn pre_agg(&mut self, _chunk_idx: IdxSize, item: &mut dyn ExactSizeIterator<Item = AnyValue>) {
+        let item = unsafe { item.next().unwrap_unchecked_release() };
+        self.pre_agg_primitive(item.extract::<f64>())
+    }
+
+    fn pre_agg_ordered(
+        &mut self,
+        _chunk_idx: IdxSize,
+        offset: IdxSize,
+        length: IdxSize,
+        values: &Series,
+    ) {
+        let arr = unsafe {
+            let arr = values.chunks().get_unchecked(0);
+            arr.sliced_unchecked(offset as usize, length as usize)
+        };
+        let arr = polars_arrow::compute::cast::cast(arr.as_ref(), &ArrowDataType::Float64).unwrap();
+        let arr = unsafe {
+            arr.as_any()
+                .downcast_ref::<PrimitiveArray<f64>>()
+                .unwrap_unchecked_release()
+        };
+        self.values.extend(arr.iter().flatten().copied())
+    }
+
+    fn dtype(&self) -> DataType {
+        (&ArrowDataType::from(K::PRIMITIVE)).into()
+    }
+
+    fn combine(&mut self, other: &dyn Any) {
+        let other = unsafe { other.downcast_ref::<Self>().unwrap_unchecked_release() };
+        self.values.extend_from_slice(&other.values);
+    }
+
+    fn finalize(&mut self) -> AnyValue<'static> {
+        let ca = Float64Chunked::from_vec("", std::mem::take(&mut self.values));
+        // the quantile is validated when the aggregation is created
+        match ca.quantile(self.quantile, self.interpol).unwrap() {
+            None => AnyValue::Null,
+            Some(out) => match K::PRIMITIVE {
+                PrimitiveType::Float32 => AnyValue::Float32(out as f32),
+                PrimitiveType::Float64 => AnyValue::Float64(out),
+                _ => unreachable!(),
+            },
+        }
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
@@ -0,0 +1,163 @@
+use std::any::Any;
+use std::marker::PhantomData;
+
+use polars_arrow::export::arrow::array::PrimitiveArray;
+use polars_core::export::arrow::datatypes::PrimitiveType;
+use polars_core::export::num::ToPrimitive;
+use polars_core::prelude::*;
+use polars_utils::unwrap::UnwrapUncheckedRelease;
+
+use super::*;
+use crate::operators::{ArrowDataType, IdxSize};
+
+/// Computes the variance or standard deviation with Welford's online algorithm.
+///
+/// The running state of two aggregators is merged with the parallel algorithm of Chan et al.
+/// `K` is the output type, the aggregation itself always runs in `f64`.
+pub struct VarAgg<K: NumericNative> {
+    count: u64,
+    mean: f64,
+    m2: f64,
+    ddof: u8,
+    is_std: bool,
+    phantom: PhantomData<K>,
+}
+
+impl<K: NumericNative> VarAgg<K> {
+    pub(crate) fn new(ddof: u8, is_std: bool) -> Self {
+        VarAgg {
+            count: 0,
+            mean: 0.0,
+            m2: 0.0,
+            ddof,
+            is_std,
+            phantom: PhantomData,
+        }
+    }
+
+    pub(crate) fn split(&self) -> Self {
+        Self::new(self.ddof, self.is_std)
+    }
+
+    #[inline]
+    fn update(&mut self, value: f64) {
+        self.count += 1;
+        let delta_1 = value - self.mean;
+        self.mean += delta_1 / self.count as f64;
+        let delta_2 = value - self.mean;
+        self.m2 += delta_1 * delta_2;
+    }
+
+    #[inline]
+    fn pre_agg_primitive<T: ToPrimitive>(&mut self, item: Option<T>) {
+        if let Some(val) = item {
+            self.update(unsafe { val.to_f64().unwrap_unchecked_release() })
+        }
+    }
+}
+
+impl<K> AggregateFn for VarAgg<K>
+where
+    K: NumericNative,
+{
+    fn has_physical_agg(&self) -> bool {
+        true
+    }
+    fn pre_agg_i8(&mut self, _chunk_idx: IdxSize, item: Option<i8>) {
+        self.pre_agg_primitive(item)
+    }
+    fn pre_agg_u8(&mut self, _chunk_idx: IdxSize, item: Option<u8>) {
+        self.pre_agg_primitive(item)
+    }
+    fn pre_agg_i16(&mut self, _chunk_idx: IdxSize, item: Option<i16>) {
+        self.pre_agg_primitive(item)
+    }
+    fn pre_agg_u16(&mut self, _chunk_idx: IdxSize, item: Option<u16>) {
+        self.pre_agg_primitive(item)
+    }
+    fn pre_agg_i32(&mut self, _chunk_idx: IdxSize, item: Option<i32>) {
+        self.pre_agg_primitive(item)
+    }
+    fn pre_agg_i64(&mut self, _chunk_idx: IdxSize, item: Option<i64>) {
+        self.pre_agg_primitive(item)
+    }
+    fn pre_agg_u32(&mut self, _chunk_idx: IdxSize, item: Option<u32>) {
+        self.pre_agg_primitive(item)
+    }
+    fn pre_agg_u64(&mut self, _chunk_idx: IdxSize, item: Option<u64>) {
+        self.pre_agg_primitive(item)
+    }
+    fn pre_agg_f32(&mut self, _chunk_idx: IdxSize, item: Option<f32>) {
+        self.pre_agg_primitive(item)
+    }
+    fn pre_agg_f64(&mut self, _chunk_idx: IdxSize, item: Option<f64>) {
+        self.pre_agg_primitive(item)
+    }
+
+    fn pre_agg(&mut self, _chunk_idx: IdxSize, item: &mut dyn ExactSizeIterator<Item = AnyValue>) {
+        let item = unsafe { item.next().unwrap_unchecked_release() };
+        self.pre_agg_primitive(item.extract::<f64>())
+    }
+
+    fn pre_agg_ordered(
+        &mut self,
+        _chunk_idx: IdxSize,
+        offset: IdxSize,
+        length: IdxSize,
+        values: &Series,
+    ) {
+        let arr = unsafe {
+            let arr = values.chunks().get_unchecked(0);
+            arr.sliced_unchecked(offset as usize, length as usize)
+        };
+        let arr = polars_arrow::compute::cast::cast(arr.as_ref(), &ArrowDataType::Float64).unwrap();
+        let arr = unsafe {
+            arr.as_any()
+                .downcast_ref::<PrimitiveArray<f64>>()
+                .unwrap_unchecked_release()
+        };
+        arr.iter()
+            .for_each(|val| self.pre_agg_primitive(val.copied()))
+    }
+
+    fn dtype(&self) -> DataType {
+        (&ArrowDataType::from(K::PRIMITIVE)).into()
+    }
+
+    fn combine(&mut self, other: &dyn Any) {
+        let other = unsafe { other.downcast_ref::<Self>().unwrap_unchecked_release() };
+        if other.count == 0 {
+            return;
+        }
+        if self.count == 0 {
+            self.count = other.count;
+            self.mean = other.mean;
+            self.m2 = other.m2;
+            return;
+        }
+        let count = self.count + other.count;
+        let delta = other.mean - self.mean;
+        self.mean += delta * other.count as f64 / count as f64;
+        self.m2 +=
+            other.m2 + delta * delta * (self.count as f64 * other.count as f64) / count as f64;
+        self.count = count;
+    }
+
+    fn finalize(&mut self) -> AnyValue<'static> {
+        // same semantics as the default engine
+        let var = match self.count {
+            0 => return AnyValue::Null,
+            1 => 0.0,
+            count => self.m2 / (count as f64 - self.ddof as f64),
+        };
+        let out = if self.is_std { var.sqrt() } else { var };
+        match K::PRIMITIVE {
+            PrimitiveType::Float32 => AnyValue::Float32(out as f32),
+            PrimitiveType::Float64 => AnyValue::Float64(out),
+            _ => unreachable!(),
+        }
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
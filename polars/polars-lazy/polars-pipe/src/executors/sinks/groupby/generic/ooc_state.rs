@@ -1,4 +1,5 @@
 use polars_core::config::verbose;
+use polars_plan::frame::StreamingConfig;
 
 use super::*;
 use crate::executors::sinks::io::IOThread;
@@ -17,10 +18,11 @@ pub(super) struct OocState {
     pub(super) io_thread: IOThreadRef,
     count: u16,
     to_disk_threshold: f64,
+    config: StreamingConfig,
 }
 
-impl Default for OocState {
-    fn default() -> Self {
+impl OocState {
+    pub(super) fn new(config: &StreamingConfig) -> Self {
         let to_disk_threshold = if std::env::var(FORCE_OOC).is_ok() {
             1.0
         } else {
@@ -28,11 +30,12 @@ impl Default for OocState {
         };
 
         Self {
-            mem_track: MemTracker::new(morsels_per_sink()),
+            mem_track: MemTracker::new(morsels_per_sink(), config.memory_budget),
             ooc: false,
             io_thread: Default::default(),
             count: 0,
             to_disk_threshold,
+            config: config.clone(),
         }
     }
 }
@@ -60,7 +63,11 @@ impl OocState {
         // start IO thread
         let mut iot = self.io_thread.lock().unwrap();
         if iot.is_none() {
-            *iot = Some(IOThread::try_new(Arc::new(spill_schema), "groupby").unwrap());
+            *iot = Some(IOThread::try_new(
+                Arc::new(spill_schema),
+                "groupby",
+                &self.config,
+            )?);
         }
         Ok(())
     }
@@ -1,6 +1,7 @@
 use std::cell::UnsafeCell;
 
 use polars_core::utils::accumulate_dataframes_vertical_unchecked;
+use polars_plan::frame::StreamingConfig;
 
 use super::*;
 use crate::executors::sinks::groupby::generic::global::GlobalTable;
@@ -25,6 +26,7 @@ impl GenericGroupby2 {
         output_schema: SchemaRef,
         agg_input_dtypes: Vec<DataType>,
         slice: Option<(i64, usize)>,
+        config: &StreamingConfig,
     ) -> Self {
         let key_dtypes: Arc<[DataType]> = Arc::from(
             output_schema
@@ -52,7 +54,7 @@ impl GenericGroupby2 {
             global_table: Arc::new(global_map),
             eval: Eval::new(key_columns, aggregation_columns),
             slice,
-            ooc_state: Default::default(),
+            ooc_state: OocState::new(config),
         }
     }
 }
@@ -4,6 +4,8 @@ mod ooc;
 mod ooc_state;
 mod primitive;
 mod string;
+#[cfg(feature = "dynamic_groupby")]
+pub(crate) mod temporal;
 mod utils;
 
 pub(crate) use generic::GenericGroupby2;
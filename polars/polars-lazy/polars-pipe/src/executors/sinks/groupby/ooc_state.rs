@@ -2,6 +2,7 @@ use std::sync::Mutex;
 
 use polars_core::config::verbose;
 use polars_core::prelude::*;
+use polars_plan::frame::StreamingConfig;
 
 use crate::executors::sinks::io::IOThread;
 use crate::executors::sinks::memory::MemTracker;
@@ -20,14 +21,20 @@ pub(super) struct OocState {
     pub(super) ooc: bool,
     // when ooc, we write to disk using an IO thread
     pub(super) io_thread: Arc<Mutex<Option<IOThread>>>,
+    pub(super) config: StreamingConfig,
 }
 
 impl OocState {
-    pub(super) fn new(io_thread: Option<Arc<Mutex<Option<IOThread>>>>, ooc: bool) -> Self {
+    pub(super) fn new(
+        io_thread: Option<Arc<Mutex<Option<IOThread>>>>,
+        ooc: bool,
+        config: &StreamingConfig,
+    ) -> Self {
         Self {
-            _mem_track: MemTracker::new(morsels_per_sink()),
+            _mem_track: MemTracker::new(morsels_per_sink(), config.memory_budget),
             ooc,
             io_thread: io_thread.unwrap_or_default(),
+            config: config.clone(),
         }
     }
 
@@ -40,7 +47,7 @@ impl OocState {
         // start IO thread
         let mut iot = self.io_thread.lock().unwrap();
         if iot.is_none() {
-            *iot = Some(IOThread::try_new(input_schema, "groupby")?)
+            *iot = Some(IOThread::try_new(input_schema, "groupby", &self.config)?)
         }
         Ok(())
     }
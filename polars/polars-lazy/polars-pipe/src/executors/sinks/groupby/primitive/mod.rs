@@ -12,6 +12,7 @@ use polars_core::prelude::*;
 use polars_core::series::IsSorted;
 use polars_core::utils::_set_partition_size;
 use polars_core::POOL;
+use polars_plan::frame::StreamingConfig;
 use polars_utils::hash_to_partition;
 use polars_utils::slice::GetSaferUnchecked;
 use polars_utils::unwrap::UnwrapUncheckedRelease;
@@ -90,6 +91,7 @@ where
         input_schema: SchemaRef,
         output_schema: SchemaRef,
         slice: Option<(i64, usize)>,
+        config: &StreamingConfig,
     ) -> Self {
         // this ooc is broken fix later
         Self::new_inner(
@@ -101,6 +103,7 @@ where
             slice,
             None,
             false,
+            config,
         )
     }
 
@@ -114,6 +117,7 @@ where
         slice: Option<(i64, usize)>,
         io_thread: Option<Arc<Mutex<Option<IOThread>>>>,
         ooc: bool,
+        config: &StreamingConfig,
     ) -> Self {
         let hb = RandomState::default();
         let partitions = _set_partition_size();
@@ -136,7 +140,7 @@ where
             hashes: vec![],
             slice,
             sort_partitions: vec![],
-            ooc_state: OocState::new(io_thread, ooc),
+            ooc_state: OocState::new(io_thread, ooc, config),
         };
         if ooc {
             out.ooc_state.init_ooc(out.input_schema.clone()).unwrap();
@@ -466,6 +470,7 @@ where
             self.slice,
             Some(self.ooc_state.io_thread.clone()),
             self.ooc_state.ooc,
+            &self.ooc_state.config,
         );
         new.hb = self.hb.clone();
         new.thread_no = thread_no;
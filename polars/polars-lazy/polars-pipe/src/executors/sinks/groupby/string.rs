@@ -9,6 +9,7 @@ use polars_core::frame::row::AnyValueBuffer;
 use polars_core::prelude::*;
 use polars_core::utils::_set_partition_size;
 use polars_core::{IdBuildHasher, POOL};
+use polars_plan::frame::StreamingConfig;
 use polars_utils::hash_to_partition;
 use polars_utils::slice::GetSaferUnchecked;
 use polars_utils::unwrap::UnwrapUncheckedRelease;
@@ -91,6 +92,7 @@ impl Utf8GroupbySink {
         input_schema: SchemaRef,
         output_schema: SchemaRef,
         slice: Option<(i64, usize)>,
+        config: &StreamingConfig,
     ) -> Self {
         Self::new_inner(
             key_column,
@@ -101,6 +103,7 @@ impl Utf8GroupbySink {
             slice,
             None,
             false,
+            config,
         )
     }
 
@@ -114,6 +117,7 @@ impl Utf8GroupbySink {
         slice: Option<(i64, usize)>,
         io_thread: Option<Arc<Mutex<Option<IOThread>>>>,
         ooc: bool,
+        config: &StreamingConfig,
     ) -> Self {
         let hb = Default::default();
         let partitions = _set_partition_size();
@@ -137,7 +141,7 @@ impl Utf8GroupbySink {
             aggregation_series: vec![],
             hashes: vec![],
             slice,
-            ooc_state: OocState::new(io_thread, ooc),
+            ooc_state: OocState::new(io_thread, ooc, config),
         };
         if ooc {
             out.ooc_state.init_ooc(out.input_schema.clone()).unwrap();
@@ -486,6 +490,7 @@ impl Sink for Utf8GroupbySink {
             self.slice,
             Some(self.ooc_state.io_thread.clone()),
             self.ooc_state.ooc,
+            &self.ooc_state.config,
         );
         new.hb = self.hb.clone();
         new.thread_no = thread_no;
@@ -0,0 +1,135 @@
+use super::*;
+
+const LB_NAME: &str = "_lower_boundary";
+const UP_NAME: &str = "_upper_boundary";
+
+/// The windows of a `groupby_dynamic`. They start at the truncated first timestamp
+/// plus the offset and advance with a fixed step `every`.
+pub(crate) struct DynamicWindows {
+    options: DynamicGroupOptions,
+    aggs: Vec<WindowAgg>,
+    // start of the first window, determined by the first row
+    first_start: Option<i64>,
+    // the first window that isn't aggregated yet
+    next_window: i64,
+}
+
+impl DynamicWindows {
+    pub(crate) fn new(options: DynamicGroupOptions, aggs: Vec<WindowAgg>) -> Self {
+        Self {
+            options,
+            aggs,
+            first_start: None,
+            next_window: 0,
+        }
+    }
+
+    fn first_start(&mut self, t: i64, tu: TimeUnit) -> PolarsResult<i64> {
+        if let Some(start) = self.first_start {
+            return Ok(start);
+        }
+        let w = Window::new(self.options.every, self.options.period, self.options.offset);
+        let start = match tu {
+            TimeUnit::Nanoseconds => w.truncate_ns(t, None, None)?,
+            TimeUnit::Microseconds => w.truncate_us(t, None, None)?,
+            TimeUnit::Milliseconds => w.truncate_ms(t, None, None)?,
+        };
+        self.first_start = Some(start);
+        Ok(start)
+    }
+}
+
+/// Offset of the first row that is a member of a window that starts at `start`.
+fn lower_bound_idx(ts: &[i64], start: i64, closed: ClosedWindow) -> usize {
+    match closed {
+        ClosedWindow::Left | ClosedWindow::Both => ts.partition_point(|t| *t < start),
+        ClosedWindow::Right | ClosedWindow::None => ts.partition_point(|t| *t <= start),
+    }
+}
+
+/// Offset of the first row after the members of a window that stops at `stop`.
+fn upper_bound_idx(ts: &[i64], stop: i64, closed: ClosedWindow) -> usize {
+    match closed {
+        ClosedWindow::Left | ClosedWindow::None => ts.partition_point(|t| *t < stop),
+        ClosedWindow::Right | ClosedWindow::Both => ts.partition_point(|t| *t <= stop),
+    }
+}
+
+impl Windows for DynamicWindows {
+    const NAME: &'static str = "dynamic_groupby";
+    const OPERATION: &'static str = "groupby_dynamic";
+
+    fn index_column(&self) -> &str {
+        &self.options.index_column
+    }
+
+    fn process(
+        &mut self,
+        df: &DataFrame,
+        ts: &[i64],
+        tu: TimeUnit,
+        context: &PExecutionContext,
+        finished: bool,
+    ) -> PolarsResult<(DataFrame, usize)> {
+        let index = df.column(&self.options.index_column)?;
+
+        let mut groups = vec![];
+        let mut lower = vec![];
+        let mut upper = vec![];
+        let mut keep_from = 0;
+        if let (Some(&first), Some(&last)) = (ts.first(), ts.last()) {
+            let first_start = self.first_start(first, tu)?;
+            let every = duration_in(&self.options.every, tu);
+            let period = duration_in(&self.options.period, tu);
+            let closed = self.options.closed_window;
+
+            let mut k = self.next_window;
+            loop {
+                let start = first_start + k * every;
+                let stop = start + period;
+                // the rows that follow are >= `last`, so they may still be a member
+                if !finished && stop >= last {
+                    break;
+                }
+                let lo = lower_bound_idx(ts, start, closed);
+                if lo == ts.len() {
+                    break;
+                }
+                let hi = upper_bound_idx(ts, stop, closed);
+                if hi > lo {
+                    groups.push([lo as IdxSize, (hi - lo) as IdxSize]);
+                    lower.push(start);
+                    upper.push(stop);
+                    k += 1;
+                } else {
+                    // skip the empty windows up to the first one that may contain `ts[lo]`
+                    k = std::cmp::max(k + 1, (ts[lo] - period - first_start).div_euclid(every));
+                }
+            }
+            self.next_window = k;
+            let start = first_start + k * every;
+            keep_from = ts.partition_point(|t| *t < start);
+        }
+
+        let groups = GroupsProxy::Slice {
+            groups,
+            rolling: false,
+        };
+        let dtype = index.dtype();
+        let mut columns = Vec::with_capacity(self.aggs.len() + 3);
+        if self.options.include_boundaries {
+            columns.push(timestamps_to_series(LB_NAME, lower.clone(), tu, dtype));
+            columns.push(timestamps_to_series(UP_NAME, upper, tu, dtype));
+        }
+        let time_key = if self.options.truncate {
+            timestamps_to_series(index.name(), lower, tu, dtype).cast(dtype)?
+        } else {
+            // safety: the groups are in bounds
+            unsafe { index.agg_first(&groups) }
+        };
+        columns.push(time_key);
+        columns.extend(aggregate(&self.aggs, df, &groups, context)?);
+
+        Ok((DataFrame::new_no_checks(columns), keep_from))
+    }
+}
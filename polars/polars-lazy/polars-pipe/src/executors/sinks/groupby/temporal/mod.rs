@@ -0,0 +1,417 @@
+//! Streaming `groupby_dynamic` and `groupby_rolling` on a sorted index column.
+//!
+//! A window can span many chunks, so the chunks are processed in the order of the source.
+//! Every chunk is appended to the rows that may still be a member of an unfinished window.
+//! The windows that can't get new members are aggregated and the remaining rows are carried
+//! over to the next chunk.
+mod dynamic;
+mod rolling;
+
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+pub(crate) use dynamic::DynamicWindows;
+use polars_core::prelude::*;
+use polars_core::utils::accumulate_dataframes_vertical_unchecked;
+use polars_plan::logical_plan::{ArenaExprIter, Context, LiteralValue};
+use polars_plan::prelude::{AAggExpr, AExpr};
+use polars_time::prelude::{
+    groupby_values, ClosedWindow, Duration, DynamicGroupOptions, RollingGroupOptions, Window,
+};
+use polars_utils::arena::{Arena, Node};
+use polars_utils::slice::SortedSlice;
+pub(crate) use rolling::RollingWindows;
+use smartstring::alias::String as SmartString;
+
+use crate::expressions::PhysicalPipedExpr;
+use crate::operators::{DataChunk, FinalizedSink, PExecutionContext, Sink, SinkResult};
+
+/// Assigns the rows of a sorted index column to windows and aggregates them.
+pub(crate) trait Windows: Send + 'static {
+    const NAME: &'static str;
+    /// The name of the operation in error messages.
+    const OPERATION: &'static str;
+
+    fn index_column(&self) -> &str;
+
+    /// Aggregate the windows of `df` that can't get new members from the rows that follow.
+    /// `ts` are the timestamps of the index column in time unit `tu`. If `finished` there
+    /// are no rows that follow and all windows are aggregated.
+    ///
+    /// Returns the aggregated windows and the offset of the first row that may still be a
+    /// member of a window that isn't aggregated.
+    fn process(
+        &mut self,
+        df: &DataFrame,
+        ts: &[i64],
+        tu: TimeUnit,
+        context: &PExecutionContext,
+        finished: bool,
+    ) -> PolarsResult<(DataFrame, usize)>;
+}
+
+#[derive(Copy, Clone)]
+enum WindowAggMethod {
+    Min,
+    Max,
+    Sum,
+    Mean,
+    Median,
+    First,
+    Last,
+    Count,
+    NUnique,
+    Std(u8),
+    Var(u8),
+}
+
+/// An aggregation that is computed per window.
+#[derive(Clone)]
+pub(crate) struct WindowAgg {
+    // `None` for a count, which only needs the length of the window
+    input: Option<Arc<dyn PhysicalPipedExpr>>,
+    method: WindowAggMethod,
+    name: SmartString,
+}
+
+impl WindowAgg {
+    fn evaluate(
+        &self,
+        chunk: &DataChunk,
+        groups: &GroupsProxy,
+        context: &PExecutionContext,
+    ) -> PolarsResult<Series> {
+        use WindowAggMethod::*;
+        let mut out = match &self.input {
+            None => groups.group_count().into_series(),
+            Some(input) => {
+                let s = input.evaluate(chunk, context.execution_state.as_any())?;
+                polars_ensure!(
+                    s.len() == chunk.data.height(),
+                    ComputeError: "the input of a streaming window aggregation must be row-wise"
+                );
+                let s = s.rechunk();
+                // safety: the groups are in bounds of the chunk
+                unsafe {
+                    match self.method {
+                        Min => s.agg_min(groups),
+                        Max => s.agg_max(groups),
+                        Sum => s.agg_sum(groups),
+                        Mean => s.agg_mean(groups),
+                        Median => s.agg_median(groups),
+                        First => s.agg_first(groups),
+                        Last => s.agg_last(groups),
+                        Count => groups.group_count().into_series(),
+                        NUnique => s.agg_n_unique(groups),
+                        Std(ddof) => s.agg_std(groups, ddof),
+                        Var(ddof) => s.agg_var(groups, ddof),
+                    }
+                }
+            }
+        };
+        out.rename(&self.name);
+        Ok(out)
+    }
+}
+
+/// The input of the aggregation is computed row by row from at least one column.
+fn is_row_wise(node: Node, expr_arena: &Arena<AExpr>) -> bool {
+    let mut has_column = false;
+    let row_wise = expr_arena.iter(node).all(|(_, ae)| match ae {
+        AExpr::Column(_) => {
+            has_column = true;
+            true
+        }
+        AExpr::Literal(lv) => !matches!(lv, LiteralValue::Series(_) | LiteralValue::Range { .. }),
+        AExpr::BinaryExpr { .. }
+        | AExpr::Ternary { .. }
+        | AExpr::Cast { .. }
+        | AExpr::Alias(_, _) => true,
+        _ => false,
+    });
+    row_wise && has_column
+}
+
+/// Whether the aggregation can be computed per window by a streaming temporal groupby.
+pub fn can_convert_to_window_agg(node: Node, expr_arena: &Arena<AExpr>) -> bool {
+    match expr_arena.get(node) {
+        AExpr::Alias(input, _) => can_convert_to_window_agg(*input, expr_arena),
+        AExpr::Count => true,
+        AExpr::Agg(agg) => match agg {
+            AAggExpr::Min {
+                input,
+                propagate_nans: false,
+            }
+            | AAggExpr::Max {
+                input,
+                propagate_nans: false,
+            }
+            | AAggExpr::Sum(input)
+            | AAggExpr::Mean(input)
+            | AAggExpr::Median(input)
+            | AAggExpr::First(input)
+            | AAggExpr::Last(input)
+            | AAggExpr::Count(input)
+            | AAggExpr::NUnique(input)
+            | AAggExpr::Std(input, _)
+            | AAggExpr::Var(input, _) => is_row_wise(*input, expr_arena),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+pub(crate) fn convert_to_window_agg<F>(
+    node: Node,
+    expr_arena: &Arena<AExpr>,
+    input_schema: &SchemaRef,
+    to_physical: &F,
+) -> PolarsResult<WindowAgg>
+where
+    F: Fn(Node, &Arena<AExpr>, Option<&SchemaRef>) -> PolarsResult<Arc<dyn PhysicalPipedExpr>>,
+{
+    use WindowAggMethod::*;
+    let name = expr_arena
+        .get(node)
+        .to_field(input_schema, Context::Aggregation, expr_arena)?
+        .name;
+
+    let mut agg_node = node;
+    if let AExpr::Alias(input, _) = expr_arena.get(agg_node) {
+        agg_node = *input
+    }
+    let (input, method) = match expr_arena.get(agg_node) {
+        AExpr::Count => (None, Count),
+        AExpr::Agg(agg) => match agg {
+            AAggExpr::Min { input, .. } => (Some(*input), Min),
+            AAggExpr::Max { input, .. } => (Some(*input), Max),
+            AAggExpr::Sum(input) => (Some(*input), Sum),
+            AAggExpr::Mean(input) => (Some(*input), Mean),
+            AAggExpr::Median(input) => (Some(*input), Median),
+            AAggExpr::First(input) => (Some(*input), First),
+            AAggExpr::Last(input) => (Some(*input), Last),
+            AAggExpr::Count(_) => (None, Count),
+            AAggExpr::NUnique(input) => (Some(*input), NUnique),
+            AAggExpr::Std(input, ddof) => (Some(*input), Std(*ddof)),
+            AAggExpr::Var(input, ddof) => (Some(*input), Var(*ddof)),
+            _ => unreachable!(),
+        },
+        _ => unreachable!(),
+    };
+    let input = input
+        .map(|node| to_physical(node, expr_arena, Some(input_schema)))
+        .transpose()?;
+    Ok(WindowAgg {
+        input,
+        method,
+        name,
+    })
+}
+
+/// Aggregate the windows of the groups of `df`.
+fn aggregate(
+    aggs: &[WindowAgg],
+    df: &DataFrame,
+    groups: &GroupsProxy,
+    context: &PExecutionContext,
+) -> PolarsResult<Vec<Series>> {
+    let chunk = DataChunk::new(0, df.clone());
+    aggs.iter()
+        .map(|agg| agg.evaluate(&chunk, groups, context))
+        .collect()
+}
+
+/// The timestamps of the index column and their time unit. Integer indexes are
+/// treated as nanoseconds, like the in-memory engine does.
+fn index_values(index: &Series, operation: &str) -> PolarsResult<(Int64Chunked, TimeUnit)> {
+    polars_ensure!(
+        index.null_count() == 0,
+        ComputeError: "null values in dynamic groupby not supported, fill nulls."
+    );
+    let (ts, tu) = match index.dtype() {
+        DataType::Datetime(tu, _) => (index.cast(&DataType::Int64)?, *tu),
+        DataType::Date => (
+            index
+                .cast(&DataType::Datetime(TimeUnit::Milliseconds, None))?
+                .cast(&DataType::Int64)?,
+            TimeUnit::Milliseconds,
+        ),
+        DataType::Int32 | DataType::Int64 => (index.cast(&DataType::Int64)?, TimeUnit::Nanoseconds),
+        dt => polars_bail!(
+            ComputeError:
+            "expected any of the following dtypes: {{ Date, Datetime, Int32, Int64 }}, got {}",
+            dt
+        ),
+    };
+    let ts = ts.i64()?.rechunk();
+    polars_ensure!(
+        ts.cont_slice().unwrap().is_sorted_ascending(),
+        InvalidOperation: "argument in operation '{}' is not sorted", operation
+    );
+    Ok((ts, tu))
+}
+
+/// The signed length of the duration in the given time unit.
+fn duration_in(duration: &Duration, tu: TimeUnit) -> i64 {
+    let d = match tu {
+        TimeUnit::Nanoseconds => duration.duration_ns(),
+        TimeUnit::Microseconds => duration.duration_us(),
+        TimeUnit::Milliseconds => duration.duration_ms(),
+    };
+    if duration.negative() {
+        -d
+    } else {
+        d
+    }
+}
+
+/// Timestamps as a column of the dtype of the index, or as `Datetime` if the
+/// index is a `Date`, like the boundaries of the in-memory engine.
+fn timestamps_to_series(name: &str, values: Vec<i64>, tu: TimeUnit, dtype: &DataType) -> Series {
+    let ca = Int64Chunked::from_vec(name, values);
+    match dtype {
+        DataType::Int64 => ca.into_series(),
+        DataType::Int32 => ca.cast(&DataType::Int32).unwrap(),
+        _ => ca.into_datetime(tu, None).into_series(),
+    }
+}
+
+/// Restores the order of the chunks and feeds them to the windows.
+struct OrderedWindows<W> {
+    windows: W,
+    // the rows that may still be a member of a window that isn't aggregated
+    carry: DataFrame,
+    // the last timestamp that was seen, as the carry may be empty
+    last: Option<i64>,
+    // the chunks that arrived before the chunks that precede them
+    pending: BTreeMap<IdxSize, DataFrame>,
+    next_chunk: IdxSize,
+    out: Vec<DataFrame>,
+}
+
+impl<W: Windows> OrderedWindows<W> {
+    fn process(
+        &mut self,
+        df: DataFrame,
+        context: &PExecutionContext,
+        finished: bool,
+    ) -> PolarsResult<()> {
+        let carried = self.carry.height();
+        let mut df = if carried == 0 {
+            df
+        } else {
+            let mut carry = std::mem::take(&mut self.carry);
+            carry.vstack_mut(&df)?;
+            carry
+        };
+        df.as_single_chunk_par();
+
+        let (ts, tu) = index_values(df.column(self.windows.index_column())?, W::OPERATION)?;
+        let ts = ts.cont_slice().unwrap();
+        if let (Some(&first), Some(last)) = (ts.get(carried), self.last) {
+            polars_ensure!(
+                first >= last,
+                InvalidOperation: "argument in operation '{}' is not sorted", W::OPERATION
+            );
+        }
+        if let Some(&last) = ts.last() {
+            self.last = Some(last);
+        }
+
+        let (out, keep_from) = self.windows.process(&df, ts, tu, context, finished)?;
+        if out.height() > 0 || (finished && self.out.is_empty()) {
+            self.out.push(out);
+        }
+        self.carry = df.slice(keep_from as i64, df.height() - keep_from);
+        Ok(())
+    }
+
+    fn sink(&mut self, context: &PExecutionContext, chunk: DataChunk) -> PolarsResult<()> {
+        if chunk.chunk_index > self.next_chunk {
+            // wait for the chunks that precede this one
+            self.pending.insert(chunk.chunk_index, chunk.data);
+            return Ok(());
+        }
+        self.process(chunk.data, context, false)?;
+        self.next_chunk += 1;
+        while let Some(df) = self.pending.remove(&self.next_chunk) {
+            self.process(df, context, false)?;
+            self.next_chunk += 1;
+        }
+        Ok(())
+    }
+
+    fn finalize(&mut self, context: &PExecutionContext) -> PolarsResult<DataFrame> {
+        let pending = std::mem::take(&mut self.pending);
+        for df in pending.into_values() {
+            self.process(df, context, false)?;
+        }
+        let empty = self.carry.clear();
+        self.process(empty, context, true)?;
+        Ok(accumulate_dataframes_vertical_unchecked(std::mem::take(
+            &mut self.out,
+        )))
+    }
+}
+
+/// A temporal groupby sink. The splits share their state, as a window can span chunks
+/// that are sent to different threads.
+pub(crate) struct TemporalGroupbySink<W> {
+    state: Arc<Mutex<OrderedWindows<W>>>,
+    slice: Option<(i64, usize)>,
+}
+
+impl<W: Windows> TemporalGroupbySink<W> {
+    pub(crate) fn new(windows: W, input_schema: &Schema, slice: Option<(i64, usize)>) -> Self {
+        let state = OrderedWindows {
+            windows,
+            carry: DataFrame::from(input_schema),
+            last: None,
+            pending: Default::default(),
+            next_chunk: 0,
+            out: vec![],
+        };
+        Self {
+            state: Arc::new(Mutex::new(state)),
+            slice,
+        }
+    }
+}
+
+impl<W: Windows> Sink for TemporalGroupbySink<W> {
+    fn sink(&mut self, context: &PExecutionContext, chunk: DataChunk) -> PolarsResult<SinkResult> {
+        let mut state = self.state.lock().unwrap();
+        state.sink(context, chunk)?;
+        Ok(SinkResult::CanHaveMoreInput)
+    }
+
+    fn combine(&mut self, _other: &mut dyn Sink) {
+        // the splits share their state
+    }
+
+    fn split(&self, _thread_no: usize) -> Box<dyn Sink> {
+        Box::new(Self {
+            state: self.state.clone(),
+            slice: self.slice,
+        })
+    }
+
+    fn finalize(&mut self, context: &PExecutionContext) -> PolarsResult<FinalizedSink> {
+        let mut state = self.state.lock().unwrap();
+        let out = state.finalize(context)?;
+        let out = match self.slice {
+            Some((offset, len)) => out.slice(offset, len),
+            None => out,
+        };
+        Ok(FinalizedSink::Finished(out))
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn fmt(&self) -> &str {
+        W::NAME
+    }
+}
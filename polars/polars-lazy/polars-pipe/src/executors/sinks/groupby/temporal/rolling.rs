@@ -0,0 +1,81 @@
+use super::*;
+
+/// The windows of a `groupby_rolling`. Every row has a window from `t + offset`
+/// to `t + offset + period`.
+pub(crate) struct RollingWindows {
+    options: RollingGroupOptions,
+    aggs: Vec<WindowAgg>,
+    // the first row that isn't aggregated yet
+    next_row: usize,
+}
+
+impl RollingWindows {
+    pub(crate) fn new(options: RollingGroupOptions, aggs: Vec<WindowAgg>) -> Self {
+        Self {
+            options,
+            aggs,
+            next_row: 0,
+        }
+    }
+}
+
+impl Windows for RollingWindows {
+    const NAME: &'static str = "rolling_groupby";
+    const OPERATION: &'static str = "groupby_rolling";
+
+    fn index_column(&self) -> &str {
+        &self.options.index_column
+    }
+
+    fn process(
+        &mut self,
+        df: &DataFrame,
+        ts: &[i64],
+        tu: TimeUnit,
+        context: &PExecutionContext,
+        finished: bool,
+    ) -> PolarsResult<(DataFrame, usize)> {
+        let index = df.column(&self.options.index_column)?;
+        let offset = duration_in(&self.options.offset, tu);
+        let period = duration_in(&self.options.period, tu);
+
+        let last = ts.last().copied().unwrap_or_default();
+        let done = if finished {
+            ts.len()
+        } else {
+            // the rows that follow are >= `last`, so they may still be a member
+            // of the windows that end at or after `last`
+            ts.partition_point(|t| t + offset + period < last)
+        };
+        let done = std::cmp::max(done, self.next_row);
+        let len = done - self.next_row;
+
+        let groups = if len > 0 {
+            let groups = groupby_values(
+                self.options.period,
+                self.options.offset,
+                ts,
+                self.options.closed_window,
+                tu,
+                None,
+            )?;
+            groups[self.next_row..done].to_vec()
+        } else {
+            vec![]
+        };
+        let groups = GroupsProxy::Slice {
+            groups,
+            rolling: true,
+        };
+        let mut columns = Vec::with_capacity(self.aggs.len() + 1);
+        columns.push(index.slice(self.next_row as i64, len));
+        columns.extend(aggregate(&self.aggs, df, &groups, context)?);
+
+        // the rows before the window of the next row are not needed anymore
+        let next_start = ts.get(done).copied().unwrap_or(last) + offset;
+        let keep_from = std::cmp::min(ts.partition_point(|t| *t < next_start), done);
+        self.next_row = done - keep_from;
+
+        Ok((DataFrame::new_no_checks(columns), keep_from))
+    }
+}
@@ -2,12 +2,15 @@ use std::fs::File;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
+use std::thread::JoinHandle;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use crossbeam_channel::{bounded, Sender};
 use polars_core::prelude::*;
+use polars_core::series::IsSorted;
 use polars_core::utils::arrow::temporal_conversions::SECONDS_IN_DAY;
 use polars_io::prelude::*;
+use polars_plan::frame::StreamingConfig;
 
 use crate::pipeline::morsels_per_sink;
 
@@ -16,15 +19,19 @@ pub(in crate::executors::sinks) type DfIter =
 // The Option<IdxCa> are the partitions it should be written to, if any
 type Payload = (Option<IdxCa>, DfIter);
 
-/// A helper that can be used to spill to disk
+/// A helper that can be used to spill to disk.
+///
+/// The spill directory is deleted when this is dropped, also if the query fails or panics.
 pub(crate) struct IOThread {
     sender: Sender<Payload>,
+    handle: Option<JoinHandle<()>>,
     _lockfile: Arc<LockFile>,
     pub(in crate::executors::sinks) dir: PathBuf,
     pub(in crate::executors::sinks) sent: Arc<AtomicUsize>,
     pub(in crate::executors::sinks) total: Arc<AtomicUsize>,
     pub(in crate::executors::sinks) thread_local_count: Arc<AtomicUsize>,
-    schema: SchemaRef,
+    pub(in crate::executors::sinks) schema: SchemaRef,
+    compression: Option<IpcCompression>,
 }
 
 fn get_lockfile_path(dir: &Path) -> PathBuf {
@@ -35,9 +42,9 @@ fn get_lockfile_path(dir: &Path) -> PathBuf {
 
 /// Starts a new thread that will clean up operations of directories that don't
 /// have a lockfile (opened with 'w' permissions).
-fn gc_thread(operation_name: &'static str) {
+fn gc_thread(spill_dir: PathBuf, operation_name: &'static str) {
     let _ = std::thread::spawn(move || {
-        let mut dir = std::env::temp_dir();
+        let mut dir = spill_dir;
         dir.push(&format!("polars/{operation_name}"));
 
         // if the directory does not exist, there is nothing to clean
@@ -77,15 +84,17 @@ impl IOThread {
     pub(in crate::executors::sinks) fn try_new(
         // Schema of the file that will be dumped to disk
         schema: SchemaRef,
-        // Will be used as subdirectory name in `~/.polars/`
+        // Will be used as subdirectory name in `{spill_dir}/polars/`
         operation_name: &'static str,
+        config: &StreamingConfig,
     ) -> PolarsResult<Self> {
         let uuid = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .unwrap()
             .as_nanos();
 
-        let mut dir = std::env::temp_dir();
+        let spill_dir = config.spill_dir.clone().unwrap_or_else(std::env::temp_dir);
+        let mut dir = spill_dir.clone();
         dir.push(&format!("polars/{operation_name}/{uuid}"));
         std::fs::create_dir_all(&dir)?;
 
@@ -95,7 +104,7 @@ impl IOThread {
 
         // start a thread that will clean up old dumps.
         // TODO: if we will have more ooc in the future  we will have a dedicated GC thread
-        gc_thread(operation_name);
+        gc_thread(spill_dir, operation_name);
 
         // we need some pushback otherwise we still could go OOM.
         let (sender, receiver) = bounded::<Payload>(morsels_per_sink() * 2);
@@ -108,7 +117,8 @@ impl IOThread {
         let total2 = total.clone();
         let lockfile2 = lockfile.clone();
         let schema2 = schema.clone();
-        std::thread::spawn(move || {
+        let compression = config.spill_compression;
+        let handle = std::thread::spawn(move || {
             let schema = schema2;
             // this moves the lockfile in the thread
             // we keep one in the thread and one in the `IoThread` struct
@@ -131,7 +141,7 @@ impl IOThread {
                         path.push(format!("{count}.ipc"));
 
                         let file = File::create(path).unwrap();
-                        let writer = IpcWriter::new(file);
+                        let writer = IpcWriter::new(file).with_compression(compression);
                         let mut writer = writer.batched(&schema).unwrap();
                         writer.write_batch(&df).unwrap();
                         writer.finish().unwrap();
@@ -142,7 +152,7 @@ impl IOThread {
                     path.push(format!("{count}.ipc"));
 
                     let file = File::create(path).unwrap();
-                    let writer = IpcWriter::new(file);
+                    let writer = IpcWriter::new(file).with_compression(compression);
                     let mut writer = writer.batched(&schema).unwrap();
 
                     for df in iter {
@@ -158,12 +168,14 @@ impl IOThread {
 
         Ok(Self {
             sender,
+            handle: Some(handle),
             dir,
             sent,
             total,
             _lockfile: lockfile,
             thread_local_count,
             schema,
+            compression,
         })
     }
 
@@ -178,7 +190,7 @@ impl IOThread {
             path.push(format!("_{count}.ipc"));
 
             let file = File::create(path).unwrap();
-            let mut writer = IpcWriter::new(file);
+            let mut writer = IpcWriter::new(file).with_compression(self.compression);
             writer.finish(&mut df).unwrap();
         } else {
             let iter = Box::new(std::iter::once(df));
@@ -206,7 +218,7 @@ impl IOThread {
         // duplicates
         path.push(format!("_{count}.ipc"));
         let file = File::create(path).unwrap();
-        let writer = IpcWriter::new(file);
+        let writer = IpcWriter::new(file).with_compression(self.compression);
         let mut writer = writer.batched(&self.schema).unwrap();
         writer.write_batch(&df).unwrap();
         writer.finish().unwrap();
@@ -221,8 +233,14 @@ impl IOThread {
 
 impl Drop for IOThread {
     fn drop(&mut self) {
-        // we drop the lockfile explicitly as the thread GC will leak.
-        std::fs::remove_file(&self._lockfile.path).unwrap();
+        // disconnect the channel so that the IO thread stops after the pending writes
+        let (sender, _) = bounded(0);
+        drop(std::mem::replace(&mut self.sender, sender));
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+        // this also removes the lockfile, the directory is not needed anymore
+        let _ = std::fs::remove_dir_all(&self.dir);
     }
 }
 
@@ -254,3 +272,31 @@ impl Drop for LockFile {
         let _ = std::fs::remove_file(&self.path);
     }
 }
+
+/// Split `df` in the given `partitions`. Returns the dataframe of every partition and the
+/// partition number they belong to, so that they can be sent to [`IOThread::dump_iter`].
+pub(in crate::executors::sinks) fn partition_df(
+    df: DataFrame,
+    partitions: &IdxCa,
+) -> PolarsResult<(DfIter, IdxCa)> {
+    let groups = partitions.group_tuples(false, false)?;
+    let partitions = unsafe { partitions.clone().into_series().agg_first(&groups) };
+    let partitions = partitions.idx().unwrap().clone();
+
+    let out = match groups {
+        GroupsProxy::Idx(idx) => {
+            let iter = idx.into_iter().map(move |(_, group)| {
+                // groups are in bounds and sorted
+                unsafe { df._take_unchecked_slice_sorted(&group, false, IsSorted::Ascending) }
+            });
+            Box::new(iter) as DfIter
+        }
+        GroupsProxy::Slice { groups, .. } => {
+            let iter = groups
+                .into_iter()
+                .map(move |[first, len]| df.slice(first as i64, len as usize));
+            Box::new(iter) as DfIter
+        }
+    };
+    Ok((out, partitions))
+}
@@ -9,10 +9,15 @@ use polars_core::export::ahash::RandomState;
 use polars_core::frame::hash_join::ChunkId;
 use polars_core::prelude::*;
 use polars_core::utils::{_set_partition_size, accumulate_dataframes_vertical_unchecked};
+use polars_plan::frame::StreamingConfig;
 use polars_utils::hash_to_partition;
 use polars_utils::slice::GetSaferUnchecked;
 
 use crate::executors::sinks::joins::inner_left::GenericJoinProbe;
+use crate::executors::sinks::joins::ooc::{GenericOocJoinProbe, OocState};
+use crate::executors::sinks::joins::outer::GenericOuterJoinProbe;
+#[cfg(feature = "semi_anti_join")]
+use crate::executors::sinks::joins::semi_anti::SemiAntiJoinProbe;
 use crate::executors::sinks::utils::{hash_rows, load_vec};
 use crate::executors::sinks::HASHMAP_INIT_SIZE;
 use crate::expressions::PhysicalPipedExpr;
@@ -73,6 +78,13 @@ pub struct GenericBuild {
     join_type: JoinType,
     // the join order is swapped to ensure we hash the smaller table
     swapped: bool,
+    // schema of the table that is streamed through the probe operator
+    probe_schema: SchemaRef,
+    // spills the build table to disk if we run out of memory
+    pub(super) ooc: OocState,
+    // `combine` cannot fail, so an error while spilling is returned by the next
+    // `sink` or `finalize`
+    spill_error: Option<PolarsError>,
 }
 
 impl GenericBuild {
@@ -82,6 +94,8 @@ impl GenericBuild {
         swapped: bool,
         join_columns_left: Arc<Vec<Arc<dyn PhysicalPipedExpr>>>,
         join_columns_right: Arc<Vec<Arc<dyn PhysicalPipedExpr>>>,
+        probe_schema: SchemaRef,
+        config: &StreamingConfig,
     ) -> Self {
         let hb: RandomState = Default::default();
         let partitions = _set_partition_size();
@@ -98,6 +112,9 @@ impl GenericBuild {
             materialized_join_cols: vec![],
             hash_tables,
             hashes: vec![],
+            probe_schema,
+            ooc: OocState::new(config),
+            spill_error: None,
         }
     }
 }
@@ -137,18 +154,26 @@ impl GenericBuild {
         }
     }
 
-    fn set_join_series(
+    fn encode_join_rows(
         &mut self,
         context: &PExecutionContext,
         chunk: &DataChunk,
-    ) -> PolarsResult<&BinaryArray<i64>> {
+    ) -> PolarsResult<BinaryArray<i64>> {
         debug_assert!(self.join_columns.is_empty());
         for phys_e in self.join_columns_left.iter() {
             let s = phys_e.evaluate(chunk, context.execution_state.as_any())?;
             let arr = s.to_physical_repr().rechunk().array_ref(0).clone();
             self.join_columns.push(arr);
         }
-        let rows_encoded = polars_row::convert_columns_no_order(&self.join_columns).into_array();
+        Ok(polars_row::convert_columns_no_order(&self.join_columns).into_array())
+    }
+
+    fn set_join_series(
+        &mut self,
+        context: &PExecutionContext,
+        chunk: &DataChunk,
+    ) -> PolarsResult<&BinaryArray<i64>> {
+        let rows_encoded = self.encode_join_rows(context, chunk)?;
         self.materialized_join_cols.push(rows_encoded);
         Ok(self.materialized_join_cols.last().unwrap())
     }
@@ -157,10 +182,101 @@ impl GenericBuild {
             .get_unchecked_release(chunk_idx as usize)
             .value_unchecked(df_idx as usize)
     }
+
+    fn spill(&mut self, df: DataFrame, rows: &BinaryArray<i64>) -> PolarsResult<()> {
+        let mut hashes = std::mem::take(&mut self.hashes);
+        hash_rows(rows, &mut hashes, &self.hb);
+        self.ooc.dump(df, &hashes)?;
+        hashes.clear();
+        self.hashes = hashes;
+        Ok(())
+    }
+
+    fn spill_chunk(&mut self, context: &PExecutionContext, chunk: DataChunk) -> PolarsResult<()> {
+        let rows = self.encode_join_rows(context, &chunk)?;
+        self.join_columns.clear();
+        self.spill(chunk.data, &rows)
+    }
+
+    /// Spill the build table that is in memory to disk and release the hash tables.
+    fn spill_in_memory(&mut self) -> PolarsResult<()> {
+        let chunks = std::mem::take(&mut self.chunks);
+        let materialized_join_cols = std::mem::take(&mut self.materialized_join_cols);
+        // empty chunks don't have materialized join columns
+        for (chunk, rows) in chunks
+            .into_iter()
+            .filter(|chunk| !chunk.is_empty())
+            .zip(materialized_join_cols.iter())
+        {
+            self.spill(chunk.data, rows)?;
+        }
+        self.hash_tables
+            .iter_mut()
+            .for_each(|table| *table = Default::default());
+        Ok(())
+    }
+
+    fn take_spill_error(&mut self) -> PolarsResult<()> {
+        match self.spill_error.take() {
+            Some(err) => Err(err),
+            None => Ok(()),
+        }
+    }
+
+    /// Stack the chunks of the build table into a single (not rechunked) dataframe.
+    /// Also returns the row offset of every chunk.
+    fn take_build_df(&mut self) -> (DataFrame, Vec<IdxSize>) {
+        let chunks_len = self.chunks.len();
+        let mut chunk_offsets = Vec::with_capacity(chunks_len);
+        let mut offset = 0 as IdxSize;
+        for chunk in &self.chunks {
+            chunk_offsets.push(offset);
+            offset += chunk.data.height() as IdxSize;
+        }
+        let df = accumulate_dataframes_vertical_unchecked(
+            std::mem::take(&mut self.chunks)
+                .into_iter()
+                .map(|chunk| chunk.data),
+        );
+        if df.height() > 0 {
+            assert_eq!(df.n_chunks(), chunks_len);
+        }
+        (df, chunk_offsets)
+    }
+}
+
+impl GenericBuild {
+    fn finalize_ooc(&mut self) -> PolarsResult<FinalizedSink> {
+        self.spill_in_memory()?;
+        let build_io_thread = self.ooc.finish();
+
+        // the partitions are joined in memory
+        let mut partition_build = Self::new(
+            self.suffix.clone(),
+            self.join_type.clone(),
+            self.swapped,
+            self.join_columns_left.clone(),
+            self.join_columns_right.clone(),
+            self.probe_schema.clone(),
+            &self.ooc.config,
+        );
+        partition_build.ooc = OocState::in_memory(&self.ooc.config);
+
+        let probe_operator = GenericOocJoinProbe::new(
+            build_io_thread,
+            self.probe_schema.clone(),
+            partition_build,
+            self.hb.clone(),
+            self.join_columns_right.clone(),
+            self.join_type.clone(),
+        )?;
+        Ok(FinalizedSink::Operator(Box::new(probe_operator)))
+    }
 }
 
 impl Sink for GenericBuild {
     fn sink(&mut self, context: &PExecutionContext, chunk: DataChunk) -> PolarsResult<SinkResult> {
+        self.take_spill_error()?;
         // we do some juggling here so that we don't
         // end up with empty chunks
         // But we always want one empty chunk if all is empty as we need
@@ -169,11 +285,16 @@ impl Sink for GenericBuild {
             self.chunks.pop().unwrap();
         }
         if chunk.is_empty() {
-            if self.chunks.is_empty() {
+            if self.chunks.is_empty() && !self.ooc.ooc {
                 self.chunks.push(chunk)
             }
             return Ok(SinkResult::CanHaveMoreInput);
         }
+        if self.ooc.check_memory_usage(&chunk)? {
+            self.spill_in_memory()?;
+            self.spill_chunk(context, chunk)?;
+            return Ok(SinkResult::CanHaveMoreInput);
+        }
         let mut hashes = std::mem::take(&mut self.hashes);
         let rows = self.set_join_series(context, &chunk)?.clone();
         hash_rows(&rows, &mut hashes, &self.hb);
@@ -215,14 +336,26 @@ impl Sink for GenericBuild {
     }
 
     fn combine(&mut self, other: &mut dyn Sink) {
+        let other = other.as_any().downcast_mut::<Self>().unwrap();
+        if self.spill_error.is_none() {
+            self.spill_error = other.spill_error.take();
+        }
+        if self.ooc.ooc || other.ooc.ooc {
+            // once a single thread is out-of-core, the whole build table goes to disk
+            self.ooc.ooc = true;
+            other.ooc.ooc = true;
+            let spilled = self.spill_in_memory().and_then(|_| other.spill_in_memory());
+            if let Err(err) = spilled {
+                self.spill_error.get_or_insert(err);
+            }
+            return;
+        }
         if self.is_empty() {
-            let other = other.as_any().downcast_mut::<Self>().unwrap();
             if !other.is_empty() {
                 std::mem::swap(self, other);
             }
             return;
         }
-        let other = other.as_any().downcast_ref::<Self>().unwrap();
         if other.is_empty() {
             return;
         }
@@ -279,37 +412,35 @@ impl Sink for GenericBuild {
             self.swapped,
             self.join_columns_left.clone(),
             self.join_columns_right.clone(),
+            self.probe_schema.clone(),
+            &self.ooc.config,
         );
         new.hb = self.hb.clone();
+        new.ooc = self.ooc.clone();
         Box::new(new)
     }
 
     fn finalize(&mut self, context: &PExecutionContext) -> PolarsResult<FinalizedSink> {
+        self.take_spill_error()?;
+        if self.ooc.ooc {
+            return self.finalize_ooc();
+        }
+        let materialized_join_cols = Arc::new(std::mem::take(&mut self.materialized_join_cols));
+        let suffix = self.suffix.clone();
+        let hb = self.hb.clone();
+        let hash_tables = Arc::new(std::mem::take(&mut self.hash_tables));
+        let join_columns_left = self.join_columns_left.clone();
+        let join_columns_right = self.join_columns_right.clone();
+
+        // take the buffers, this saves one allocation
+        let mut join_series = std::mem::take(&mut self.join_columns);
+        join_series.clear();
+        let mut hashes = std::mem::take(&mut self.hashes);
+        hashes.clear();
+
         match self.join_type {
             JoinType::Inner | JoinType::Left => {
-                let chunks_len = self.chunks.len();
-                let left_df = accumulate_dataframes_vertical_unchecked(
-                    std::mem::take(&mut self.chunks)
-                        .into_iter()
-                        .map(|chunk| chunk.data),
-                );
-                if left_df.height() > 0 {
-                    assert_eq!(left_df.n_chunks(), chunks_len);
-                }
-                let materialized_join_cols =
-                    Arc::new(std::mem::take(&mut self.materialized_join_cols));
-                let suffix = self.suffix.clone();
-                let hb = self.hb.clone();
-                let hash_tables = Arc::new(std::mem::take(&mut self.hash_tables));
-                let join_columns_left = self.join_columns_left.clone();
-                let join_columns_right = self.join_columns_right.clone();
-
-                // take the buffers, this saves one allocation
-                let mut join_series = std::mem::take(&mut self.join_columns);
-                join_series.clear();
-                let mut hashes = std::mem::take(&mut self.hashes);
-                hashes.clear();
-
+                let (left_df, _) = self.take_build_df();
                 let probe_operator = GenericJoinProbe::new(
                     left_df,
                     materialized_join_cols,
@@ -326,6 +457,40 @@ impl Sink for GenericBuild {
                 );
                 Ok(FinalizedSink::Operator(Box::new(probe_operator)))
             }
+            JoinType::Outer => {
+                let (left_df, chunk_offsets) = self.take_build_df();
+                let probe_operator = GenericOuterJoinProbe::new(
+                    left_df,
+                    self.probe_schema.clone(),
+                    materialized_join_cols,
+                    suffix,
+                    hb,
+                    hash_tables,
+                    chunk_offsets,
+                    join_columns_left,
+                    join_columns_right,
+                    self.swapped,
+                    join_series,
+                    hashes,
+                    context,
+                )?;
+                Ok(FinalizedSink::Operator(Box::new(probe_operator)))
+            }
+            #[cfg(feature = "semi_anti_join")]
+            JoinType::Semi | JoinType::Anti => {
+                // only the keys of the build table are needed
+                self.chunks.clear();
+                let probe_operator = SemiAntiJoinProbe::new(
+                    materialized_join_cols,
+                    hb,
+                    hash_tables,
+                    join_columns_right,
+                    join_series,
+                    hashes,
+                    matches!(self.join_type, JoinType::Anti),
+                );
+                Ok(FinalizedSink::Operator(Box::new(probe_operator)))
+            }
             _ => unimplemented!(),
         }
     }
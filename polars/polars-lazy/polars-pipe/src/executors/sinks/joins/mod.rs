@@ -2,6 +2,10 @@
 mod cross;
 mod generic_build;
 mod inner_left;
+mod ooc;
+mod outer;
+#[cfg(feature = "semi_anti_join")]
+mod semi_anti;
 
 #[cfg(feature = "cross_join")]
 pub(crate) use cross::*;
@@ -0,0 +1,312 @@
+use std::fs::File;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
+
+use polars_arrow::export::arrow::array::BinaryArray;
+use polars_core::config::verbose;
+use polars_core::error::PolarsResult;
+use polars_core::export::ahash::RandomState;
+use polars_core::prelude::*;
+use polars_core::utils::accumulate_dataframes_vertical_unchecked;
+use polars_io::ipc::IpcReader;
+use polars_io::SerReader;
+use polars_plan::frame::StreamingConfig;
+use polars_row::RowsEncoded;
+use polars_utils::hash_to_partition;
+
+use crate::executors::sinks::io::{block_thread_until_io_thread_done, partition_df, IOThread};
+use crate::executors::sinks::joins::generic_build::GenericBuild;
+use crate::executors::sinks::memory::MemTracker;
+use crate::executors::sinks::utils::hash_rows;
+use crate::expressions::PhysicalPipedExpr;
+use crate::operators::{
+    DataChunk, FinalizedSink, Operator, OperatorResult, PExecutionContext, Sink,
+};
+use crate::pipeline::{morsels_per_sink, FORCE_OOC, PARTITION_SIZE};
+
+// If the free memory drops below this fraction of the free memory at the
+// start of the join, we spill the build table to disk.
+const TO_DISK_THRESHOLD: f64 = 0.3;
+
+/// Keeps track of the memory pressure of a join build and spills
+/// the build table to disk once we go out-of-core.
+#[derive(Clone)]
+pub(super) struct OocState {
+    // Stores available memory in the system at the start of this sink.
+    // and stores the memory used by this this sink.
+    mem_track: MemTracker,
+    // join in-memory or out-of-core
+    pub(super) ooc: bool,
+    // when ooc, we write to disk using an IO thread
+    io_thread: Arc<Mutex<Option<IOThread>>>,
+    to_disk_threshold: f64,
+    pub(super) config: StreamingConfig,
+}
+
+impl OocState {
+    pub(super) fn new(config: &StreamingConfig) -> Self {
+        let to_disk_threshold = if std::env::var(FORCE_OOC).is_ok() {
+            1.0
+        } else {
+            TO_DISK_THRESHOLD
+        };
+        Self {
+            mem_track: MemTracker::new(morsels_per_sink(), config.memory_budget),
+            ooc: false,
+            io_thread: Default::default(),
+            to_disk_threshold,
+            config: config.clone(),
+        }
+    }
+
+    /// A state that never goes out-of-core. Used to join the spilled partitions,
+    /// which are expected to fit in memory.
+    pub(super) fn in_memory(config: &StreamingConfig) -> Self {
+        Self {
+            to_disk_threshold: 0.0,
+            ..Self::new(config)
+        }
+    }
+
+    fn init_ooc(&mut self, spill_schema: SchemaRef) -> PolarsResult<()> {
+        if verbose() {
+            eprintln!("OOC join started");
+        }
+        self.ooc = true;
+
+        // start IO thread
+        let mut iot = self.io_thread.lock().unwrap();
+        if iot.is_none() {
+            *iot = Some(IOThread::try_new(spill_schema, "join_build", &self.config)?)
+        }
+        Ok(())
+    }
+
+    /// Returns `true` if the build table must be spilled to disk.
+    pub(super) fn check_memory_usage(&mut self, chunk: &DataChunk) -> PolarsResult<bool> {
+        if self.ooc {
+            return Ok(true);
+        }
+        if self.mem_track.free_memory_fraction_since_start() < self.to_disk_threshold {
+            self.init_ooc(Arc::new(chunk.data.schema()))?;
+        }
+        Ok(self.ooc)
+    }
+
+    pub(super) fn dump(&self, df: DataFrame, hashes: &[u64]) -> PolarsResult<()> {
+        let iot = self.io_thread.lock().unwrap();
+        dump_partitioned(iot.as_ref().unwrap(), df, hashes)
+    }
+
+    /// Take the IO thread once all data is sent and block until it is written.
+    pub(super) fn finish(&self) -> IOThread {
+        let iot = self.io_thread.lock().unwrap().take().unwrap();
+        block_thread_until_io_thread_done(&iot);
+        iot
+    }
+}
+
+/// Partition the rows of `df` by the hashes of their join keys and send them to disk.
+/// The build and probe tables use the same hasher, so matching rows end up in the
+/// same partition.
+fn dump_partitioned(io_thread: &IOThread, df: DataFrame, hashes: &[u64]) -> PolarsResult<()> {
+    let partitions = hashes
+        .iter()
+        .map(|h| hash_to_partition(*h, PARTITION_SIZE) as IdxSize)
+        .collect::<Vec<_>>();
+    let partitions = IdxCa::from_vec("", partitions);
+    let (iter, partitions) = partition_df(df, &partitions)?;
+    io_thread.dump_iter(Some(partitions), iter);
+    Ok(())
+}
+
+fn read_partition(dir: &Path, partition: usize) -> PolarsResult<Vec<DataFrame>> {
+    let dir = dir.join(format!("{partition}"));
+    if !dir.exists() {
+        return Ok(vec![]);
+    }
+    std::fs::read_dir(dir)?
+        .map(|entry| {
+            let file = File::open(entry?.path())?;
+            IpcReader::new(file).set_rechunk(false).finish()
+        })
+        .collect()
+}
+
+/// Whether a join produces output for the probe rows that have no match.
+fn keeps_unmatched_probe(join_type: &JoinType) -> bool {
+    match join_type {
+        JoinType::Left | JoinType::Outer => true,
+        #[cfg(feature = "semi_anti_join")]
+        JoinType::Anti => true,
+        _ => false,
+    }
+}
+
+/// Probe side of an out-of-core join, similar to a grace hash join.
+///
+/// The build table is spilled to disk in partitions. The probe chunks are partitioned
+/// the same way and spilled as well. Once all probe chunks are seen, the partitions are
+/// joined one by one in memory (see `flush`).
+#[derive(Clone)]
+pub struct GenericOocJoinProbe {
+    // holding these keeps the lockfiles in place
+    build_io_thread: Arc<IOThread>,
+    probe_io_thread: Arc<IOThread>,
+    // an in-memory build that joins a single partition
+    partition_build: Arc<GenericBuild>,
+    hb: RandomState,
+    // the columns that will be joined on
+    join_columns_probe: Arc<Vec<Arc<dyn PhysicalPipedExpr>>>,
+    join_type: JoinType,
+
+    // amortize allocations
+    current_rows: RowsEncoded,
+    join_columns: Vec<ArrayRef>,
+    hashes: Vec<u64>,
+    // the next partition that will be joined
+    partition: usize,
+}
+
+impl GenericOocJoinProbe {
+    pub(super) fn new(
+        build_io_thread: IOThread,
+        probe_schema: SchemaRef,
+        partition_build: GenericBuild,
+        hb: RandomState,
+        join_columns_probe: Arc<Vec<Arc<dyn PhysicalPipedExpr>>>,
+        join_type: JoinType,
+    ) -> PolarsResult<Self> {
+        let probe_io_thread =
+            IOThread::try_new(probe_schema, "join_probe", &partition_build.ooc.config)?;
+        Ok(GenericOocJoinProbe {
+            build_io_thread: Arc::new(build_io_thread),
+            probe_io_thread: Arc::new(probe_io_thread),
+            partition_build: Arc::new(partition_build),
+            hb,
+            join_columns_probe,
+            join_type,
+            current_rows: Default::default(),
+            join_columns: vec![],
+            hashes: vec![],
+            partition: 0,
+        })
+    }
+
+    fn set_join_series(
+        &mut self,
+        context: &PExecutionContext,
+        chunk: &DataChunk,
+    ) -> PolarsResult<BinaryArray<i64>> {
+        debug_assert!(self.join_columns.is_empty());
+        for phys_e in self.join_columns_probe.iter() {
+            let s = phys_e.evaluate(chunk, context.execution_state.as_any())?;
+            let s = s.to_physical_repr().rechunk();
+            self.join_columns.push(s.array_ref(0).clone());
+        }
+        polars_row::convert_columns_amortized_no_order(&self.join_columns, &mut self.current_rows);
+
+        // safety: we keep rows-encode alive
+        unsafe { Ok(self.current_rows.borrow_array()) }
+    }
+
+    /// Join the spilled build and probe tables of a single partition.
+    fn join_partition(
+        &self,
+        context: &PExecutionContext,
+        partition: usize,
+    ) -> PolarsResult<Option<DataFrame>> {
+        let build_dfs = read_partition(&self.build_io_thread.dir, partition)?;
+        let probe_dfs = read_partition(&self.probe_io_thread.dir, partition)?;
+        // only an outer join produces output for the build rows that have no match
+        if probe_dfs.is_empty() && !matches!(self.join_type, JoinType::Outer) {
+            return Ok(None);
+        }
+        if build_dfs.is_empty() && !keeps_unmatched_probe(&self.join_type) {
+            return Ok(None);
+        }
+
+        let mut build = self.partition_build.split(0);
+        if build_dfs.is_empty() {
+            // we need a single empty chunk to finish the join
+            let df = DataFrame::from(self.build_io_thread.schema.as_ref());
+            build.sink(context, DataChunk::new(0, df))?;
+        }
+        for (i, df) in build_dfs.into_iter().enumerate() {
+            build.sink(context, DataChunk::new(i as IdxSize, df))?;
+        }
+        let FinalizedSink::Operator(mut probe) = build.finalize(context)? else {
+            unreachable!()
+        };
+
+        let mut out = Vec::with_capacity(probe_dfs.len() + 1);
+        for (i, df) in probe_dfs.into_iter().enumerate() {
+            let chunk = DataChunk::new(i as IdxSize, df);
+            loop {
+                match probe.execute(context, &chunk)? {
+                    OperatorResult::Finished(chunk) => {
+                        out.push(chunk.data);
+                        break;
+                    }
+                    OperatorResult::HaveMoreOutPut(chunk) => out.push(chunk.data),
+                    OperatorResult::NeedsNewData => break,
+                }
+            }
+        }
+        if let Some(df) = probe.flush(context)? {
+            out.push(df)
+        }
+
+        if out.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(accumulate_dataframes_vertical_unchecked(out)))
+        }
+    }
+}
+
+impl Operator for GenericOocJoinProbe {
+    fn execute(
+        &mut self,
+        context: &PExecutionContext,
+        chunk: &DataChunk,
+    ) -> PolarsResult<OperatorResult> {
+        if !chunk.is_empty() {
+            let mut hashes = std::mem::take(&mut self.hashes);
+            let rows = self.set_join_series(context, chunk)?;
+            hash_rows(&rows, &mut hashes, &self.hb);
+            dump_partitioned(&self.probe_io_thread, chunk.data.clone(), &hashes)?;
+
+            // clear memory
+            hashes.clear();
+            self.hashes = hashes;
+            self.join_columns.clear();
+        }
+        // the output is produced when the partitions are joined
+        Ok(OperatorResult::NeedsNewData)
+    }
+
+    fn flush(&mut self, context: &PExecutionContext) -> PolarsResult<Option<DataFrame>> {
+        if self.partition == 0 {
+            block_thread_until_io_thread_done(&self.probe_io_thread);
+        }
+        // we return the output partition by partition, so that
+        // the output doesn't need to fit in memory
+        while self.partition < PARTITION_SIZE {
+            let partition = self.partition;
+            self.partition += 1;
+            if let Some(df) = self.join_partition(context, partition)? {
+                return Ok(Some(df));
+            }
+        }
+        Ok(None)
+    }
+
+    fn split(&self, _thread_no: usize) -> Box<dyn Operator> {
+        let new = self.clone();
+        Box::new(new)
+    }
+    fn fmt(&self) -> &str {
+        "generic_ooc_join_probe"
+    }
+}
@@ -0,0 +1,324 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use polars_arrow::export::arrow::array::BinaryArray;
+use polars_core::error::PolarsResult;
+use polars_core::export::ahash::RandomState;
+use polars_core::frame::hash_join::{ChunkId, _finish_join};
+use polars_core::prelude::*;
+use polars_core::series::IsSorted;
+use polars_row::RowsEncoded;
+use polars_utils::hash_to_partition;
+use polars_utils::slice::GetSaferUnchecked;
+use smartstring::alias::String as SmartString;
+
+use crate::executors::sinks::joins::generic_build::*;
+use crate::executors::sinks::utils::hash_rows;
+use crate::expressions::PhysicalPipedExpr;
+use crate::operators::{DataChunk, Operator, OperatorResult, PExecutionContext};
+
+/// Probe side of an outer join.
+///
+/// The probe rows are streamed through and joined with the build table like a left join.
+/// Meanwhile we keep track of the keys in the build table that found a match, so that the
+/// unmatched build rows can be emitted once all probe chunks are processed (see `flush`).
+#[derive(Clone)]
+pub struct GenericOuterJoinProbe {
+    // all chunks are stacked into a single dataframe
+    // the dataframe is not rechunked.
+    df_a: Arc<DataFrame>,
+    // the schema of the probe table
+    // needed to create the null rows of the unmatched build rows
+    probe_schema: SchemaRef,
+    // the join columns are all tightly packed
+    // the values of a join column(s) can be found
+    // by:
+    // first get the offset of the chunks and multiply that with the number of join
+    // columns
+    //      * chunk_offset = (idx * n_join_keys)
+    //      * end = (offset + n_join_keys)
+    materialized_join_cols: Arc<Vec<BinaryArray<i64>>>,
+    suffix: Arc<str>,
+    hb: RandomState,
+    // partitioned tables that will be used for probing
+    // stores the key and the chunk_idx, df_idx of the build table
+    hash_tables: Arc<Vec<PlIdHashMap<Key, Vec<ChunkId>>>>,
+    // the row offset of every chunk in `df_a`
+    chunk_offsets: Arc<Vec<IdxSize>>,
+    // per row of `df_a`, whether the key of that row found a match
+    // only the first row of a key is marked
+    matched: Arc<Vec<AtomicBool>>,
+    // the split operators share their state, only one of them should flush
+    flushed: Arc<AtomicBool>,
+
+    // the columns that will be joined on
+    join_columns_probe: Arc<Vec<Arc<dyn PhysicalPipedExpr>>>,
+    // names of the join columns of the left and right table of the output
+    key_names_left: Arc<Vec<SmartString>>,
+    key_names_right: Arc<Vec<SmartString>>,
+
+    // amortize allocations
+    current_rows: RowsEncoded,
+    join_columns: Vec<ArrayRef>,
+    join_tuples_a: Vec<Option<ChunkId>>,
+    join_tuples_b: Vec<DfIdx>,
+    hashes: Vec<u64>,
+    // the join order is swapped to ensure we hash the smaller table
+    swapped: bool,
+    // cached output names
+    output_names: Option<Vec<SmartString>>,
+}
+
+fn key_names(
+    exprs: &[Arc<dyn PhysicalPipedExpr>],
+    df: DataFrame,
+    context: &PExecutionContext,
+) -> PolarsResult<Vec<SmartString>> {
+    let tmp = DataChunk {
+        data: df,
+        chunk_index: 0,
+    };
+    exprs
+        .iter()
+        .map(|phys_e| {
+            phys_e
+                .evaluate(&tmp, context.execution_state.as_any())
+                .map(|s| SmartString::from(s.name()))
+        })
+        .collect()
+}
+
+impl GenericOuterJoinProbe {
+    #[allow(clippy::too_many_arguments)]
+    pub(super) fn new(
+        df_a: DataFrame,
+        probe_schema: SchemaRef,
+        materialized_join_cols: Arc<Vec<BinaryArray<i64>>>,
+        suffix: Arc<str>,
+        hb: RandomState,
+        hash_tables: Arc<Vec<PlIdHashMap<Key, Vec<ChunkId>>>>,
+        chunk_offsets: Vec<IdxSize>,
+        join_columns_build: Arc<Vec<Arc<dyn PhysicalPipedExpr>>>,
+        join_columns_probe: Arc<Vec<Arc<dyn PhysicalPipedExpr>>>,
+        swapped: bool,
+        join_columns: Vec<ArrayRef>,
+        hashes: Vec<u64>,
+        context: &PExecutionContext,
+    ) -> PolarsResult<Self> {
+        let key_names_build = key_names(&join_columns_build, df_a.slice(0, 0), context)?;
+        let key_names_probe = key_names(
+            &join_columns_probe,
+            DataFrame::from(probe_schema.as_ref()),
+            context,
+        )?;
+        let (key_names_left, key_names_right) = if swapped {
+            (key_names_probe, key_names_build)
+        } else {
+            (key_names_build, key_names_probe)
+        };
+        let matched = (0..df_a.height())
+            .map(|_| AtomicBool::new(false))
+            .collect::<Vec<_>>();
+
+        Ok(GenericOuterJoinProbe {
+            df_a: Arc::new(df_a),
+            probe_schema,
+            materialized_join_cols,
+            suffix,
+            hb,
+            hash_tables,
+            chunk_offsets: Arc::new(chunk_offsets),
+            matched: Arc::new(matched),
+            flushed: Arc::new(AtomicBool::new(false)),
+            join_columns_probe,
+            key_names_left: Arc::new(key_names_left),
+            key_names_right: Arc::new(key_names_right),
+            current_rows: Default::default(),
+            join_columns,
+            join_tuples_a: vec![],
+            join_tuples_b: vec![],
+            hashes,
+            swapped,
+            output_names: None,
+        })
+    }
+
+    fn set_join_series(
+        &mut self,
+        context: &PExecutionContext,
+        chunk: &DataChunk,
+    ) -> PolarsResult<BinaryArray<i64>> {
+        debug_assert!(self.join_columns.is_empty());
+        for phys_e in self.join_columns_probe.iter() {
+            let s = phys_e.evaluate(chunk, context.execution_state.as_any())?;
+            let s = s.to_physical_repr().rechunk();
+            self.join_columns.push(s.array_ref(0).clone());
+        }
+        polars_row::convert_columns_amortized_no_order(&self.join_columns, &mut self.current_rows);
+
+        // safety: we keep rows-encode alive
+        unsafe { Ok(self.current_rows.borrow_array()) }
+    }
+
+    #[inline]
+    fn mark_matched(&self, chunk_id: ChunkId) {
+        let [chunk_idx, df_idx] = chunk_id;
+        let idx = unsafe { *self.chunk_offsets.get_unchecked_release(chunk_idx as usize) } + df_idx;
+        let flag = unsafe { self.matched.get_unchecked_release(idx as usize) };
+        // prevent contended writes if the key is already marked
+        if !flag.load(Ordering::Relaxed) {
+            flag.store(true, Ordering::Relaxed)
+        }
+    }
+
+    /// Coalesce the join keys of the left and the right table and join the remaining columns.
+    fn finish_outer(
+        &mut self,
+        mut left_df: DataFrame,
+        right_df: DataFrame,
+    ) -> PolarsResult<DataFrame> {
+        for (name_left, name_right) in self.key_names_left.iter().zip(self.key_names_right.iter()) {
+            let left = left_df.column(name_left)?;
+            let right = right_df.column(name_right)?;
+            let mut coalesced = left.zip_with(&left.is_not_null(), right)?;
+            coalesced.rename(name_left);
+            left_df.with_column(coalesced)?;
+        }
+        let right_df = right_df.drop_many(self.key_names_right.as_slice());
+
+        Ok(match &self.output_names {
+            None => {
+                let out = _finish_join(left_df, right_df, Some(self.suffix.as_ref()))?;
+                self.output_names = Some(out.get_column_names_owned());
+                out
+            }
+            Some(names) => unsafe {
+                // safety:
+                // if we have duplicate names, we overwrite
+                // them in the next snippet
+                left_df
+                    .get_columns_mut()
+                    .extend_from_slice(right_df.get_columns());
+                left_df
+                    .get_columns_mut()
+                    .iter_mut()
+                    .zip(names)
+                    .for_each(|(s, name)| {
+                        s.rename(name);
+                    });
+                left_df
+            },
+        })
+    }
+}
+
+impl Operator for GenericOuterJoinProbe {
+    fn execute(
+        &mut self,
+        context: &PExecutionContext,
+        chunk: &DataChunk,
+    ) -> PolarsResult<OperatorResult> {
+        self.join_tuples_a.clear();
+        self.join_tuples_b.clear();
+        let mut hashes = std::mem::take(&mut self.hashes);
+        let rows = self.set_join_series(context, chunk)?;
+        hash_rows(&rows, &mut hashes, &self.hb);
+        self.hashes = hashes;
+
+        for (i, (h, row)) in self.hashes.iter().zip(rows.values_iter()).enumerate() {
+            let df_idx_probe = i as IdxSize;
+            // get the hashtable belonging by this hash partition
+            let partition = hash_to_partition(*h, self.hash_tables.len());
+            let current_table = unsafe { self.hash_tables.get_unchecked_release(partition) };
+
+            let entry = current_table
+                .raw_entry()
+                .from_hash(*h, |key| {
+                    compare_fn(key, *h, &self.materialized_join_cols, row)
+                })
+                .map(|key_val| key_val.1);
+
+            match entry {
+                Some(indexes_build) => {
+                    self.mark_matched(indexes_build[0]);
+                    self.join_tuples_a
+                        .extend(indexes_build.iter().copied().map(Some));
+                    self.join_tuples_b
+                        .extend(std::iter::repeat(df_idx_probe).take(indexes_build.len()));
+                }
+                None => {
+                    self.join_tuples_b.push(df_idx_probe);
+                    self.join_tuples_a.push(None);
+                }
+            }
+        }
+
+        let build_df = unsafe {
+            self.df_a
+                ._take_opt_chunked_unchecked_seq(&self.join_tuples_a)
+        };
+        let probe_df = unsafe {
+            chunk
+                .data
+                ._take_unchecked_slice_sorted(&self.join_tuples_b, false, IsSorted::Ascending)
+        };
+        let (left_df, right_df) = if self.swapped {
+            (probe_df, build_df)
+        } else {
+            (build_df, probe_df)
+        };
+        let out = self.finish_outer(left_df, right_df)?;
+
+        // clear memory
+        self.join_columns.clear();
+        self.hashes.clear();
+
+        Ok(OperatorResult::Finished(chunk.with_data(out)))
+    }
+
+    fn flush(&mut self, _context: &PExecutionContext) -> PolarsResult<Option<DataFrame>> {
+        if self.flushed.swap(true, Ordering::Relaxed) {
+            return Ok(None);
+        }
+        // all probe chunks are processed, so we can collect the rows
+        // of the build table that didn't find a match
+        let mut unmatched = vec![];
+        for table in self.hash_tables.iter() {
+            for indexes_build in table.values() {
+                let [chunk_idx, df_idx] = indexes_build[0];
+                let idx = self.chunk_offsets[chunk_idx as usize] + df_idx;
+                if !self.matched[idx as usize].load(Ordering::Relaxed) {
+                    unmatched.extend_from_slice(indexes_build);
+                }
+            }
+        }
+        if unmatched.is_empty() {
+            return Ok(None);
+        }
+
+        let build_df = unsafe {
+            self.df_a
+                ._take_chunked_unchecked_seq(&unmatched, IsSorted::Not)
+        };
+        let probe_df = DataFrame::new_no_checks(
+            self.probe_schema
+                .iter()
+                .map(|(name, dtype)| Series::full_null(name, unmatched.len(), dtype))
+                .collect(),
+        );
+        let (left_df, right_df) = if self.swapped {
+            (probe_df, build_df)
+        } else {
+            (build_df, probe_df)
+        };
+        self.finish_outer(left_df, right_df).map(Some)
+    }
+
+    fn split(&self, _thread_no: usize) -> Box<dyn Operator> {
+        let new = self.clone();
+        Box::new(new)
+    }
+    fn fmt(&self) -> &str {
+        "generic_outer_join_probe"
+    }
+}
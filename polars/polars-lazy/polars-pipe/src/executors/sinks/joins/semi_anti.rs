@@ -0,0 +1,131 @@
+use std::sync::Arc;
+
+use polars_arrow::export::arrow::array::BinaryArray;
+use polars_core::error::PolarsResult;
+use polars_core::export::ahash::RandomState;
+use polars_core::frame::hash_join::ChunkId;
+use polars_core::prelude::*;
+use polars_row::RowsEncoded;
+use polars_utils::hash_to_partition;
+use polars_utils::slice::GetSaferUnchecked;
+
+use crate::executors::sinks::joins::generic_build::*;
+use crate::executors::sinks::utils::hash_rows;
+use crate::expressions::PhysicalPipedExpr;
+use crate::operators::{DataChunk, Operator, OperatorResult, PExecutionContext};
+
+/// Probe side of a semi or anti join.
+///
+/// The right table is the build table, the rows of the left table are streamed through and
+/// kept if their key is (semi) or is not (anti) found in the build table.
+#[derive(Clone)]
+pub struct SemiAntiJoinProbe {
+    // the join columns are all tightly packed
+    // the values of a join column(s) can be found
+    // by:
+    // first get the offset of the chunks and multiply that with the number of join
+    // columns
+    //      * chunk_offset = (idx * n_join_keys)
+    //      * end = (offset + n_join_keys)
+    materialized_join_cols: Arc<Vec<BinaryArray<i64>>>,
+    hb: RandomState,
+    // partitioned tables that will be used for probing
+    // stores the key and the chunk_idx, df_idx of the right table
+    hash_tables: Arc<Vec<PlIdHashMap<Key, Vec<ChunkId>>>>,
+
+    // the columns that will be joined on
+    join_columns_left: Arc<Vec<Arc<dyn PhysicalPipedExpr>>>,
+
+    // amortize allocations
+    current_rows: RowsEncoded,
+    join_columns: Vec<ArrayRef>,
+    hashes: Vec<u64>,
+    is_anti: bool,
+}
+
+impl SemiAntiJoinProbe {
+    pub(super) fn new(
+        materialized_join_cols: Arc<Vec<BinaryArray<i64>>>,
+        hb: RandomState,
+        hash_tables: Arc<Vec<PlIdHashMap<Key, Vec<ChunkId>>>>,
+        join_columns_left: Arc<Vec<Arc<dyn PhysicalPipedExpr>>>,
+        join_columns: Vec<ArrayRef>,
+        hashes: Vec<u64>,
+        is_anti: bool,
+    ) -> Self {
+        SemiAntiJoinProbe {
+            materialized_join_cols,
+            hb,
+            hash_tables,
+            join_columns_left,
+            current_rows: Default::default(),
+            join_columns,
+            hashes,
+            is_anti,
+        }
+    }
+
+    fn set_join_series(
+        &mut self,
+        context: &PExecutionContext,
+        chunk: &DataChunk,
+    ) -> PolarsResult<BinaryArray<i64>> {
+        debug_assert!(self.join_columns.is_empty());
+        for phys_e in self.join_columns_left.iter() {
+            let s = phys_e.evaluate(chunk, context.execution_state.as_any())?;
+            let s = s.to_physical_repr().rechunk();
+            self.join_columns.push(s.array_ref(0).clone());
+        }
+        polars_row::convert_columns_amortized_no_order(&self.join_columns, &mut self.current_rows);
+
+        // safety: we keep rows-encode alive
+        unsafe { Ok(self.current_rows.borrow_array()) }
+    }
+}
+
+impl Operator for SemiAntiJoinProbe {
+    fn execute(
+        &mut self,
+        context: &PExecutionContext,
+        chunk: &DataChunk,
+    ) -> PolarsResult<OperatorResult> {
+        let mut hashes = std::mem::take(&mut self.hashes);
+        let rows = self.set_join_series(context, chunk)?;
+        hash_rows(&rows, &mut hashes, &self.hb);
+        self.hashes = hashes;
+
+        let mask: BooleanChunked = self
+            .hashes
+            .iter()
+            .zip(rows.values_iter())
+            .map(|(h, row)| {
+                // get the hashtable belonging by this hash partition
+                let partition = hash_to_partition(*h, self.hash_tables.len());
+                let current_table = unsafe { self.hash_tables.get_unchecked_release(partition) };
+
+                let found = current_table
+                    .raw_entry()
+                    .from_hash(*h, |key| {
+                        compare_fn(key, *h, &self.materialized_join_cols, row)
+                    })
+                    .is_some();
+                found != self.is_anti
+            })
+            .collect();
+        let out = chunk.data.filter(&mask)?;
+
+        // clear memory
+        self.join_columns.clear();
+        self.hashes.clear();
+
+        Ok(OperatorResult::Finished(chunk.with_data(out)))
+    }
+
+    fn split(&self, _thread_no: usize) -> Box<dyn Operator> {
+        let new = self.clone();
+        Box::new(new)
+    }
+    fn fmt(&self) -> &str {
+        "semi_anti_join_probe"
+    }
+}
@@ -16,10 +16,14 @@ pub(super) struct MemTracker {
     thread_count: usize,
     available_at_start: usize,
     refresh_interval: usize,
+    // free memory of the system at the start of this node
+    free_at_start: usize,
+    // the maximum memory this node may use
+    memory_budget: Option<usize>,
 }
 
 impl MemTracker {
-    pub(super) fn new(thread_count: usize) -> Self {
+    pub(super) fn new(thread_count: usize, memory_budget: Option<usize>) -> Self {
         let refresh_interval = if std::env::var(FORCE_OOC).is_ok() {
             1
         } else {
@@ -33,17 +37,32 @@ impl MemTracker {
             thread_count,
             available_at_start: 0,
             refresh_interval,
+            free_at_start: MEMINFO.free() as usize,
+            memory_budget,
         };
-        let available = MEMINFO.free() as usize;
+        let available = out.free_memory();
         out.available_mem.store(available, Ordering::Relaxed);
         out.available_at_start = available;
         out
     }
 
+    /// Free memory of the system, bounded by what is left of the memory budget.
     /// This shouldn't be called often as this is expensive.
+    fn free_memory(&self) -> usize {
+        let free = MEMINFO.free() as usize;
+        match self.memory_budget {
+            Some(budget) => {
+                // all memory that is taken since the start counts against the budget
+                let used = self.free_at_start.saturating_sub(free);
+                budget.saturating_sub(used).min(free)
+            }
+            None => free,
+        }
+    }
+
     fn refresh_memory(&self) {
         self.available_mem
-            .store(MEMINFO.free() as usize, Ordering::Relaxed);
+            .store(self.free_memory(), Ordering::Relaxed);
     }
 
     /// Get available memory of the system measured on latest refresh.
@@ -61,6 +80,10 @@ impl MemTracker {
         // we divide first to reduce the precision loss in floats
         let available_at_start = (self.available_at_start / TO_MB) as f64;
         let available = (self.get_available() / TO_MB) as f64;
+        if available_at_start == 0.0 {
+            // a budget of less than a MB; we are out of memory right away
+            return 0.0;
+        }
         available / available_at_start
     }
 
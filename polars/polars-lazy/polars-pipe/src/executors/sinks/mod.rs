@@ -1,22 +1,38 @@
-#[cfg(any(feature = "parquet", feature = "ipc"))]
+#[cfg(any(
+    feature = "parquet",
+    feature = "ipc",
+    feature = "csv",
+    feature = "json"
+))]
 mod file_sink;
 pub(crate) mod groupby;
 mod io;
 mod joins;
 mod memory;
 mod ordered;
+#[cfg(feature = "parquet")]
+mod partitioned_file_sink;
 mod reproject;
 mod slice;
 mod sort;
+mod unique;
 mod utils;
 
-#[cfg(any(feature = "parquet", feature = "ipc"))]
+#[cfg(any(
+    feature = "parquet",
+    feature = "ipc",
+    feature = "csv",
+    feature = "json"
+))]
 pub(crate) use file_sink::*;
 pub(crate) use joins::*;
 pub(crate) use ordered::*;
+#[cfg(feature = "parquet")]
+pub(crate) use partitioned_file_sink::*;
 pub(crate) use reproject::*;
 pub(crate) use slice::*;
 pub(crate) use sort::*;
+pub(crate) use unique::*;
 
 // We must strike a balance between cache coherence and resizing costs.
 // Overallocation seems a lot more expensive than resizing so we start reasonable small.
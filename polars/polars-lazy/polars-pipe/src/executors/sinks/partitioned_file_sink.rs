@@ -0,0 +1,325 @@
+use std::any::Any;
+use std::fs::File;
+use std::path::{Path, PathBuf};
+use std::thread::JoinHandle;
+
+use crossbeam_channel::{bounded, Receiver, Sender};
+use polars_core::prelude::*;
+use polars_core::series::IsSorted;
+use polars_io::parquet::BatchedWriter;
+use polars_plan::prelude::*;
+
+use super::file_sink::create_parquet_writer;
+use crate::operators::{DataChunk, FinalizedSink, PExecutionContext, Sink, SinkResult};
+use crate::pipeline::morsels_per_sink;
+
+// the directory of the rows that have a null partition value, as in hive
+const NULL_PARTITION: &str = "__HIVE_DEFAULT_PARTITION__";
+
+// the partitions of a chunk, the directory of a partition is relative to the root
+type PartitionedChunk = (IdxSize, Vec<(PathBuf, DataFrame)>);
+
+/// Percent-encode the characters that cannot be part of a `key=value` directory name,
+/// like hive does. `.` and `..` are encoded as well, so that a value never refers to
+/// another directory.
+fn escape_path_part(part: &str) -> String {
+    if part == "." || part == ".." {
+        return "%2E".repeat(part.len());
+    }
+    let mut escaped = String::with_capacity(part.len());
+    for c in part.chars() {
+        match c {
+            '\u{1}'..='\u{1f}'
+            | '\u{7f}'
+            | '"'
+            | '#'
+            | '%'
+            | '\''
+            | '*'
+            | '/'
+            | ':'
+            | '='
+            | '?'
+            | '\\'
+            | '['
+            | ']'
+            | '^'
+            | '{' => escaped.push_str(&format!("%{:02X}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn partition_value(av: AnyValue) -> String {
+    match av {
+        // hive writes the empty string to the null partition as well
+        AnyValue::Null | AnyValue::Utf8("") => NULL_PARTITION.to_string(),
+        // don't quote the strings
+        AnyValue::Utf8(v) => escape_path_part(v),
+        av => escape_path_part(&av.to_string()),
+    }
+}
+
+/// Split `df` in its partitions and determine the `key=value` directory of each.
+fn split_partitions(df: &DataFrame, by: &[String]) -> PolarsResult<Vec<(PathBuf, DataFrame)>> {
+    let gb = df.groupby_stable(by)?;
+    let groups = gb.get_groups();
+    let take_group = |i: usize| match groups {
+        // safety: the groups are in bounds and sorted
+        GroupsProxy::Idx(idx) => unsafe {
+            df._take_unchecked_slice_sorted(&idx.all()[i], false, IsSorted::Ascending)
+        },
+        GroupsProxy::Slice { groups, .. } => {
+            let [first, len] = groups[i];
+            df.slice(first as i64, len as usize)
+        }
+    };
+
+    (0..groups.len())
+        .map(|i| {
+            let part = take_group(i);
+            let mut dir = PathBuf::new();
+            for name in by {
+                let value = part.column(name)?.get(0)?;
+                dir.push(format!(
+                    "{}={}",
+                    escape_path_part(name),
+                    partition_value(value)
+                ));
+            }
+            Ok((dir, part))
+        })
+        .collect()
+}
+
+struct PartitionFile {
+    writer: BatchedWriter<File>,
+    rows: usize,
+    bytes: usize,
+    // the write after which this file was last written to
+    last_write: usize,
+}
+
+/// The open files of the partitions, owned by the io thread.
+struct PartitionedWriters {
+    root: PathBuf,
+    options: PartitionedParquetWriteOptions,
+    schema: Schema,
+    open: PlHashMap<PathBuf, PartitionFile>,
+    // the number of files started per partition, this determines the name of the next one
+    n_files: PlHashMap<PathBuf, usize>,
+    n_writes: usize,
+}
+
+impl PartitionedWriters {
+    fn write(&mut self, dir: PathBuf, mut df: DataFrame) -> PolarsResult<()> {
+        let max_rows = self.options.max_rows_per_file;
+        let max_bytes = self.options.max_bytes_per_file;
+
+        while df.height() > 0 {
+            if !self.open.contains_key(&dir) {
+                self.open_file(&dir)?;
+            }
+            let file = self.open.get_mut(&dir).unwrap();
+
+            // don't exceed the maximum number of rows, the remainder goes to the next file
+            let len = match max_rows {
+                Some(max) => std::cmp::min(df.height(), max - file.rows),
+                None => df.height(),
+            };
+            let batch = df.slice(0, len);
+            df = df.slice(len as i64, usize::MAX);
+
+            file.writer.write_batch(&batch)?;
+            file.rows += batch.height();
+            file.bytes += batch.estimated_size();
+            self.n_writes += 1;
+            file.last_write = self.n_writes;
+
+            let full = max_rows.map_or(false, |max| file.rows >= max)
+                || max_bytes.map_or(false, |max| file.bytes >= max);
+            if full {
+                self.close(&dir)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn open_file(&mut self, dir: &Path) -> PolarsResult<()> {
+        if self.open.len() >= self.options.max_open_files {
+            let lru = self
+                .open
+                .iter()
+                .min_by_key(|(_, file)| file.last_write)
+                .map(|(dir, _)| dir.clone())
+                .unwrap();
+            self.close(&lru)?;
+        }
+
+        let mut path = self.root.join(dir);
+        std::fs::create_dir_all(&path)?;
+        let n = self.n_files.entry(dir.to_path_buf()).or_insert(0);
+        path.push(format!("part-{n}.parquet"));
+        *n += 1;
+
+        let writer = create_parquet_writer(&path, &self.options.parquet, &self.schema)?;
+        let file = PartitionFile {
+            writer,
+            rows: 0,
+            bytes: 0,
+            last_write: self.n_writes,
+        };
+        self.open.insert(dir.to_path_buf(), file);
+        Ok(())
+    }
+
+    fn close(&mut self, dir: &Path) -> PolarsResult<()> {
+        if let Some(mut file) = self.open.remove(dir) {
+            file.writer.finish()?;
+        }
+        Ok(())
+    }
+
+    fn finish(&mut self) -> PolarsResult<()> {
+        for (_, mut file) in self.open.drain() {
+            file.writer.finish()?;
+        }
+        Ok(())
+    }
+}
+
+fn init_writer_thread(
+    receiver: Receiver<Option<PartitionedChunk>>,
+    mut writers: PartitionedWriters,
+    morsels_per_sink: usize,
+) -> JoinHandle<PolarsResult<()>> {
+    std::thread::spawn(move || {
+        let maintain_order = writers.options.parquet.maintain_order;
+        // collect the chunks per push to determine the order they should be written in
+        let mut chunks = Vec::with_capacity(morsels_per_sink);
+
+        while let Ok(chunk) = receiver.recv() {
+            let last_write = if let Some(chunk) = chunk {
+                chunks.push(chunk);
+                false
+            } else {
+                true
+            };
+
+            if chunks.len() == morsels_per_sink || last_write {
+                if maintain_order {
+                    chunks.sort_by_key(|(chunk_index, _)| *chunk_index);
+                }
+                for (_, partitions) in chunks.drain(..) {
+                    for (dir, df) in partitions {
+                        writers.write(dir, df)?;
+                    }
+                }
+
+                if last_write {
+                    return writers.finish();
+                }
+            }
+        }
+        Ok(())
+    })
+}
+
+/// Writes a hive partitioned dataset. The chunks are split in their partitions on
+/// the pipeline threads and written by a single io thread that keeps a writer open
+/// per partition.
+#[derive(Clone)]
+pub struct PartitionedParquetSink {
+    partition_by: Arc<Vec<String>>,
+    sender: Sender<Option<PartitionedChunk>>,
+    io_thread_handle: Arc<Option<JoinHandle<PolarsResult<()>>>>,
+}
+
+impl PartitionedParquetSink {
+    pub fn new(
+        root: &Path,
+        options: PartitionedParquetWriteOptions,
+        schema: &Schema,
+    ) -> PolarsResult<Self> {
+        for name in &options.partition_by {
+            schema.try_get(name)?;
+        }
+        let partition_by = Arc::new(options.partition_by.clone());
+        let writers = PartitionedWriters {
+            root: root.to_path_buf(),
+            options,
+            schema: schema.clone(),
+            open: Default::default(),
+            n_files: Default::default(),
+            n_writes: 0,
+        };
+
+        let morsels_per_sink = morsels_per_sink();
+        let backpressure = morsels_per_sink * 2;
+        let (sender, receiver) = bounded(backpressure);
+        let io_thread_handle = Arc::new(Some(init_writer_thread(
+            receiver,
+            writers,
+            morsels_per_sink,
+        )));
+
+        Ok(Self {
+            partition_by,
+            sender,
+            io_thread_handle,
+        })
+    }
+}
+
+impl Sink for PartitionedParquetSink {
+    fn sink(&mut self, _context: &PExecutionContext, chunk: DataChunk) -> PolarsResult<SinkResult> {
+        // don't add empty dataframes
+        if chunk.data.height() == 0 {
+            return Ok(SinkResult::CanHaveMoreInput);
+        }
+        let partitions = split_partitions(&chunk.data, &self.partition_by)?;
+        // the io thread stopped on an error, which is returned by `finalize`
+        if self
+            .sender
+            .send(Some((chunk.chunk_index, partitions)))
+            .is_err()
+        {
+            return Ok(SinkResult::Finished);
+        }
+        Ok(SinkResult::CanHaveMoreInput)
+    }
+
+    fn combine(&mut self, _other: &mut dyn Sink) {
+        // already synchronized
+    }
+
+    fn split(&self, _thread_no: usize) -> Box<dyn Sink> {
+        Box::new(self.clone())
+    }
+
+    fn finalize(&mut self, _context: &PExecutionContext) -> PolarsResult<FinalizedSink> {
+        // `None` indicates that we can flush all remaining chunks.
+        // this fails if the io thread already stopped on an error
+        let _ = self.sender.send(None);
+
+        // wait until all files are written
+        Arc::get_mut(&mut self.io_thread_handle)
+            .unwrap()
+            .take()
+            .unwrap()
+            .join()
+            .unwrap()?;
+
+        // return a dummy dataframe;
+        Ok(FinalizedSink::Finished(Default::default()))
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn fmt(&self) -> &str {
+        "partitioned_file_sink"
+    }
+}
@@ -3,7 +3,6 @@ use std::sync::atomic::{AtomicU32, AtomicU64, Ordering};
 
 use crossbeam_queue::SegQueue;
 use polars_core::prelude::*;
-use polars_core::series::IsSorted;
 use polars_core::utils::accumulate_dataframes_vertical_unchecked;
 use polars_core::POOL;
 use polars_io::ipc::IpcReader;
@@ -11,7 +10,7 @@ use polars_io::SerReader;
 use polars_ops::prelude::*;
 use rayon::prelude::*;
 
-use crate::executors::sinks::io::{DfIter, IOThread};
+use crate::executors::sinks::io::{partition_df, IOThread};
 use crate::executors::sinks::sort::source::SortSource;
 use crate::operators::FinalizedSink;
 
@@ -102,7 +101,7 @@ impl PartitionSpiller {
 }
 
 pub(super) fn sort_ooc(
-    io_thread: &IOThread,
+    io_thread: IOThread,
     // these partitions are the samples
     // these are not yet assigned to a buckets
     samples: Series,
@@ -147,7 +146,7 @@ pub(super) fn sort_ooc(
             PolarsResult::Ok(())
         })
     })?;
-    partitions_spiller.spill_all(io_thread);
+    partitions_spiller.spill_all(&io_thread);
     if verbose {
         eprintln!("finished partitioning sort files");
     }
@@ -169,7 +168,7 @@ pub(super) fn sort_ooc(
         })
         .collect::<std::io::Result<Vec<_>>>()?;
 
-    let source = SortSource::new(files, idx, descending, slice, verbose);
+    let source = SortSource::new(files, idx, descending, slice, verbose, io_thread);
     Ok(FinalizedSink::Source(Box::new(source)))
 }
 
@@ -178,26 +177,3 @@ fn det_partitions(s: &Series, partitions: &Series, descending: bool) -> IdxCa {
 
     search_sorted(partitions, &s, SearchSortedSide::Any, descending).unwrap()
 }
-
-fn partition_df(df: DataFrame, partitions: &IdxCa) -> PolarsResult<(DfIter, IdxCa)> {
-    let groups = partitions.group_tuples(false, false)?;
-    let partitions = unsafe { partitions.clone().into_series().agg_first(&groups) };
-    let partitions = partitions.idx().unwrap().clone();
-
-    let out = match groups {
-        GroupsProxy::Idx(idx) => {
-            let iter = idx.into_iter().map(move |(_, group)| {
-                // groups are in bounds and sorted
-                unsafe { df._take_unchecked_slice_sorted(&group, false, IsSorted::Ascending) }
-            });
-            Box::new(iter) as DfIter
-        }
-        GroupsProxy::Slice { groups, .. } => {
-            let iter = groups
-                .into_iter()
-                .map(move |[first, len]| df.slice(first as i64, len as usize));
-            Box::new(iter) as DfIter
-        }
-    };
-    Ok((out, partitions))
-}
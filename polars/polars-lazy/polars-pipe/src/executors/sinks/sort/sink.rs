@@ -6,6 +6,7 @@ use polars_core::error::PolarsResult;
 use polars_core::frame::DataFrame;
 use polars_core::prelude::{AnyValue, SchemaRef, Series, SortOptions};
 use polars_core::utils::accumulate_dataframes_vertical_unchecked;
+use polars_plan::frame::StreamingConfig;
 use polars_plan::prelude::SortArguments;
 
 use crate::executors::sinks::io::{block_thread_until_io_thread_done, IOThread};
@@ -25,6 +26,7 @@ pub struct SortSink {
     // when ooc, we write to disk using an IO thread
     // RwLock as we want to have multiple readers at once.
     io_thread: Arc<RwLock<Option<IOThread>>>,
+    config: StreamingConfig,
     // location in the dataframe of the columns to sort by
     sort_idx: usize,
     sort_args: SortArguments,
@@ -38,7 +40,12 @@ pub struct SortSink {
 }
 
 impl SortSink {
-    pub(crate) fn new(sort_idx: usize, sort_args: SortArguments, schema: SchemaRef) -> Self {
+    pub(crate) fn new(
+        sort_idx: usize,
+        sort_args: SortArguments,
+        schema: SchemaRef,
+        config: &StreamingConfig,
+    ) -> Self {
         // for testing purposes
         let ooc = std::env::var(FORCE_OOC).is_ok();
         let n_morsels_per_sink = morsels_per_sink();
@@ -46,9 +53,10 @@ impl SortSink {
         let mut out = Self {
             schema,
             chunks: Default::default(),
-            mem_track: MemTracker::new(n_morsels_per_sink),
+            mem_track: MemTracker::new(n_morsels_per_sink, config.memory_budget),
             ooc,
             io_thread: Default::default(),
+            config: config.clone(),
             sort_idx,
             sort_args,
             dist_sample: vec![],
@@ -71,7 +79,11 @@ impl SortSink {
         // start IO thread
         let mut iot = self.io_thread.write().unwrap();
         if iot.is_none() {
-            *iot = Some(IOThread::try_new(self.schema.clone(), "sort")?)
+            *iot = Some(IOThread::try_new(
+                self.schema.clone(),
+                "sort",
+                &self.config,
+            )?)
         }
         Ok(())
     }
@@ -155,6 +167,7 @@ impl Sink for SortSink {
             mem_track: self.mem_track.clone(),
             ooc: self.ooc,
             io_thread: self.io_thread.clone(),
+            config: self.config.clone(),
             sort_idx: self.sort_idx,
             sort_args: self.sort_args.clone(),
             dist_sample: vec![],
@@ -167,18 +180,18 @@ impl Sink for SortSink {
         if self.ooc {
             // spill everything
             self.dump(true).unwrap();
-            let lock = self.io_thread.read().unwrap();
-            let io_thread = lock.as_ref().unwrap();
+            // the sort source takes ownership, so that the spill files live until it is done
+            let io_thread = self.io_thread.write().unwrap().take().unwrap();
 
             let dist = Series::from_any_values("", &self.dist_sample, false).unwrap();
             let dist = dist.sort_with(SortOptions {
                 descending: self.sort_args.descending[0],
-                nulls_last: self.sort_args.nulls_last,
+                nulls_last: self.sort_args.nulls_last[0],
                 multithreaded: true,
                 maintain_order: self.sort_args.maintain_order,
             });
 
-            block_thread_until_io_thread_done(io_thread);
+            block_thread_until_io_thread_done(&io_thread);
 
             sort_ooc(
                 io_thread,
@@ -220,7 +233,7 @@ pub(super) fn sort_accumulated(
     df.sort_impl(
         vec![sort_column],
         vec![descending],
-        false,
+        vec![false],
         false,
         slice,
         true,
@@ -5,6 +5,7 @@ use polars_core::prelude::sort::_broadcast_descending;
 use polars_core::prelude::sort::arg_sort_multiple::_get_rows_encoded_compat_array;
 use polars_core::prelude::*;
 use polars_core::series::IsSorted;
+use polars_plan::frame::StreamingConfig;
 use polars_plan::prelude::*;
 use polars_row::decode::decode_rows_from_binary;
 use polars_row::SortField;
@@ -17,25 +18,38 @@ const POLARS_SORT_COLUMN: &str = "__POLARS_SORT_COLUMN";
 
 fn get_sort_fields(sort_idx: &[usize], sort_args: &SortArguments) -> Vec<SortField> {
     let mut descending = sort_args.descending.clone();
+    let mut nulls_last = sort_args.nulls_last.clone();
     _broadcast_descending(sort_idx.len(), &mut descending);
+    _broadcast_descending(sort_idx.len(), &mut nulls_last);
     descending
         .into_iter()
-        .map(|descending| SortField {
+        .zip(nulls_last)
+        .map(|(descending, nulls_last)| SortField {
             descending,
-            nulls_last: sort_args.nulls_last,
+            nulls_last,
         })
         .collect()
 }
 
+// the row decoder does not yet support nulls last
 #[cfg(feature = "dtype-categorical")]
-fn sort_column_can_be_decoded(schema: &Schema, sort_idx: &[usize]) -> bool {
-    !sort_idx
-        .iter()
-        .any(|i| matches!(schema.get_at_index(*i).unwrap().1, DataType::Categorical(_)))
+fn sort_column_can_be_decoded(
+    schema: &Schema,
+    sort_idx: &[usize],
+    sort_args: &SortArguments,
+) -> bool {
+    !sort_args.nulls_last.iter().any(|nulls_last| *nulls_last)
+        && !sort_idx
+            .iter()
+            .any(|i| matches!(schema.get_at_index(*i).unwrap().1, DataType::Categorical(_)))
 }
 #[cfg(not(feature = "dtype-categorical"))]
-fn sort_column_can_be_decoded(_schema: &Schema, _sort_idx: &[usize]) -> bool {
-    true
+fn sort_column_can_be_decoded(
+    _schema: &Schema,
+    _sort_idx: &[usize],
+    sort_args: &SortArguments,
+) -> bool {
+    !sort_args.nulls_last.iter().any(|nulls_last| *nulls_last)
 }
 
 fn sort_by_idx<V: Clone>(values: &[V], idx: &[usize]) -> Vec<V> {
@@ -140,8 +154,11 @@ impl SortSinkMultiple {
         sort_args: SortArguments,
         output_schema: SchemaRef,
         sort_idx: Vec<usize>,
+        config: &StreamingConfig,
     ) -> Self {
-        let can_decode = sort_column_can_be_decoded(&output_schema, &sort_idx);
+        // a stable sort encodes the position of the rows as last sort field, which we can't decode
+        let can_decode = !sort_args.maintain_order
+            && sort_column_can_be_decoded(&output_schema, &sort_idx, &sort_args);
         let mut schema = (*output_schema).clone();
 
         let mut sort_dtypes = None;
@@ -159,7 +176,13 @@ impl SortSinkMultiple {
             sort_dtypes = Some(dtypes.into());
         }
         schema.with_column(POLARS_SORT_COLUMN.into(), DataType::Binary);
-        let sort_fields = get_sort_fields(&sort_idx, &sort_args);
+        let mut sort_fields = get_sort_fields(&sort_idx, &sort_args);
+        if sort_args.maintain_order {
+            sort_fields.push(SortField {
+                descending: false,
+                nulls_last: false,
+            });
+        }
 
         // don't set descending and nulls last as this
         // will be solved by the row encoding
@@ -168,11 +191,12 @@ impl SortSinkMultiple {
             schema.len() - 1,
             SortArguments {
                 descending: vec![false],
-                nulls_last: false,
+                nulls_last: vec![false],
                 slice: sort_args.slice,
                 maintain_order: false,
             },
             Arc::new(schema),
+            config,
         ));
 
         SortSinkMultiple {
@@ -188,7 +212,9 @@ impl SortSinkMultiple {
     }
 
     fn encode(&mut self, chunk: &mut DataChunk) -> PolarsResult<()> {
+        let chunk_index = chunk.chunk_index;
         let df = &mut chunk.data;
+        let height = df.height();
         let cols = unsafe { df.get_columns_mut() };
 
         self.sort_column.clear();
@@ -198,6 +224,13 @@ impl SortSinkMultiple {
             let arr = _get_rows_encoded_compat_array(s)?;
             self.sort_column.push(arr);
         }
+        if self.sort_args.maintain_order {
+            // the position of the rows in the source, so that equal keys keep their order
+            let offset = (chunk_index as u64) << 32;
+            let position: NoNull<UInt64Chunked> = (0..height as u64).map(|i| offset + i).collect();
+            let arr = _get_rows_encoded_compat_array(&position.into_inner().into_series())?;
+            self.sort_column.push(arr);
+        }
 
         if self.can_decode {
             // we remove columns by index, but then the aren't correct anymore
@@ -5,6 +5,7 @@ use polars_core::utils::{accumulate_dataframes_vertical_unchecked, split_df};
 use polars_core::POOL;
 use rayon::prelude::*;
 
+use crate::executors::sinks::io::IOThread;
 use crate::executors::sinks::sort::ooc::read_df;
 use crate::executors::sinks::sort::sink::sort_accumulated;
 use crate::operators::{DataChunk, PExecutionContext, Source, SourceResult};
@@ -17,6 +18,8 @@ pub struct SortSource {
     chunk_offset: IdxSize,
     slice: Option<(i64, usize)>,
     finished: bool,
+    // holding this keeps the spill files in place
+    _io_thread: IOThread,
 }
 
 impl SortSource {
@@ -26,6 +29,7 @@ impl SortSource {
         descending: bool,
         slice: Option<(i64, usize)>,
         verbose: bool,
+        io_thread: IOThread,
     ) -> Self {
         if verbose {
             eprintln!("started sort source phase");
@@ -44,6 +48,7 @@ impl SortSource {
             chunk_offset: 0,
             slice,
             finished: false,
+            _io_thread: io_thread,
         }
     }
     fn finish_batch(&mut self, dfs: Vec<DataFrame>) -> Vec<DataChunk> {
@@ -0,0 +1,181 @@
+use std::any::Any;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+use polars_core::prelude::*;
+use polars_plan::prelude::DistinctOptions;
+
+use crate::operators::{DataChunk, FinalizedSink, PExecutionContext, Sink, SinkResult};
+
+// the number of occurrences of a row, needed for `UniqueKeepStrategy::None`
+const COUNT_NAME: &str = "__POLARS_UNIQUE_COUNT";
+
+/// Reduce `df` to its unique rows in the order of the default engine. For
+/// `UniqueKeepStrategy::None` the first row of every key is kept and the
+/// occurrences are summed in the count column.
+///
+/// This is idempotent, so the unique rows of the processed chunks can be reduced
+/// again together with the rows of a new chunk.
+fn reduce(df: &DataFrame, options: &DistinctOptions) -> PolarsResult<DataFrame> {
+    let subset = options.subset.as_ref().map(|subset| subset.as_slice());
+    match options.keep_strategy {
+        UniqueKeepStrategy::None => {
+            let names = match subset {
+                Some(subset) => subset.iter().map(|s| s.as_str()).collect::<Vec<_>>(),
+                None => df
+                    .get_column_names()
+                    .into_iter()
+                    .filter(|name| *name != COUNT_NAME)
+                    .collect(),
+            };
+            let gb = df.groupby_stable(names)?;
+            let groups = gb.get_groups();
+            let columns = df
+                .get_columns()
+                .iter()
+                .map(|s| {
+                    // safety: the groups are in bounds
+                    if s.name() == COUNT_NAME {
+                        unsafe { s.agg_sum(groups) }.cast(&IDX_DTYPE)
+                    } else {
+                        Ok(unsafe { s.agg_first(groups) })
+                    }
+                })
+                .collect::<PolarsResult<Vec<_>>>()?;
+            Ok(DataFrame::new_no_checks(columns))
+        }
+        keep => df.unique_impl(true, subset, keep, None),
+    }
+}
+
+/// The unique rows of the chunks that are processed in the order of the source.
+struct OrderedUnique {
+    options: DistinctOptions,
+    uniques: DataFrame,
+    // the height of `uniques` after it was last reduced
+    reduced_height: usize,
+    // the chunks that arrived before the chunks that precede them
+    pending: BTreeMap<IdxSize, DataFrame>,
+    next_chunk: IdxSize,
+}
+
+impl OrderedUnique {
+    fn process(&mut self, df: DataFrame) -> PolarsResult<()> {
+        self.uniques.vstack_mut(&df)?;
+        // amortize the reductions, the rows of a chunk are already unique
+        if self.uniques.height() > 2 * self.reduced_height {
+            self.reduce()?;
+        }
+        Ok(())
+    }
+
+    fn reduce(&mut self) -> PolarsResult<()> {
+        self.uniques.as_single_chunk_par();
+        self.uniques = reduce(&self.uniques, &self.options)?;
+        self.reduced_height = self.uniques.height();
+        Ok(())
+    }
+
+    fn sink(&mut self, chunk_index: IdxSize, df: DataFrame) -> PolarsResult<()> {
+        if chunk_index > self.next_chunk {
+            // wait for the chunks that precede this one
+            self.pending.insert(chunk_index, df);
+            return Ok(());
+        }
+        self.process(df)?;
+        self.next_chunk += 1;
+        while let Some(df) = self.pending.remove(&self.next_chunk) {
+            self.process(df)?;
+            self.next_chunk += 1;
+        }
+        Ok(())
+    }
+
+    fn finalize(&mut self) -> PolarsResult<DataFrame> {
+        let pending = std::mem::take(&mut self.pending);
+        for df in pending.into_values() {
+            self.process(df)?;
+        }
+        self.reduce()?;
+        let mut out = std::mem::take(&mut self.uniques);
+        if let UniqueKeepStrategy::None = self.options.keep_strategy {
+            let count = out.drop_in_place(COUNT_NAME)?;
+            let mask = count.equal(1)?;
+            out = out.filter(&mask)?;
+        }
+        Ok(match self.options.slice {
+            Some((offset, len)) => out.slice(offset, len),
+            None => out,
+        })
+    }
+}
+
+/// An order preserving unique. The chunks are reduced to their unique rows in
+/// parallel and then combined in the order of the source, so that the output
+/// matches the default engine. The splits share their state.
+pub(crate) struct UniqueOrderedSink {
+    options: DistinctOptions,
+    state: Arc<Mutex<OrderedUnique>>,
+}
+
+impl UniqueOrderedSink {
+    pub(crate) fn new(options: DistinctOptions, input_schema: &Schema) -> Self {
+        let mut uniques = DataFrame::from(input_schema);
+        if let UniqueKeepStrategy::None = options.keep_strategy {
+            uniques
+                .with_column(Series::new_empty(COUNT_NAME, &IDX_DTYPE))
+                .unwrap();
+        }
+        let state = OrderedUnique {
+            options: options.clone(),
+            uniques,
+            reduced_height: 0,
+            pending: Default::default(),
+            next_chunk: 0,
+        };
+        Self {
+            options,
+            state: Arc::new(Mutex::new(state)),
+        }
+    }
+}
+
+impl Sink for UniqueOrderedSink {
+    fn sink(&mut self, _context: &PExecutionContext, chunk: DataChunk) -> PolarsResult<SinkResult> {
+        let mut df = chunk.data;
+        if let UniqueKeepStrategy::None = self.options.keep_strategy {
+            let count = IdxCa::from_vec(COUNT_NAME, vec![1; df.height()]);
+            df.with_column(count.into_series())?;
+        }
+        df.as_single_chunk_par();
+        let df = reduce(&df, &self.options)?;
+
+        let mut state = self.state.lock().unwrap();
+        state.sink(chunk.chunk_index, df)?;
+        Ok(SinkResult::CanHaveMoreInput)
+    }
+
+    fn combine(&mut self, _other: &mut dyn Sink) {
+        // the splits share their state
+    }
+
+    fn split(&self, _thread_no: usize) -> Box<dyn Sink> {
+        Box::new(Self {
+            options: self.options.clone(),
+            state: self.state.clone(),
+        })
+    }
+
+    fn finalize(&mut self, _context: &PExecutionContext) -> PolarsResult<FinalizedSink> {
+        let mut state = self.state.lock().unwrap();
+        Ok(FinalizedSink::Finished(state.finalize()?))
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn fmt(&self) -> &str {
+        "unique_ordered"
+    }
+}
@@ -0,0 +1,83 @@
+use polars_core::POOL;
+use polars_plan::prelude::{AnonymousScan, AnonymousScanOptions, BatchedScan};
+
+use super::*;
+use crate::pipeline::determine_chunk_size;
+
+pub(crate) struct AnonymousScanSource {
+    function: Arc<dyn AnonymousScan>,
+    options: Option<AnonymousScanOptions>,
+    batched_reader: Option<Box<dyn BatchedScan>>,
+    n_threads: usize,
+    chunk_index: IdxSize,
+    verbose: bool,
+}
+
+impl AnonymousScanSource {
+    pub(crate) fn new(
+        function: Arc<dyn AnonymousScan>,
+        options: AnonymousScanOptions,
+        verbose: bool,
+    ) -> Self {
+        AnonymousScanSource {
+            function,
+            options: Some(options),
+            batched_reader: None,
+            n_threads: POOL.current_num_threads(),
+            chunk_index: 0,
+            verbose,
+        }
+    }
+
+    // Delay initializing the reader
+    // otherwise all files would be opened during construction of the pipeline
+    fn init_reader(&mut self) -> PolarsResult<()> {
+        let options = self.options.take().unwrap();
+        let n_cols = options
+            .output_schema
+            .as_ref()
+            .unwrap_or(&options.schema)
+            .len();
+        let chunk_size = determine_chunk_size(std::cmp::max(n_cols, 1), self.n_threads)?;
+
+        if self.verbose {
+            eprintln!("STREAMING CHUNK SIZE: {chunk_size} rows")
+        }
+
+        self.batched_reader = Some(self.function.scan_batched(options, chunk_size)?);
+        Ok(())
+    }
+}
+
+impl Source for AnonymousScanSource {
+    fn get_batches(&mut self, _context: &PExecutionContext) -> PolarsResult<SourceResult> {
+        if self.batched_reader.is_none() {
+            self.init_reader()?
+        }
+
+        let batches = self
+            .batched_reader
+            .as_mut()
+            .unwrap()
+            .next_batches(self.n_threads)?;
+        Ok(match batches {
+            None => SourceResult::Finished,
+            Some(batches) => SourceResult::GotMoreData(
+                batches
+                    .into_iter()
+                    .map(|data| {
+                        let out = DataChunk {
+                            chunk_index: self.chunk_index,
+                            data,
+                        };
+                        self.chunk_index += 1;
+                        out
+                    })
+                    .collect(),
+            ),
+        })
+    }
+    fn fmt(&self) -> &str {
+        "anonymous_scan"
+    }
+}
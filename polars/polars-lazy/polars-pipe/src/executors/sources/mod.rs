@@ -1,3 +1,4 @@
+mod anonymous_scan;
 #[cfg(feature = "csv")]
 mod csv;
 mod frame;
@@ -7,6 +8,7 @@ mod parquet;
 mod reproject;
 mod union;
 
+pub(crate) use anonymous_scan::AnonymousScanSource;
 #[cfg(feature = "csv")]
 pub(crate) use csv::CsvSource;
 pub(crate) use frame::*;
@@ -18,5 +18,15 @@ pub trait Operator: Send + Sync {
 
     fn split(&self, thread_no: usize) -> Box<dyn Operator>;
 
+    /// Called once all sources have been pushed through this operator. Operators that
+    /// hold back output until they have seen all data, e.g. the probe side of an outer
+    /// join, return it here. This is called until it returns `None`, so large output
+    /// can be returned in multiple parts.
+    ///
+    /// The split operators share their state, so only one of them should return data.
+    fn flush(&mut self, _context: &PExecutionContext) -> PolarsResult<Option<DataFrame>> {
+        Ok(None)
+    }
+
     fn fmt(&self) -> &str;
 }
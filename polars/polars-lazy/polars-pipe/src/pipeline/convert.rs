@@ -5,6 +5,7 @@ use std::sync::Arc;
 use hashbrown::hash_map::Entry;
 use polars_core::prelude::*;
 use polars_core::with_match_physical_integer_polars_type;
+use polars_plan::frame::StreamingConfig;
 use polars_plan::prelude::*;
 
 use crate::executors::operators::HstackOperator;
@@ -113,6 +114,24 @@ where
                 _ => todo!(),
             }
         }
+        AnonymousScan {
+            function,
+            predicate,
+            options,
+            output_schema,
+            ..
+        } => {
+            // add predicate to operators
+            if let (true, Some(predicate)) = (push_predicate, predicate) {
+                let predicate = to_physical(predicate, expr_arena, output_schema.as_ref())?;
+                let op = operators::FilterOperator { predicate };
+                let op = Box::new(op) as Box<dyn Operator>;
+                operator_objects.push(op)
+            }
+            let options = Arc::try_unwrap(options).unwrap_or_else(|options| (*options).clone());
+            let src = sources::AnonymousScanSource::new(function, options, verbose);
+            Ok(Box::new(src) as Box<dyn Source>)
+        }
         _ => unreachable!(),
     }
 }
@@ -122,6 +141,7 @@ pub fn get_sink<F>(
     lp_arena: &mut Arena<ALogicalPlan>,
     expr_arena: &mut Arena<AExpr>,
     to_physical: &F,
+    config: &StreamingConfig,
 ) -> PolarsResult<Box<dyn Sink>>
 where
     F: Fn(Node, &Arena<AExpr>, Option<&SchemaRef>) -> PolarsResult<Arc<dyn PhysicalPipedExpr>>,
@@ -137,10 +157,25 @@ where
                     Box::new(ParquetSink::new(path, *options, input_schema.as_ref())?)
                         as Box<dyn Sink>
                 }
+                #[cfg(feature = "parquet")]
+                FileType::PartitionedParquet(options) => Box::new(PartitionedParquetSink::new(
+                    path,
+                    options.clone(),
+                    input_schema.as_ref(),
+                )?) as Box<dyn Sink>,
                 #[cfg(feature = "ipc")]
                 FileType::Ipc(options) => {
                     Box::new(IpcSink::new(path, *options, input_schema.as_ref())?) as Box<dyn Sink>
                 }
+                #[cfg(feature = "csv")]
+                FileType::Csv(options) => {
+                    Box::new(CsvSink::new(path, options.clone(), input_schema.as_ref())?)
+                        as Box<dyn Sink>
+                }
+                #[cfg(feature = "json")]
+                FileType::Json(options) => {
+                    Box::new(JsonSink::new(path, *options, input_schema.as_ref())?) as Box<dyn Sink>
+                }
                 FileType::Memory => Box::new(OrderedSink::new()) as Box<dyn Sink>,
             }
         }
@@ -160,7 +195,7 @@ where
                 JoinType::Cross => {
                     Box::new(CrossJoin::new(options.args.suffix().into())) as Box<dyn Sink>
                 }
-                join_type @ JoinType::Inner | join_type @ JoinType::Left => {
+                join_type => {
                     let input_schema_left = lp_arena.get(*input_left).schema(lp_arena);
                     let join_columns_left = Arc::new(exprs_to_physical(
                         left_on,
@@ -178,10 +213,18 @@ where
 
                     let swapped = swap_join_order(options);
 
-                    let (join_columns_left, join_columns_right) = if swapped {
-                        (join_columns_right, join_columns_left)
+                    let (join_columns_left, join_columns_right, probe_schema) = if swapped {
+                        (
+                            join_columns_right,
+                            join_columns_left,
+                            input_schema_left.into_owned(),
+                        )
                     } else {
-                        (join_columns_left, join_columns_right)
+                        (
+                            join_columns_left,
+                            join_columns_right,
+                            input_schema_right.into_owned(),
+                        )
                     };
 
                     Box::new(GenericBuild::new(
@@ -190,9 +233,10 @@ where
                         swapped,
                         join_columns_left,
                         join_columns_right,
+                        probe_schema,
+                        config,
                     )) as Box<dyn Sink>
                 }
-                _ => unimplemented!(),
             }
         }
         Slice { offset, len, .. } => {
@@ -206,13 +250,15 @@ where
         } => {
             let input_schema = lp_arena.get(*input).schema(lp_arena).into_owned();
 
-            if by_column.len() == 1 {
+            // the single column sort sink does not place nulls last and is not stable,
+            // the row encoding of the multiple column sink does and can be
+            if by_column.len() == 1 && !args.nulls_last[0] && !args.maintain_order {
                 let by_column = aexpr_to_leaf_names_iter(by_column[0], expr_arena)
                     .next()
                     .unwrap();
                 let index = input_schema.try_index_of(by_column.as_ref())?;
 
-                let sort_sink = SortSink::new(index, args.clone(), input_schema);
+                let sort_sink = SortSink::new(index, args.clone(), input_schema, config);
                 Box::new(sort_sink) as Box<dyn Sink>
             } else {
                 let sort_idx = by_column
@@ -223,10 +269,17 @@ where
                     })
                     .collect::<PolarsResult<Vec<_>>>()?;
 
-                let sort_sink = SortSinkMultiple::new(args.clone(), input_schema, sort_idx);
+                let sort_sink = SortSinkMultiple::new(args.clone(), input_schema, sort_idx, config);
                 Box::new(sort_sink) as Box<dyn Sink>
             }
         }
+        Distinct { input, options }
+            if options.maintain_order
+                || matches!(options.keep_strategy, UniqueKeepStrategy::None) =>
+        {
+            let input_schema = lp_arena.get(*input).schema(lp_arena).into_owned();
+            Box::new(UniqueOrderedSink::new(options.clone(), &input_schema)) as Box<dyn Sink>
+        }
         Distinct { input, options } => {
             // We create a Groupby.agg_first()/agg_last (depending on the keep strategy
             let input_schema = lp_arena.get(*input).schema(lp_arena).into_owned();
@@ -307,10 +360,40 @@ where
                 output_schema,
                 input_agg_dtypes,
                 options.slice,
+                config,
             ));
 
             Box::new(ReProjectSink::new(input_schema, groupby_sink))
         }
+        #[cfg(feature = "dynamic_groupby")]
+        Aggregate {
+            input,
+            aggs,
+            options,
+            ..
+        } if options.dynamic.is_some() || options.rolling.is_some() => {
+            use crate::executors::sinks::groupby::temporal::*;
+
+            let input_schema = lp_arena.get(*input).schema(lp_arena).as_ref().clone();
+            let aggs = aggs
+                .iter()
+                .map(|node| convert_to_window_agg(*node, expr_arena, &input_schema, &to_physical))
+                .collect::<PolarsResult<Vec<_>>>()?;
+
+            match (&options.dynamic, &options.rolling) {
+                (Some(dynamic), _) => Box::new(TemporalGroupbySink::new(
+                    DynamicWindows::new(dynamic.clone(), aggs),
+                    &input_schema,
+                    options.slice,
+                )) as Box<dyn Sink>,
+                (_, Some(rolling)) => Box::new(TemporalGroupbySink::new(
+                    RollingWindows::new(rolling.clone(), aggs),
+                    &input_schema,
+                    options.slice,
+                )) as Box<dyn Sink>,
+                _ => unreachable!(),
+            }
+        }
         Aggregate {
             input,
             keys,
@@ -348,6 +431,7 @@ where
                     output_schema.clone(),
                     input_agg_dtypes,
                     options.slice,
+                    config,
                 ))
             } else {
                 match (
@@ -363,6 +447,7 @@ where
                                 input_schema,
                                 output_schema.clone(),
                                 options.slice,
+                                config,
                             )) as Box<dyn Sink>
                         })
                     }
@@ -373,6 +458,7 @@ where
                         input_schema,
                         output_schema.clone(),
                         options.slice,
+                        config,
                     )) as Box<dyn Sink>,
                     _ => Box::new(GenericGroupby2::new(
                         key_columns,
@@ -381,6 +467,7 @@ where
                         output_schema.clone(),
                         input_agg_dtypes,
                         options.slice,
+                        config,
                     )),
                 }
             }
@@ -525,6 +612,7 @@ pub fn create_pipeline<F>(
     to_physical: F,
     verbose: bool,
     sink_cache: &mut PlHashMap<usize, Box<dyn Sink>>,
+    config: &StreamingConfig,
 ) -> PolarsResult<PipeLine>
 where
     F: Fn(Node, &Arena<AExpr>, Option<&SchemaRef>) -> PolarsResult<Arc<dyn PhysicalPipedExpr>>,
@@ -536,7 +624,7 @@ where
 
     for node in sources {
         let src = match lp_arena.get(*node) {
-            lp @ DataFrameScan { .. } => get_source(
+            lp @ (DataFrameScan { .. } | AnonymousScan { .. }) => get_source(
                 lp.clone(),
                 &mut operator_objects,
                 expr_arena,
@@ -588,11 +676,11 @@ where
             // ensure that shared sinks are really shared
             // to achieve this we store/fetch them in a cache
             let sink = if *shared_count.borrow() == 1 {
-                get_sink(node, lp_arena, expr_arena, &to_physical)?
+                get_sink(node, lp_arena, expr_arena, &to_physical, config)?
             } else {
                 match sink_cache.entry(node.0) {
                     Entry::Vacant(entry) => {
-                        let sink = get_sink(node, lp_arena, expr_arena, &to_physical)?;
+                        let sink = get_sink(node, lp_arena, expr_arena, &to_physical, config)?;
                         entry.insert(sink.split(0));
                         sink
                     }
@@ -615,9 +703,15 @@ where
 }
 
 pub fn swap_join_order(options: &JoinOptions) -> bool {
-    matches!(options.args.how, JoinType::Left)
-        || match (options.rows_left, options.rows_right) {
-            ((Some(left), _), (Some(right), _)) => left > right,
-            ((_, left), (_, right)) => left > right,
-        }
+    // left, semi and anti joins stream the left table and build the right table
+    match options.args.how {
+        JoinType::Left => return true,
+        #[cfg(feature = "semi_anti_join")]
+        JoinType::Semi | JoinType::Anti => return true,
+        _ => {}
+    }
+    match (options.rows_left, options.rows_right) {
+        ((Some(left), _), (Some(right), _)) => left > right,
+        ((_, left), (_, right)) => left > right,
+    }
 }
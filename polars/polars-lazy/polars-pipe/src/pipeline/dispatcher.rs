@@ -10,6 +10,7 @@ use polars_core::utils::accumulate_dataframes_vertical_unchecked;
 use polars_core::POOL;
 use polars_utils::arena::Node;
 use polars_utils::sync::SyncPtr;
+use polars_utils::IdxSize;
 use rayon::prelude::*;
 
 use crate::executors::sources::DataFrameSource;
@@ -318,6 +319,40 @@ impl PipeLine {
         Ok(SinkResult::CanHaveMoreInput)
     }
 
+    /// Flush the operators in `operator_start..operator_end` in order and push their output
+    /// through the operators that follow into the `sink`. Returns `true` if the sink is finished.
+    fn flush_operators(
+        &mut self,
+        ec: &PExecutionContext,
+        operator_start: usize,
+        operator_end: usize,
+        sink: &mut Box<dyn Sink>,
+    ) -> PolarsResult<bool> {
+        // the split operators share their state, so we only need to flush a single thread.
+        let Some(mut operator_pipe) = self.operators.first_mut().map(std::mem::take) else {
+            return Ok(false);
+        };
+        let mut finished = false;
+        'operators: for op_i in operator_start..operator_end {
+            while let Some(data) = operator_pipe[op_i].flush(ec)? {
+                // flushed data comes after all the chunks of the sources
+                let chunk = DataChunk::new(IdxSize::MAX, data);
+                let operators = &mut operator_pipe[op_i + 1..operator_end];
+                let sink_result = if operators.is_empty() {
+                    sink.sink(ec, chunk)?
+                } else {
+                    self.push_operators(chunk, ec, operators, sink)?
+                };
+                if let SinkResult::Finished = sink_result {
+                    finished = true;
+                    break 'operators;
+                }
+            }
+        }
+        self.operators[0] = operator_pipe;
+        Ok(finished)
+    }
+
     /// Replace the current sources with a [`DataFrameSource`].
     fn set_df_as_sources(&mut self, df: DataFrame) {
         let src = Box::new(DataFrameSource::from_df(df)) as Box<dyn Source>;
@@ -379,8 +414,6 @@ impl PipeLine {
                     })
                 })
                 .unwrap();
-            operator_start = operator_end;
-
             let mut shared_sink_count = {
                 let mut shared_sink_count = shared_count.borrow_mut();
                 *shared_sink_count -= 1;
@@ -395,6 +428,15 @@ impl PipeLine {
                 shared_sink_count = count;
             }
 
+            // All pipelines that push into this sink are done, so the operators
+            // in front of it have seen all their data.
+            if shared_sink_count == 0 && !sink_finished {
+                sink_finished =
+                    self.flush_operators(ec, operator_start, operator_end, &mut reduced_sink)?;
+            }
+
+            operator_start = operator_end;
+
             if i != last_i {
                 let sink_result = reduced_sink.finalize(ec)?;
                 match sink_result {
@@ -8,6 +8,8 @@ use polars_core::prelude::*;
 use polars_core::POOL;
 
 pub use crate::executors::sinks::groupby::aggregates::can_convert_to_hash_agg;
+#[cfg(feature = "dynamic_groupby")]
+pub use crate::executors::sinks::groupby::temporal::can_convert_to_window_agg;
 
 pub(crate) fn morsels_per_sink() -> usize {
     POOL.current_num_threads()
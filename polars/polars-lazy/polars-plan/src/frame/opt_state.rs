@@ -1,3 +1,9 @@
+#[cfg(feature = "streaming")]
+use std::path::PathBuf;
+
+#[cfg(feature = "streaming")]
+use polars_io::ipc::IpcCompression;
+
 #[derive(Copy, Clone, Debug)]
 /// State of the allowed optimizations
 pub struct OptState {
@@ -35,3 +41,37 @@ impl Default for OptState {
 
 /// AllowedOptimizations
 pub type AllowedOptimizations = OptState;
+
+/// Configuration of the out-of-core operations of the streaming engine.
+#[cfg(feature = "streaming")]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct StreamingConfig {
+    /// Directory the spill files are written to. Defaults to the temp directory of the system.
+    pub spill_dir: Option<PathBuf>,
+    /// Memory in bytes the operators may use before they spill to disk. Defaults to the
+    /// memory that is free when the query starts.
+    pub memory_budget: Option<usize>,
+    /// Compression of the spilled IPC files.
+    pub spill_compression: Option<IpcCompression>,
+}
+
+#[cfg(feature = "streaming")]
+impl StreamingConfig {
+    /// Set the directory the spill files are written to.
+    pub fn with_spill_dir(mut self, spill_dir: impl Into<PathBuf>) -> Self {
+        self.spill_dir = Some(spill_dir.into());
+        self
+    }
+
+    /// Set the memory in bytes the operators may use before they spill to disk.
+    pub fn with_memory_budget(mut self, memory_budget: usize) -> Self {
+        self.memory_budget = Some(memory_budget);
+        self
+    }
+
+    /// Set the compression of the spilled IPC files.
+    pub fn with_spill_compression(mut self, compression: Option<IpcCompression>) -> Self {
+        self.spill_compression = compression;
+        self
+    }
+}
@@ -33,6 +33,30 @@ pub trait AnonymousScan: Send + Sync {
     fn allows_slice_pushdown(&self) -> bool {
         false
     }
+
+    /// specify if the scan provider can produce its data in batches, which allows the
+    /// streaming engine to use it as a source. See [`AnonymousScan::scan_batched`].
+    ///
+    /// Defaults to `false`
+    fn allows_streaming(&self) -> bool {
+        false
+    }
+
+    /// Creates a reader that produces the data in batches of roughly `chunk_size` rows.
+    /// Only called if [`AnonymousScan::allows_streaming`] returns `true`.
+    fn scan_batched(
+        &self,
+        _scan_opts: AnonymousScanOptions,
+        _chunk_size: usize,
+    ) -> PolarsResult<Box<dyn BatchedScan>> {
+        polars_bail!(ComputeError: "this scan cannot be read in batches")
+    }
+}
+
+/// A reader that produces the data of an [`AnonymousScan`] in batches.
+pub trait BatchedScan: Send {
+    /// Read the next `n` batches. Returns `None` if the scan is exhausted.
+    fn next_batches(&mut self, n: usize) -> PolarsResult<Option<Vec<DataFrame>>>;
 }
 
 impl<F> AnonymousScan for F
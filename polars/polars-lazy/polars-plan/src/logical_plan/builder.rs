@@ -637,17 +637,25 @@ impl LogicalPlanBuilder {
         self,
         by_column: Vec<Expr>,
         descending: Vec<bool>,
-        null_last: bool,
+        nulls_last: Vec<bool>,
         maintain_order: bool,
     ) -> Self {
         let schema = try_delayed!(self.0.schema(), &self.0, into);
         let by_column = try_delayed!(rewrite_projections(by_column, &schema, &[]), &self.0, into);
+        if nulls_last.len() != 1 && nulls_last.len() != by_column.len() {
+            let err = polars_err!(
+                ComputeError:
+                "the length of `nulls_last` ({}) does not match the number of sort columns ({})",
+                nulls_last.len(), by_column.len(),
+            );
+            return raise_err!(err, &self.0, into);
+        }
         LogicalPlan::Sort {
             input: Box::new(self.0),
             by_column,
             args: SortArguments {
                 descending,
-                nulls_last: null_last,
+                nulls_last,
                 slice: None,
                 maintain_order,
             },
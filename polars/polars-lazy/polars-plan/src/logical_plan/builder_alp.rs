@@ -11,7 +11,7 @@ pub struct ALogicalPlanBuilder<'a> {
 }
 
 impl<'a> ALogicalPlanBuilder<'a> {
-    pub(crate) fn new(
+    pub fn new(
         root: Node,
         expr_arena: &'a mut Arena<AExpr>,
         lp_arena: &'a mut Arena<ALogicalPlan>,
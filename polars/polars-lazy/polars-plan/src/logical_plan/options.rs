@@ -2,6 +2,8 @@ use std::path::PathBuf;
 
 use polars_core::prelude::*;
 #[cfg(feature = "csv")]
+use polars_io::csv::SerializeOptions;
+#[cfg(feature = "csv")]
 use polars_io::csv::{CsvEncoding, NullValues};
 #[cfg(feature = "ipc")]
 use polars_io::ipc::IpcCompression;
@@ -61,6 +63,43 @@ pub struct ParquetWriteOptions {
     pub maintain_order: bool,
 }
 
+#[cfg(feature = "parquet")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PartitionedParquetWriteOptions {
+    /// Options of the written parquet files
+    pub parquet: ParquetWriteOptions,
+    /// Write a directory level `key=value` per column
+    pub partition_by: Vec<String>,
+    /// Start a new file in a partition once it holds this many rows
+    pub max_rows_per_file: Option<usize>,
+    /// Start a new file in a partition once this many (in-memory) bytes are written to it
+    pub max_bytes_per_file: Option<usize>,
+    /// The number of files that may be open at once; the least recently
+    /// written file is closed if a new one must be opened.
+    pub max_open_files: usize,
+}
+
+#[cfg(feature = "parquet")]
+impl PartitionedParquetWriteOptions {
+    pub fn new<I, S>(partition_by: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self {
+            parquet: Default::default(),
+            partition_by: partition_by
+                .into_iter()
+                .map(|s| s.as_ref().to_string())
+                .collect(),
+            max_rows_per_file: None,
+            max_bytes_per_file: None,
+            max_open_files: 64,
+        }
+    }
+}
+
 #[cfg(feature = "ipc")]
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -71,6 +110,40 @@ pub struct IpcWriterOptions {
     pub maintain_order: bool,
 }
 
+#[cfg(feature = "csv")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct CsvWriterOptions {
+    /// Write the column names as the first line
+    pub include_header: bool,
+    /// Number of rows serialized per thread at a time
+    pub batch_size: usize,
+    /// maintain the order the data was processed
+    pub maintain_order: bool,
+    /// Delimiter, quoting, null and temporal formatting options
+    pub serialize_options: SerializeOptions,
+}
+
+#[cfg(feature = "csv")]
+impl Default for CsvWriterOptions {
+    fn default() -> Self {
+        Self {
+            include_header: true,
+            batch_size: 1024,
+            maintain_order: false,
+            serialize_options: SerializeOptions::default(),
+        }
+    }
+}
+
+#[cfg(feature = "json")]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct JsonWriterOptions {
+    /// maintain the order the data was processed
+    pub maintain_order: bool,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct IpcScanOptions {
@@ -252,7 +325,8 @@ pub struct LogicalPlanUdfOptions {
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct SortArguments {
     pub descending: Vec<bool>,
-    pub nulls_last: bool,
+    /// Whether nulls are placed last, per sort column or a single value for all columns.
+    pub nulls_last: Vec<bool>,
     pub slice: Option<(i64, usize)>,
     pub maintain_order: bool,
 }
@@ -297,7 +371,13 @@ pub struct FileSinkOptions {
 pub enum FileType {
     #[cfg(feature = "parquet")]
     Parquet(ParquetWriteOptions),
+    #[cfg(feature = "parquet")]
+    PartitionedParquet(PartitionedParquetWriteOptions),
     #[cfg(feature = "ipc")]
     Ipc(IpcWriterOptions),
+    #[cfg(feature = "csv")]
+    Csv(CsvWriterOptions),
+    #[cfg(feature = "json")]
+    Json(JsonWriterOptions),
     Memory,
 }
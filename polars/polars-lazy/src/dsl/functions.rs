@@ -24,6 +24,8 @@ pub(crate) fn concat_impl<L: AsRef<[LazyFrame]>>(
     );
 
     let mut opt_state = lf.opt_state;
+    #[cfg(feature = "streaming")]
+    let streaming_config = lf.streaming_config.clone();
     let options = UnionOptions {
         parallel,
         from_partitioned_ds,
@@ -62,6 +64,10 @@ pub(crate) fn concat_impl<L: AsRef<[LazyFrame]>>(
             };
             let mut lf = LazyFrame::from(lp);
             lf.opt_state = opt_state;
+            #[cfg(feature = "streaming")]
+            {
+                lf.streaming_config = streaming_config;
+            }
 
             lf
         }
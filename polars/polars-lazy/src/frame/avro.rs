@@ -0,0 +1,80 @@
+use std::path::{Path, PathBuf};
+
+use polars_core::prelude::*;
+use polars_io::RowCount;
+
+use super::{LazyFileListReader, LazyFrame, ScanArgsAnonymous};
+
+#[derive(Clone)]
+pub struct LazyAvroReader {
+    pub(crate) path: PathBuf,
+    pub(crate) rechunk: bool,
+    pub(crate) row_count: Option<RowCount>,
+    pub(crate) n_rows: Option<usize>,
+}
+
+impl LazyAvroReader {
+    pub fn new(path: impl AsRef<Path>) -> Self {
+        LazyAvroReader {
+            path: path.as_ref().to_path_buf(),
+            rechunk: true,
+            row_count: None,
+            n_rows: None,
+        }
+    }
+    /// Add a `row_count` column.
+    #[must_use]
+    pub fn with_row_count(mut self, row_count: Option<RowCount>) -> Self {
+        self.row_count = row_count;
+        self
+    }
+    /// Stop reading when `n` rows are read.
+    #[must_use]
+    pub fn with_n_rows(mut self, num_rows: Option<usize>) -> Self {
+        self.n_rows = num_rows;
+        self
+    }
+}
+
+impl LazyFileListReader for LazyAvroReader {
+    fn finish_no_glob(self) -> PolarsResult<LazyFrame> {
+        let options = ScanArgsAnonymous {
+            name: "AVRO SCAN",
+            n_rows: self.n_rows,
+            row_count: self.row_count.clone(),
+            ..ScanArgsAnonymous::default()
+        };
+
+        LazyFrame::anonymous_scan(std::sync::Arc::new(self), options)
+    }
+
+    fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn with_path(mut self, path: PathBuf) -> Self {
+        self.path = path;
+        self
+    }
+
+    fn rechunk(&self) -> bool {
+        self.rechunk
+    }
+
+    /// Rechunk the memory to contiguous chunks when parsing is done.
+    #[must_use]
+    fn with_rechunk(mut self, toggle: bool) -> Self {
+        self.rechunk = toggle;
+        self
+    }
+
+    /// Stop reading when `n` rows are read.
+    fn n_rows(&self) -> Option<usize> {
+        self.n_rows
+    }
+
+    /// Add a `row_count` column.
+    fn row_count(&self) -> Option<&RowCount> {
+        self.row_count.as_ref()
+    }
+}
@@ -1,8 +1,12 @@
 //! Lazy variant of a [DataFrame](polars_core::frame::DataFrame).
+#[cfg(feature = "avro")]
+mod avro;
 #[cfg(feature = "csv")]
 mod csv;
 #[cfg(feature = "ipc")]
 mod ipc;
+#[cfg(feature = "ipc_streaming")]
+mod ipc_stream;
 #[cfg(feature = "json")]
 mod ndjson;
 #[cfg(feature = "parquet")]
@@ -17,16 +21,25 @@ mod file_list_reader;
 pub mod pivot;
 
 use std::borrow::Cow;
-#[cfg(any(feature = "parquet", feature = "ipc"))]
+#[cfg(any(
+    feature = "parquet",
+    feature = "ipc",
+    feature = "csv",
+    feature = "json"
+))]
 use std::path::PathBuf;
 use std::sync::Arc;
 
 pub use anonymous_scan::*;
+#[cfg(feature = "avro")]
+pub use avro::*;
 #[cfg(feature = "csv")]
 pub use csv::*;
 pub use file_list_reader::*;
 #[cfg(feature = "ipc")]
 pub use ipc::*;
+#[cfg(feature = "ipc_streaming")]
+pub use ipc_stream::*;
 #[cfg(feature = "json")]
 pub use ndjson::*;
 #[cfg(feature = "parquet")]
@@ -36,6 +49,8 @@ use polars_core::frame::explode::MeltArgs;
 use polars_core::frame::hash_join::{JoinType, JoinValidation};
 use polars_core::prelude::*;
 use polars_io::RowCount;
+#[cfg(feature = "streaming")]
+pub use polars_plan::frame::StreamingConfig;
 pub use polars_plan::frame::{AllowedOptimizations, OptState};
 use polars_plan::global::FETCH_ROWS;
 #[cfg(any(feature = "ipc", feature = "parquet", feature = "csv"))]
@@ -49,7 +64,7 @@ use crate::physical_plan::executors::Executor;
 use crate::physical_plan::planner::create_physical_plan;
 use crate::physical_plan::state::ExecutionState;
 #[cfg(feature = "streaming")]
-use crate::physical_plan::streaming::insert_streaming_nodes;
+use crate::physical_plan::streaming::{insert_streaming_nodes, StreamingExplain};
 use crate::prelude::*;
 
 pub trait IntoLazy {
@@ -63,6 +78,8 @@ impl IntoLazy for DataFrame {
         LazyFrame {
             logical_plan: lp,
             opt_state: Default::default(),
+            #[cfg(feature = "streaming")]
+            streaming_config: Default::default(),
         }
     }
 }
@@ -75,6 +92,17 @@ impl IntoLazy for DataFrame {
 pub struct LazyFrame {
     pub logical_plan: LogicalPlan,
     pub(crate) opt_state: OptState,
+    #[cfg(feature = "streaming")]
+    pub(crate) streaming_config: StreamingConfig,
+}
+
+/// The optimizations and the streaming configuration that a [`LazyFrame`] passes on to the
+/// frames that are derived from it.
+#[derive(Clone, Default)]
+struct FrameState {
+    opt_state: OptState,
+    #[cfg(feature = "streaming")]
+    streaming_config: StreamingConfig,
 }
 
 impl From<LogicalPlan> for LazyFrame {
@@ -85,6 +113,8 @@ impl From<LogicalPlan> for LazyFrame {
                 file_caching: true,
                 ..Default::default()
             },
+            #[cfg(feature = "streaming")]
+            streaming_config: Default::default(),
         }
     }
 }
@@ -99,14 +129,20 @@ impl LazyFrame {
         LogicalPlanBuilder::from(self.logical_plan)
     }
 
-    fn get_opt_state(&self) -> OptState {
-        self.opt_state
+    fn get_state(&self) -> FrameState {
+        FrameState {
+            opt_state: self.opt_state,
+            #[cfg(feature = "streaming")]
+            streaming_config: self.streaming_config.clone(),
+        }
     }
 
-    fn from_logical_plan(logical_plan: LogicalPlan, opt_state: OptState) -> Self {
+    fn from_logical_plan(logical_plan: LogicalPlan, state: FrameState) -> Self {
         LazyFrame {
             logical_plan,
-            opt_state,
+            opt_state: state.opt_state,
+            #[cfg(feature = "streaming")]
+            streaming_config: state.streaming_config,
         }
     }
 
@@ -189,13 +225,27 @@ impl LazyFrame {
         self
     }
 
+    /// Set the spill directory, memory budget and spill compression of the streaming engine.
+    #[cfg(feature = "streaming")]
+    pub fn with_streaming_config(mut self, config: StreamingConfig) -> Self {
+        self.streaming_config = config;
+        self
+    }
+
     /// Explain the naive logical plan.
     pub fn describe_plan(&self) -> String {
         self.logical_plan.describe()
     }
 
     /// Explain the optimized logical plan.
+    ///
+    /// If streaming is enabled, this also shows the nodes of every streaming pipeline and
+    /// why the other nodes are not streamed.
     pub fn describe_optimized_plan(&self) -> PolarsResult<String> {
+        #[cfg(feature = "streaming")]
+        if self.opt_state.streaming {
+            return self.describe_streaming_plan();
+        }
         let mut expr_arena = Arena::with_capacity(64);
         let mut lp_arena = Arena::with_capacity(64);
         let lp_top = self.clone().optimize_with_scratch(
@@ -208,6 +258,30 @@ impl LazyFrame {
         Ok(logical_plan.describe())
     }
 
+    #[cfg(feature = "streaming")]
+    fn describe_streaming_plan(&self) -> PolarsResult<String> {
+        let mut expr_arena = Arena::with_capacity(64);
+        let mut lp_arena = Arena::with_capacity(64);
+        let mut scratch = vec![];
+        let streaming_config = self.streaming_config.clone();
+        let lp_top =
+            self.clone()
+                .optimize_logical_plan(&mut lp_arena, &mut expr_arena, &mut scratch)?;
+        let mut explain = StreamingExplain::default();
+        insert_streaming_nodes(
+            lp_top,
+            &mut lp_arena,
+            &mut expr_arena,
+            &mut scratch,
+            true,
+            true,
+            &streaming_config,
+            Some(&mut explain),
+        )?;
+        let logical_plan = node_to_lp(lp_top, &expr_arena, &mut lp_arena);
+        Ok(format!("{}\n{explain}", logical_plan.describe()))
+    }
+
     /// Explain the logical plan.
     pub fn explain(&self, optimized: bool) -> PolarsResult<String> {
         if optimized {
@@ -236,21 +310,24 @@ impl LazyFrame {
         let nulls_last = options.nulls_last;
         let maintain_order = options.maintain_order;
 
-        let opt_state = self.get_opt_state();
+        let state = self.get_state();
         let lp = self
             .get_plan_builder()
             .sort(
                 vec![col(by_column)],
                 vec![descending],
-                nulls_last,
+                vec![nulls_last],
                 maintain_order,
             )
             .build();
-        Self::from_logical_plan(lp, opt_state)
+        Self::from_logical_plan(lp, state)
     }
 
     /// Add a sort operation to the logical plan.
     ///
+    /// `nulls_last` is either a single `bool` that applies to all columns, or a `Vec<bool>`
+    /// with a value per column.
+    ///
     /// # Example
     ///
     /// ```rust
@@ -262,25 +339,37 @@ impl LazyFrame {
     ///       df.lazy()
     ///         .sort_by_exprs(vec![col("sepal.width")], vec![false], false, false)
     /// }
+    ///
+    /// /// Sort DataFrame by 'sepal.width' with nulls first and by 'sepal.length' with nulls last
+    /// fn example_nulls_last(df: DataFrame) -> LazyFrame {
+    ///       df.lazy()
+    ///         .sort_by_exprs(
+    ///             vec![col("sepal.width"), col("sepal.length")],
+    ///             vec![false, true],
+    ///             vec![false, true],
+    ///             false,
+    ///         )
+    /// }
     /// ```
     pub fn sort_by_exprs<E: AsRef<[Expr]>, B: AsRef<[bool]>>(
         self,
         by_exprs: E,
         descending: B,
-        nulls_last: bool,
+        nulls_last: impl IntoVec<bool>,
         maintain_order: bool,
     ) -> Self {
         let by_exprs = by_exprs.as_ref().to_vec();
         let descending = descending.as_ref().to_vec();
+        let nulls_last = nulls_last.into_vec();
         if by_exprs.is_empty() {
             self
         } else {
-            let opt_state = self.get_opt_state();
+            let state = self.get_state();
             let lp = self
                 .get_plan_builder()
                 .sort(by_exprs, descending, nulls_last, maintain_order)
                 .build();
-            Self::from_logical_plan(lp, opt_state)
+            Self::from_logical_plan(lp, state)
         }
     }
 
@@ -289,7 +378,7 @@ impl LazyFrame {
         k: IdxSize,
         by_exprs: E,
         descending: B,
-        nulls_last: bool,
+        nulls_last: impl IntoVec<bool>,
         maintain_order: bool,
     ) -> Self {
         let mut descending = descending.as_ref().to_vec();
@@ -307,7 +396,7 @@ impl LazyFrame {
         k: IdxSize,
         by_exprs: E,
         descending: B,
-        nulls_last: bool,
+        nulls_last: impl IntoVec<bool>,
         maintain_order: bool,
     ) -> Self {
         let descending = descending.as_ref().to_vec();
@@ -355,7 +444,7 @@ impl LazyFrame {
                 .get_plan_builder()
                 .add_err(polars_err!(SchemaFieldNotFound: "{}", name))
                 .build();
-            Some(Self::from_logical_plan(lp, self.opt_state))
+            Some(Self::from_logical_plan(lp, self.get_state()))
         } else {
             None
         }
@@ -443,24 +532,24 @@ impl LazyFrame {
 
     /// Fill none values in the DataFrame
     pub fn fill_null<E: Into<Expr>>(self, fill_value: E) -> LazyFrame {
-        let opt_state = self.get_opt_state();
+        let state = self.get_state();
         let lp = self.get_plan_builder().fill_null(fill_value.into()).build();
-        Self::from_logical_plan(lp, opt_state)
+        Self::from_logical_plan(lp, state)
     }
 
     /// Fill NaN values in the DataFrame
     pub fn fill_nan<E: Into<Expr>>(self, fill_value: E) -> LazyFrame {
-        let opt_state = self.get_opt_state();
+        let state = self.get_state();
         let lp = self.get_plan_builder().fill_nan(fill_value.into()).build();
-        Self::from_logical_plan(lp, opt_state)
+        Self::from_logical_plan(lp, state)
     }
 
     /// Caches the result into a new LazyFrame. This should be used to prevent computations
     /// running multiple times
     pub fn cache(self) -> Self {
-        let opt_state = self.get_opt_state();
+        let state = self.get_state();
         let lp = self.get_plan_builder().cache().build();
-        Self::from_logical_plan(lp, opt_state)
+        Self::from_logical_plan(lp, state)
     }
 
     /// Fetch is like a collect operation, but it overwrites the number of rows read by every scan
@@ -503,22 +592,24 @@ impl LazyFrame {
         scratch: &mut Vec<Node>,
         _fmt: bool,
     ) -> PolarsResult<Node> {
-        #[allow(unused_mut)]
-        let mut opt_state = self.opt_state;
         let streaming = self.opt_state.streaming;
-        #[cfg(feature = "cse")]
-        if streaming && self.opt_state.comm_subplan_elim {
-            polars_warn!(
-                "Cannot combine 'streaming' with 'comm_subplan_elim'. CSE will be turned off."
-            );
-            opt_state.comm_subplan_elim = false;
-        }
-        let lp_top = optimize(self.logical_plan, opt_state, lp_arena, expr_arena, scratch)?;
+        #[cfg(feature = "streaming")]
+        let streaming_config = self.streaming_config.clone();
+        let lp_top = self.optimize_logical_plan(lp_arena, expr_arena, scratch)?;
 
         if streaming {
             #[cfg(feature = "streaming")]
             {
-                insert_streaming_nodes(lp_top, lp_arena, expr_arena, scratch, _fmt, true)?;
+                insert_streaming_nodes(
+                    lp_top,
+                    lp_arena,
+                    expr_arena,
+                    scratch,
+                    _fmt,
+                    true,
+                    &streaming_config,
+                    None,
+                )?;
             }
             #[cfg(not(feature = "streaming"))]
             {
@@ -528,10 +619,32 @@ impl LazyFrame {
         Ok(lp_top)
     }
 
+    /// Run the optimizations on the logical plan, without inserting the streaming nodes.
+    fn optimize_logical_plan(
+        self,
+        lp_arena: &mut Arena<ALogicalPlan>,
+        expr_arena: &mut Arena<AExpr>,
+        scratch: &mut Vec<Node>,
+    ) -> PolarsResult<Node> {
+        #[allow(unused_mut)]
+        let mut opt_state = self.opt_state;
+        #[cfg(feature = "cse")]
+        if opt_state.streaming && opt_state.comm_subplan_elim {
+            polars_warn!(
+                "Cannot combine 'streaming' with 'comm_subplan_elim'. CSE will be turned off."
+            );
+            opt_state.comm_subplan_elim = false;
+        }
+        optimize(self.logical_plan, opt_state, lp_arena, expr_arena, scratch)
+    }
+
+    /// Optimize the query and create its physical plan. If `plan` is given, it is filled with
+    /// the first line of the description of every node of the optimized plan.
     #[allow(unused_mut)]
     fn prepare_collect(
         mut self,
         check_sink: bool,
+        plan: Option<&mut PlHashMap<Node, String>>,
     ) -> PolarsResult<(ExecutionState, Box<dyn Executor>, bool)> {
         let file_caching = self.opt_state.file_caching;
         let mut expr_arena = Arena::with_capacity(256);
@@ -561,6 +674,16 @@ impl LazyFrame {
         } else {
             true
         };
+        if let Some(plan) = plan {
+            let mut stack = vec![lp_top];
+            while let Some(node) = stack.pop() {
+                let lp = node_to_lp_cloned(node, &expr_arena, &lp_arena);
+                let description = format!("{lp:?}");
+                let line = description.lines().next().unwrap_or_default();
+                plan.insert(node, line.trim().to_string());
+                lp_arena.get(node).copy_inputs(&mut stack);
+            }
+        }
         let physical_plan = create_physical_plan(lp_top, &mut lp_arena, &mut expr_arena)?;
 
         let state = ExecutionState::with_finger_prints(finger_prints);
@@ -584,7 +707,7 @@ impl LazyFrame {
     /// }
     /// ```
     pub fn collect(self) -> PolarsResult<DataFrame> {
-        let (mut state, mut physical_plan, _) = self.prepare_collect(false)?;
+        let (mut state, mut physical_plan, _) = self.prepare_collect(false, None)?;
         let out = physical_plan.execute(&mut state);
         #[cfg(debug_assertions)]
         {
@@ -602,13 +725,52 @@ impl LazyFrame {
     ///
     /// The units of the timings are microseconds.
     pub fn profile(self) -> PolarsResult<(DataFrame, DataFrame)> {
-        let (mut state, mut physical_plan, _) = self.prepare_collect(false)?;
+        self.profile_impl(false)
+    }
+
+    /// Profile a LazyFrame, like [`LazyFrame::profile`].
+    ///
+    /// The profiling information has an additional `rows` column with the number of rows
+    /// each node produced, and a `plan` column with the first line of the description of
+    /// the node in the optimized plan (see [`LazyFrame::describe_optimized_plan`]).
+    pub fn profile_with_row_counts(self) -> PolarsResult<(DataFrame, DataFrame)> {
+        self.profile_impl(true)
+    }
+
+    fn profile_impl(self, with_rows: bool) -> PolarsResult<(DataFrame, DataFrame)> {
+        let mut plan = PlHashMap::new();
+        let (mut state, mut physical_plan, _) =
+            self.prepare_collect(false, with_rows.then_some(&mut plan))?;
         state.time_nodes();
         let out = physical_plan.execute(&mut state)?;
-        let timer_df = state.finish_timer()?;
+        let timer_df = state.finish_timer(with_rows.then_some(&plan))?;
         Ok((out, timer_df))
     }
 
+    /// Whether the whole query can be run in streaming fashion, which the `sink_*` methods
+    /// require. A query that can't be streamed can be collected before it is sunk.
+    #[cfg(feature = "streaming")]
+    pub fn is_streamable(&self) -> PolarsResult<bool> {
+        let mut lf = self.clone();
+        lf.opt_state.streaming = true;
+        lf.logical_plan = LogicalPlan::FileSink {
+            input: Box::new(lf.logical_plan),
+            payload: FileSinkOptions {
+                path: Default::default(),
+                file_type: FileType::Memory,
+            },
+        };
+        let mut expr_arena = Arena::with_capacity(64);
+        let mut lp_arena = Arena::with_capacity(64);
+        // the plan is only inspected, not run
+        let lp_top = lf.optimize_with_scratch(&mut lp_arena, &mut expr_arena, &mut vec![], true)?;
+        // the sink is replaced by a pipeline if everything below it is streamed
+        Ok(!matches!(
+            lp_arena.get(lp_top),
+            ALogicalPlan::FileSink { .. }
+        ))
+    }
+
     /// Stream a query result into a parquet file. This is useful if the final result doesn't fit
     /// into memory. This methods will return an error if the query cannot be completely done in a
     /// streaming fashion.
@@ -622,7 +784,7 @@ impl LazyFrame {
                 file_type: FileType::Parquet(options),
             },
         };
-        let (mut state, mut physical_plan, is_streaming) = self.prepare_collect(true)?;
+        let (mut state, mut physical_plan, is_streaming) = self.prepare_collect(true, None)?;
         polars_ensure!(
             is_streaming,
             ComputeError: "cannot run the whole query in a streaming order; \
@@ -632,6 +794,43 @@ impl LazyFrame {
         Ok(())
     }
 
+    /// Stream a query result into a hive partitioned parquet dataset at `root`. Every partition
+    /// of the `partition_by` columns is written to `key=value/part-N.parquet`, where a new part is
+    /// started if a file exceeds the maximum number of rows or bytes, or if its writer was closed
+    /// because too many files were open. This methods will return an error if the query cannot be
+    /// completely done in a streaming fashion.
+    #[cfg(feature = "parquet")]
+    pub fn sink_parquet_partitioned(
+        mut self,
+        root: PathBuf,
+        options: PartitionedParquetWriteOptions,
+    ) -> PolarsResult<()> {
+        polars_ensure!(
+            !options.partition_by.is_empty(),
+            ComputeError: "`partition_by` of a partitioned sink cannot be empty"
+        );
+        polars_ensure!(
+            options.max_open_files > 0 && options.max_rows_per_file != Some(0),
+            ComputeError: "`max_open_files` and `max_rows_per_file` of a partitioned sink must be positive"
+        );
+        self.opt_state.streaming = true;
+        self.logical_plan = LogicalPlan::FileSink {
+            input: Box::new(self.logical_plan),
+            payload: FileSinkOptions {
+                path: Arc::new(root),
+                file_type: FileType::PartitionedParquet(options),
+            },
+        };
+        let (mut state, mut physical_plan, is_streaming) = self.prepare_collect(true, None)?;
+        polars_ensure!(
+            is_streaming,
+            ComputeError: "cannot run the whole query in a streaming order; \
+            use `collect()` and `PartitionedWriter` instead"
+        );
+        let _ = physical_plan.execute(&mut state)?;
+        Ok(())
+    }
+
     /// Stream a query result into an ipc/arrow file. This is useful if the final result doesn't fit
     /// into memory. This methods will return an error if the query cannot be completely done in a
     /// streaming fashion.
@@ -645,7 +844,7 @@ impl LazyFrame {
                 file_type: FileType::Ipc(options),
             },
         };
-        let (mut state, mut physical_plan, is_streaming) = self.prepare_collect(true)?;
+        let (mut state, mut physical_plan, is_streaming) = self.prepare_collect(true, None)?;
         polars_ensure!(
             is_streaming,
             ComputeError: "cannot run the whole query in a streaming order; \
@@ -655,6 +854,52 @@ impl LazyFrame {
         Ok(())
     }
 
+    /// Stream a query result into a csv file. This is useful if the final result doesn't fit
+    /// into memory. This methods will return an error if the query cannot be completely done in a
+    /// streaming fashion.
+    #[cfg(feature = "csv")]
+    pub fn sink_csv(mut self, path: PathBuf, options: CsvWriterOptions) -> PolarsResult<()> {
+        self.opt_state.streaming = true;
+        self.logical_plan = LogicalPlan::FileSink {
+            input: Box::new(self.logical_plan),
+            payload: FileSinkOptions {
+                path: Arc::new(path),
+                file_type: FileType::Csv(options),
+            },
+        };
+        let (mut state, mut physical_plan, is_streaming) = self.prepare_collect(true, None)?;
+        polars_ensure!(
+            is_streaming,
+            ComputeError: "cannot run the whole query in a streaming order; \
+            use `collect().write_csv()` instead"
+        );
+        let _ = physical_plan.execute(&mut state)?;
+        Ok(())
+    }
+
+    /// Stream a query result into a newline delimited json file. This is useful if the final
+    /// result doesn't fit into memory. This methods will return an error if the query cannot be
+    /// completely done in a streaming fashion.
+    #[cfg(feature = "json")]
+    pub fn sink_ndjson(mut self, path: PathBuf, options: JsonWriterOptions) -> PolarsResult<()> {
+        self.opt_state.streaming = true;
+        self.logical_plan = LogicalPlan::FileSink {
+            input: Box::new(self.logical_plan),
+            payload: FileSinkOptions {
+                path: Arc::new(path),
+                file_type: FileType::Json(options),
+            },
+        };
+        let (mut state, mut physical_plan, is_streaming) = self.prepare_collect(true, None)?;
+        polars_ensure!(
+            is_streaming,
+            ComputeError: "cannot run the whole query in a streaming order; \
+            use `collect().write_json()` instead"
+        );
+        let _ = physical_plan.execute(&mut state)?;
+        Ok(())
+    }
+
     /// Filter by some predicate expression.
     ///
     /// # Example
@@ -670,9 +915,9 @@ impl LazyFrame {
     /// }
     /// ```
     pub fn filter(self, predicate: Expr) -> Self {
-        let opt_state = self.get_opt_state();
+        let state = self.get_state();
         let lp = self.get_plan_builder().filter(predicate).build();
-        Self::from_logical_plan(lp, opt_state)
+        Self::from_logical_plan(lp, state)
     }
 
     /// Select (and rename) columns from the query.
@@ -701,20 +946,20 @@ impl LazyFrame {
     /// }
     /// ```
     pub fn select<E: AsRef<[Expr]>>(self, exprs: E) -> Self {
-        let opt_state = self.get_opt_state();
+        let state = self.get_state();
         let lp = self
             .get_plan_builder()
             .project(exprs.as_ref().to_vec())
             .build();
-        Self::from_logical_plan(lp, opt_state)
+        Self::from_logical_plan(lp, state)
     }
 
     /// A projection that doesn't get optimized and may drop projections if they are not in
     /// schema after optimization
     fn select_local(self, exprs: Vec<Expr>) -> Self {
-        let opt_state = self.get_opt_state();
+        let state = self.get_state();
         let lp = self.get_plan_builder().project_local(exprs).build();
-        Self::from_logical_plan(lp, opt_state)
+        Self::from_logical_plan(lp, state)
     }
 
     /// Group by and aggregate.
@@ -742,13 +987,13 @@ impl LazyFrame {
             .iter()
             .map(|e| e.clone().into())
             .collect::<Vec<_>>();
-        let opt_state = self.get_opt_state();
+        let state = self.get_state();
 
         #[cfg(feature = "dynamic_groupby")]
         {
             LazyGroupBy {
                 logical_plan: self.logical_plan,
-                opt_state,
+                state,
                 keys,
                 maintain_order: false,
                 dynamic_options: None,
@@ -760,7 +1005,7 @@ impl LazyFrame {
         {
             LazyGroupBy {
                 logical_plan: self.logical_plan,
-                opt_state,
+                state,
                 keys,
                 maintain_order: false,
             }
@@ -789,10 +1034,10 @@ impl LazyFrame {
                 .with_column(index_column)
                 .groupby_rolling(Expr::Column(name), by, options);
         }
-        let opt_state = self.get_opt_state();
+        let state = self.get_state();
         LazyGroupBy {
             logical_plan: self.logical_plan,
-            opt_state,
+            state,
             keys: by.as_ref().to_vec(),
             maintain_order: true,
             dynamic_options: None,
@@ -830,10 +1075,10 @@ impl LazyFrame {
                 .with_column(index_column)
                 .groupby_dynamic(Expr::Column(name), by, options);
         }
-        let opt_state = self.get_opt_state();
+        let state = self.get_state();
         LazyGroupBy {
             logical_plan: self.logical_plan,
-            opt_state,
+            state,
             keys: by.as_ref().to_vec(),
             maintain_order: true,
             dynamic_options: Some(options),
@@ -848,13 +1093,13 @@ impl LazyFrame {
             .iter()
             .map(|e| e.clone().into())
             .collect::<Vec<_>>();
-        let opt_state = self.get_opt_state();
+        let state = self.get_state();
 
         #[cfg(feature = "dynamic_groupby")]
         {
             LazyGroupBy {
                 logical_plan: self.logical_plan,
-                opt_state,
+                state,
                 keys,
                 maintain_order: true,
                 dynamic_options: None,
@@ -866,13 +1111,126 @@ impl LazyFrame {
         {
             LazyGroupBy {
                 logical_plan: self.logical_plan,
-                opt_state,
+                state,
                 keys,
                 maintain_order: true,
             }
         }
     }
 
+    /// Group by every set of keys in `sets` and union the aggregated results, similar
+    /// to SQL `GROUPING SETS`.
+    ///
+    /// The output contains every key that appears in any of the sets; keys that are not
+    /// part of a set are null in the rows produced by that set. An empty set aggregates
+    /// over the whole frame.
+    ///
+    /// If `grouping_id` is given, an `Int32` column with that name is added, holding a
+    /// bitmask of the keys that were aggregated away in that row. The first key is the
+    /// most significant bit, as in SQL `GROUPING()`.
+    pub fn groupby_grouping_sets<E: AsRef<[Expr]>>(
+        self,
+        sets: &[E],
+        aggs: &[Expr],
+        grouping_id: Option<&str>,
+    ) -> PolarsResult<LazyFrame> {
+        polars_ensure!(
+            !sets.is_empty(),
+            ComputeError: "grouping sets require at least one set of keys"
+        );
+        let schema = self.schema()?;
+
+        // the union of all keys, in order of first appearance
+        let mut keys: Vec<Field> = vec![];
+        for set in sets {
+            for e in set.as_ref() {
+                let field = e.to_field(&schema, Context::Default)?;
+                if !keys.iter().any(|key| key.name() == field.name()) {
+                    keys.push(field)
+                }
+            }
+        }
+        polars_ensure!(
+            keys.len() < 32,
+            ComputeError: "grouping sets support at most 31 distinct keys, got {}", keys.len()
+        );
+        let agg_names = aggs
+            .iter()
+            .map(|e| Ok(e.to_field(&schema, Context::Aggregation)?.name))
+            .collect::<PolarsResult<Vec<_>>>()?;
+
+        let frames = sets
+            .iter()
+            .map(|exprs| {
+                // a key may appear more than once in a set, e.g. `a, ROLLUP(a, b)` in SQL
+                let exprs = exprs.as_ref();
+                let mut set = Vec::with_capacity(exprs.len());
+                let mut set_names = Vec::with_capacity(exprs.len());
+                for e in exprs {
+                    let name = e.to_field(&schema, Context::Default)?.name;
+                    if !set_names.contains(&name) {
+                        set.push(e.clone());
+                        set_names.push(name);
+                    }
+                }
+
+                let lf = if set.is_empty() {
+                    // make sure the grand total has a row, even without aggregations
+                    if aggs.is_empty() {
+                        self.clone().select([count()])
+                    } else {
+                        self.clone().select(aggs)
+                    }
+                } else {
+                    self.clone().groupby(&set).agg(aggs)
+                };
+
+                let mut mask = 0i32;
+                let mut projection = Vec::with_capacity(keys.len() + aggs.len() + 1);
+                for key in &keys {
+                    mask <<= 1;
+                    if set_names.contains(key.name()) {
+                        projection.push(col(key.name()));
+                    } else {
+                        mask |= 1;
+                        projection.push(lit(NULL).cast(key.data_type().clone()).alias(key.name()));
+                    }
+                }
+                projection.extend(agg_names.iter().map(|name| col(name)));
+                if let Some(name) = grouping_id {
+                    projection.push(lit(mask).alias(name));
+                }
+                Ok(lf.select(projection))
+            })
+            .collect::<PolarsResult<Vec<_>>>()?;
+
+        crate::dsl::concat(frames, UnionArgs::default())
+    }
+
+    /// Group by all prefixes of `by`, from the full set of keys down to the empty set,
+    /// similar to SQL `GROUP BY ROLLUP(..)`. This produces subtotals per level of the
+    /// hierarchy and a grand total in one pass.
+    ///
+    /// See [`groupby_grouping_sets`][`Self::groupby_grouping_sets`] for how the results
+    /// are combined.
+    pub fn groupby_rollup<E: AsRef<[Expr]>>(self, by: E, aggs: &[Expr]) -> PolarsResult<LazyFrame> {
+        let by = by.as_ref();
+        let sets = (0..=by.len())
+            .rev()
+            .map(|n| by[..n].to_vec())
+            .collect::<Vec<_>>();
+        self.groupby_grouping_sets(&sets, aggs, None)
+    }
+
+    /// Group by every subset of `by`, similar to SQL `GROUP BY CUBE(..)`.
+    ///
+    /// See [`groupby_grouping_sets`][`Self::groupby_grouping_sets`] for how the results
+    /// are combined.
+    pub fn groupby_cube<E: AsRef<[Expr]>>(self, by: E, aggs: &[Expr]) -> PolarsResult<LazyFrame> {
+        let sets = cube_sets(by.as_ref())?;
+        self.groupby_grouping_sets(&sets, aggs, None)
+    }
+
     /// Join query with other lazy query.
     ///
     /// # Example
@@ -998,9 +1356,9 @@ impl LazyFrame {
     /// }
     /// ```
     pub fn with_column(self, expr: Expr) -> LazyFrame {
-        let opt_state = self.get_opt_state();
+        let state = self.get_state();
         let lp = self.get_plan_builder().with_columns(vec![expr]).build();
-        Self::from_logical_plan(lp, opt_state)
+        Self::from_logical_plan(lp, state)
     }
 
     /// Add multiple columns to a DataFrame.
@@ -1019,9 +1377,9 @@ impl LazyFrame {
     /// ```
     pub fn with_columns<E: AsRef<[Expr]>>(self, exprs: E) -> LazyFrame {
         let exprs = exprs.as_ref().to_vec();
-        let opt_state = self.get_opt_state();
+        let state = self.get_state();
         let lp = self.get_plan_builder().with_columns(exprs).build();
-        Self::from_logical_plan(lp, opt_state)
+        Self::from_logical_plan(lp, state)
     }
 
     pub fn with_context<C: AsRef<[LazyFrame]>>(self, contexts: C) -> LazyFrame {
@@ -1030,9 +1388,9 @@ impl LazyFrame {
             .iter()
             .map(|lf| lf.logical_plan.clone())
             .collect();
-        let opt_state = self.get_opt_state();
+        let state = self.get_state();
         let lp = self.get_plan_builder().with_context(contexts).build();
-        Self::from_logical_plan(lp, opt_state)
+        Self::from_logical_plan(lp, state)
     }
 
     /// Aggregate all the columns as their maximum values.
@@ -1082,9 +1440,9 @@ impl LazyFrame {
             .iter()
             .map(|e| e.clone().into())
             .collect::<Vec<_>>();
-        let opt_state = self.get_opt_state();
+        let state = self.get_state();
         let lp = self.get_plan_builder().explode(columns).build();
-        Self::from_logical_plan(lp, opt_state)
+        Self::from_logical_plan(lp, state)
     }
 
     /// Aggregate all the columns as the sum of their null value count.
@@ -1098,7 +1456,7 @@ impl LazyFrame {
         subset: Option<Vec<String>>,
         keep_strategy: UniqueKeepStrategy,
     ) -> LazyFrame {
-        let opt_state = self.get_opt_state();
+        let state = self.get_state();
         let options = DistinctOptions {
             subset: subset.map(Arc::new),
             maintain_order: true,
@@ -1106,7 +1464,7 @@ impl LazyFrame {
             ..Default::default()
         };
         let lp = self.get_plan_builder().distinct(options).build();
-        Self::from_logical_plan(lp, opt_state)
+        Self::from_logical_plan(lp, state)
     }
 
     /// Keep unique rows, do not maintain order
@@ -1115,7 +1473,7 @@ impl LazyFrame {
         subset: Option<Vec<String>>,
         keep_strategy: UniqueKeepStrategy,
     ) -> LazyFrame {
-        let opt_state = self.get_opt_state();
+        let state = self.get_state();
         let options = DistinctOptions {
             subset: subset.map(Arc::new),
             maintain_order: false,
@@ -1123,7 +1481,7 @@ impl LazyFrame {
             ..Default::default()
         };
         let lp = self.get_plan_builder().distinct(options).build();
-        Self::from_logical_plan(lp, opt_state)
+        Self::from_logical_plan(lp, state)
     }
 
     /// Drop null rows.
@@ -1146,9 +1504,9 @@ impl LazyFrame {
 
     /// Slice the DataFrame.
     pub fn slice(self, offset: i64, len: IdxSize) -> LazyFrame {
-        let opt_state = self.get_opt_state();
+        let state = self.get_state();
         let lp = self.get_plan_builder().slice(offset, len).build();
-        Self::from_logical_plan(lp, opt_state)
+        Self::from_logical_plan(lp, state)
     }
 
     /// Get the first row.
@@ -1169,9 +1527,9 @@ impl LazyFrame {
 
     /// Melt the DataFrame from wide to long format
     pub fn melt(self, args: MeltArgs) -> LazyFrame {
-        let opt_state = self.get_opt_state();
+        let state = self.get_state();
         let lp = self.get_plan_builder().melt(Arc::new(args)).build();
-        Self::from_logical_plan(lp, opt_state)
+        Self::from_logical_plan(lp, state)
     }
 
     /// Limit the DataFrame to the first `n` rows. Note if you don't want the rows to be scanned,
@@ -1197,7 +1555,7 @@ impl LazyFrame {
     where
         F: 'static + Fn(DataFrame) -> PolarsResult<DataFrame> + Send + Sync,
     {
-        let opt_state = self.get_opt_state();
+        let state = self.get_state();
         let lp = self
             .get_plan_builder()
             .map(
@@ -1207,7 +1565,7 @@ impl LazyFrame {
                 name.unwrap_or("ANONYMOUS UDF"),
             )
             .build();
-        Self::from_logical_plan(lp, opt_state)
+        Self::from_logical_plan(lp, state)
     }
 
     #[cfg(feature = "python")]
@@ -1218,18 +1576,18 @@ impl LazyFrame {
         schema: Option<SchemaRef>,
         validate_output: bool,
     ) -> LazyFrame {
-        let opt_state = self.get_opt_state();
+        let state = self.get_state();
         let lp = self
             .get_plan_builder()
             .map_python(function, optimizations, schema, validate_output)
             .build();
-        Self::from_logical_plan(lp, opt_state)
+        Self::from_logical_plan(lp, state)
     }
 
     pub(crate) fn map_private(self, function: FunctionNode) -> LazyFrame {
-        let opt_state = self.get_opt_state();
+        let state = self.get_state();
         let lp = self.get_plan_builder().map_private(function).build();
-        Self::from_logical_plan(lp, opt_state)
+        Self::from_logical_plan(lp, state)
     }
 
     /// Add a new column at index 0 that counts the rows.
@@ -1309,7 +1667,7 @@ impl LazyFrame {
 #[derive(Clone)]
 pub struct LazyGroupBy {
     pub logical_plan: LogicalPlan,
-    opt_state: OptState,
+    state: FrameState,
     keys: Vec<Expr>,
     maintain_order: bool,
     #[cfg(feature = "dynamic_groupby")]
@@ -1358,7 +1716,7 @@ impl LazyGroupBy {
         let lp = LogicalPlanBuilder::from(self.logical_plan)
             .groupby(self.keys, aggs, None, self.maintain_order)
             .build();
-        LazyFrame::from_logical_plan(lp, self.opt_state)
+        LazyFrame::from_logical_plan(lp, self.state)
     }
 
     /// Return first n rows of each group
@@ -1410,7 +1768,7 @@ impl LazyGroupBy {
             maintain_order: self.maintain_order,
             options: Arc::new(options),
         };
-        LazyFrame::from_logical_plan(lp, self.opt_state)
+        LazyFrame::from_logical_plan(lp, self.state)
     }
 }
 
@@ -1498,11 +1856,11 @@ impl JoinBuilder {
 
     /// Finish builder
     pub fn finish(self) -> LazyFrame {
-        let mut opt_state = self.lf.opt_state;
+        let mut state = self.lf.get_state();
         let other = self.other.expect("with not set");
 
         // if any of the nodes reads from files we must activate this this plan as well.
-        opt_state.file_caching |= other.opt_state.file_caching;
+        state.opt_state.file_caching |= other.opt_state.file_caching;
 
         let args = JoinArgs {
             how: self.how,
@@ -1527,6 +1885,27 @@ impl JoinBuilder {
                 .into(),
             )
             .build();
-        LazyFrame::from_logical_plan(lp, opt_state)
+        LazyFrame::from_logical_plan(lp, state)
     }
 }
+
+/// All subsets of `by` in the order of `by`, from the full set of keys down to the empty set.
+/// These are the grouping sets of [`LazyFrame::groupby_cube`].
+fn cube_sets(by: &[Expr]) -> PolarsResult<Vec<Vec<Expr>>> {
+    let n = by.len();
+    polars_ensure!(
+        n < 32,
+        ComputeError: "groupby error: CUBE supports at most 31 keys, got {}", n
+    );
+    let sets = (0..1usize << n)
+        .rev()
+        .map(|mask| {
+            by.iter()
+                .enumerate()
+                .filter(|(i, _)| mask & (1 << (n - 1 - i)) != 0)
+                .map(|(_, e)| e.clone())
+                .collect()
+        })
+        .collect();
+    Ok(sets)
+}
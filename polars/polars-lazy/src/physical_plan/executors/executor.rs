@@ -11,6 +11,25 @@ pub trait Executor: Send {
     fn execute(&mut self, cache: &mut ExecutionState) -> PolarsResult<DataFrame>;
 }
 
+/// Runs its input as the node of the logical plan it was created from, so that the
+/// profile can tell which node a timing belongs to.
+pub(crate) struct NodeExec {
+    pub(crate) node: Node,
+    pub(crate) input: Box<dyn Executor>,
+}
+
+impl Executor for NodeExec {
+    fn execute(&mut self, state: &mut ExecutionState) -> PolarsResult<DataFrame> {
+        if !state.has_node_timer() {
+            return self.input.execute(state);
+        }
+        let parent = state.node.replace(self.node);
+        let out = self.input.execute(state);
+        state.node = parent;
+        out
+    }
+}
+
 pub struct Dummy {}
 impl Executor for Dummy {
     fn execute(&mut self, _cache: &mut ExecutionState) -> PolarsResult<DataFrame> {
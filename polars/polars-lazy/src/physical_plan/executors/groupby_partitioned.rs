@@ -255,6 +255,8 @@ impl PartitionGroupByExec {
             &mut vec![],
             false,
             false,
+            &Default::default(),
+            None,
         )
         .unwrap();
 
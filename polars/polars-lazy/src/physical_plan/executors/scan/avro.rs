@@ -0,0 +1,54 @@
+use polars_io::avro::{AvroReader, BatchedAvroReader};
+
+use super::*;
+use crate::prelude::{AnonymousScan, AnonymousScanOptions, BatchedScan, LazyAvroReader};
+
+impl LazyAvroReader {
+    fn reader(&self, scan_opts: AnonymousScanOptions) -> PolarsResult<AvroReader<std::fs::File>> {
+        let file = std::fs::File::open(&self.path)?;
+        let columns = scan_opts
+            .with_columns
+            .map(|columns| columns.as_ref().clone());
+        Ok(AvroReader::new(file)
+            .with_columns(columns)
+            .with_n_rows(scan_opts.n_rows))
+    }
+}
+
+impl AnonymousScan for LazyAvroReader {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+    fn scan(&self, scan_opts: AnonymousScanOptions) -> PolarsResult<DataFrame> {
+        self.reader(scan_opts)?.set_rechunk(self.rechunk).finish()
+    }
+
+    fn schema(&self, _infer_schema_length: Option<usize>) -> PolarsResult<Schema> {
+        let file = std::fs::File::open(&self.path)?;
+        AvroReader::new(file).schema()
+    }
+    fn allows_projection_pushdown(&self) -> bool {
+        true
+    }
+    fn allows_streaming(&self) -> bool {
+        true
+    }
+
+    /// Avro is read one block at a time, so `chunk_size` is determined by the writer of the file.
+    fn scan_batched(
+        &self,
+        scan_opts: AnonymousScanOptions,
+        _chunk_size: usize,
+    ) -> PolarsResult<Box<dyn BatchedScan>> {
+        let reader = self.reader(scan_opts)?.batched()?;
+        Ok(Box::new(BatchedAvroScan(reader)))
+    }
+}
+
+struct BatchedAvroScan(BatchedAvroReader<std::fs::File>);
+
+impl BatchedScan for BatchedAvroScan {
+    fn next_batches(&mut self, n: usize) -> PolarsResult<Option<Vec<DataFrame>>> {
+        self.0.next_batches(n)
+    }
+}
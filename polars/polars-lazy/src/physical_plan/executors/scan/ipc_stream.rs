@@ -0,0 +1,29 @@
+use polars_io::ipc::IpcStreamReader;
+
+use super::*;
+use crate::prelude::{AnonymousScan, AnonymousScanOptions, LazyIpcStreamReader};
+
+impl AnonymousScan for LazyIpcStreamReader {
+    fn as_any(&self) -> &dyn std::any::Any {
+        self
+    }
+    fn scan(&self, scan_opts: AnonymousScanOptions) -> PolarsResult<DataFrame> {
+        let file = std::fs::File::open(&self.path)?;
+        let columns = scan_opts
+            .with_columns
+            .map(|columns| columns.as_ref().clone());
+        IpcStreamReader::new(file)
+            .with_columns(columns)
+            .with_n_rows(scan_opts.n_rows)
+            .set_rechunk(self.rechunk)
+            .finish()
+    }
+
+    fn schema(&self, _infer_schema_length: Option<usize>) -> PolarsResult<Schema> {
+        let file = std::fs::File::open(&self.path)?;
+        IpcStreamReader::new(file).schema()
+    }
+    fn allows_projection_pushdown(&self) -> bool {
+        true
+    }
+}
@@ -1,7 +1,11 @@
+#[cfg(feature = "avro")]
+mod avro;
 #[cfg(feature = "csv")]
 mod csv;
 #[cfg(feature = "ipc")]
 mod ipc;
+#[cfg(feature = "ipc_streaming")]
+mod ipc_stream;
 #[cfg(feature = "json")]
 mod ndjson;
 #[cfg(feature = "parquet")]
@@ -1,7 +1,7 @@
 use polars_core::error::to_compute_err;
 
 use super::*;
-use crate::prelude::{AnonymousScan, AnonymousScanOptions, LazyJsonLineReader};
+use crate::prelude::{AnonymousScan, AnonymousScanOptions, BatchedScan, LazyJsonLineReader};
 
 impl AnonymousScan for LazyJsonLineReader {
     fn as_any(&self) -> &dyn std::any::Any {
@@ -32,4 +32,29 @@ impl AnonymousScan for LazyJsonLineReader {
     fn allows_projection_pushdown(&self) -> bool {
         true
     }
+    fn allows_streaming(&self) -> bool {
+        true
+    }
+
+    fn scan_batched(
+        &self,
+        scan_opts: AnonymousScanOptions,
+        chunk_size: usize,
+    ) -> PolarsResult<Box<dyn BatchedScan>> {
+        let schema = scan_opts.output_schema.unwrap_or(scan_opts.schema);
+        let reader = JsonLineReader::from_path(&self.path)?
+            .with_schema(schema)
+            .with_chunk_size(Some(chunk_size))
+            .with_n_rows(scan_opts.n_rows)
+            .batched()?;
+        Ok(Box::new(BatchedJsonLineScan(reader)))
+    }
+}
+
+struct BatchedJsonLineScan(BatchedJsonLineReader);
+
+impl BatchedScan for BatchedJsonLineScan {
+    fn next_batches(&mut self, n: usize) -> PolarsResult<Option<Vec<DataFrame>>> {
+        self.0.next_batches(n)
+    }
 }
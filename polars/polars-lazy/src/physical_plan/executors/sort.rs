@@ -34,7 +34,7 @@ impl SortExec {
         df.sort_impl(
             by_columns,
             std::mem::take(&mut self.args.descending),
-            self.args.nulls_last,
+            std::mem::take(&mut self.args.nulls_last),
             self.args.maintain_order,
             self.args.slice,
             true,
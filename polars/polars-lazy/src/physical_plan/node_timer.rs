@@ -3,36 +3,56 @@ use std::time::Instant;
 
 use polars_core::prelude::*;
 use polars_core::utils::NoNull;
+use polars_utils::arena::Node;
 
 type StartInstant = Instant;
 type EndInstant = Instant;
 
 type Nodes = Vec<String>;
 type Ticks = Vec<(StartInstant, EndInstant)>;
+type Rows = Vec<IdxSize>;
+type PlanNodes = Vec<Option<Node>>;
 
 #[derive(Clone)]
 pub(super) struct NodeTimer {
     query_start: Instant,
-    data: Arc<Mutex<(Nodes, Ticks)>>,
+    data: Arc<Mutex<(Nodes, Ticks, Rows, PlanNodes)>>,
 }
 
 impl NodeTimer {
     pub(super) fn new() -> Self {
         Self {
             query_start: Instant::now(),
-            data: Arc::new(Mutex::new((Vec::with_capacity(16), Vec::with_capacity(16)))),
+            data: Arc::new(Mutex::new((
+                Vec::with_capacity(16),
+                Vec::with_capacity(16),
+                Vec::with_capacity(16),
+                Vec::with_capacity(16),
+            ))),
         }
     }
 
-    pub(super) fn store(&self, start: StartInstant, end: EndInstant, name: String) {
+    pub(super) fn store(
+        &self,
+        start: StartInstant,
+        end: EndInstant,
+        rows: usize,
+        name: String,
+        plan_node: Option<Node>,
+    ) {
         let mut data = self.data.lock().unwrap();
         let nodes = &mut data.0;
         nodes.push(name);
         let ticks = &mut data.1;
-        ticks.push((start, end))
+        ticks.push((start, end));
+        let n_rows = &mut data.2;
+        n_rows.push(rows as IdxSize);
+        data.3.push(plan_node);
     }
 
-    pub(super) fn finish(self) -> PolarsResult<DataFrame> {
+    /// The timings of the nodes. If `plan` is given, the number of rows they produced and the
+    /// first line of the description of their node in the logical plan are added.
+    pub(super) fn finish(self, plan: Option<&PlHashMap<Node, String>>) -> PolarsResult<DataFrame> {
         let mut data = self.data.lock().unwrap();
         let mut nodes = std::mem::take(&mut data.0);
         nodes.push("optimization".to_string());
@@ -57,10 +77,24 @@ impl NodeTimer {
         let mut end = end.into_inner();
         end.rename("end");
 
-        DataFrame::new_no_checks(vec![nodes_s, start.into_series(), end.into_series()]).sort(
-            vec!["start"],
-            vec![false],
-            false,
-        )
+        let mut columns = vec![nodes_s, start.into_series(), end.into_series()];
+        if let Some(plan) = plan {
+            // the optimization doesn't produce rows and isn't a node of the plan
+            let mut rows: IdxCa = std::mem::take(&mut data.2)
+                .into_iter()
+                .map(Some)
+                .chain(std::iter::once(None))
+                .collect();
+            rows.rename("rows");
+            columns.push(rows.into_series());
+            let mut plan_nodes: Utf8Chunked = std::mem::take(&mut data.3)
+                .into_iter()
+                .map(|node| node.and_then(|node| plan.get(&node).map(|s| s.as_str())))
+                .chain(std::iter::once(None))
+                .collect();
+            plan_nodes.rename("plan");
+            columns.push(plan_nodes.into_series());
+        }
+        DataFrame::new_no_checks(columns).sort(vec!["start"], vec![false], false)
     }
 }
@@ -143,6 +143,15 @@ pub fn create_physical_plan(
     root: Node,
     lp_arena: &mut Arena<ALogicalPlan>,
     expr_arena: &mut Arena<AExpr>,
+) -> PolarsResult<Box<dyn Executor>> {
+    let input = create_physical_plan_impl(root, lp_arena, expr_arena)?;
+    Ok(Box::new(executors::NodeExec { node: root, input }))
+}
+
+fn create_physical_plan_impl(
+    root: Node,
+    lp_arena: &mut Arena<ALogicalPlan>,
+    expr_arena: &mut Arena<AExpr>,
 ) -> PolarsResult<Box<dyn Executor>> {
     use ALogicalPlan::*;
 
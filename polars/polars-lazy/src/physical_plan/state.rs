@@ -10,6 +10,7 @@ use polars_core::frame::hash_join::ChunkJoinOptIds;
 use polars_core::prelude::*;
 #[cfg(any(feature = "parquet", feature = "csv", feature = "ipc"))]
 use polars_plan::logical_plan::FileFingerPrint;
+use polars_utils::arena::Node;
 
 #[cfg(any(feature = "ipc", feature = "parquet", feature = "csv"))]
 use super::file_cache::FileCache;
@@ -78,6 +79,8 @@ pub struct ExecutionState {
     pub(super) flags: AtomicU8,
     pub(super) ext_contexts: Arc<Vec<DataFrame>>,
     node_timer: Option<NodeTimer>,
+    // the node of the logical plan that is executed, if the nodes are timed
+    pub(super) node: Option<Node>,
 }
 
 impl ExecutionState {
@@ -89,11 +92,20 @@ impl ExecutionState {
         self.node_timer.is_some()
     }
 
-    pub(crate) fn finish_timer(self) -> PolarsResult<DataFrame> {
-        self.node_timer.unwrap().finish()
+    /// The timings of the nodes. If `plan` is given, the number of rows of the nodes and the
+    /// first line of the description of their node in the logical plan are added.
+    pub(crate) fn finish_timer(
+        self,
+        plan: Option<&PlHashMap<Node, String>>,
+    ) -> PolarsResult<DataFrame> {
+        self.node_timer.unwrap().finish(plan)
     }
 
-    pub(super) fn record<T, F: FnOnce() -> T>(&self, func: F, name: Cow<'static, str>) -> T {
+    pub(super) fn record<F: FnOnce() -> PolarsResult<DataFrame>>(
+        &self,
+        func: F,
+        name: Cow<'static, str>,
+    ) -> PolarsResult<DataFrame> {
         match &self.node_timer {
             None => func(),
             Some(timer) => {
@@ -101,7 +113,8 @@ impl ExecutionState {
                 let out = func();
                 let end = std::time::Instant::now();
 
-                timer.store(start, end, name.as_ref().to_string());
+                let rows = out.as_ref().map_or(0, |df| df.height());
+                timer.store(start, end, rows, name.as_ref().to_string(), self.node);
                 out
             }
         }
@@ -121,6 +134,7 @@ impl ExecutionState {
             flags: AtomicU8::new(self.flags.load(Ordering::Relaxed)),
             ext_contexts: self.ext_contexts.clone(),
             node_timer: self.node_timer.clone(),
+            node: self.node,
         }
     }
 
@@ -138,6 +152,7 @@ impl ExecutionState {
             flags: AtomicU8::new(self.flags.load(Ordering::Relaxed)),
             ext_contexts: self.ext_contexts.clone(),
             node_timer: self.node_timer.clone(),
+            node: self.node,
         }
     }
 
@@ -159,6 +174,7 @@ impl ExecutionState {
             flags: AtomicU8::new(StateFlags::init().as_u8()),
             ext_contexts: Default::default(),
             node_timer: None,
+            node: None,
         }
     }
 
@@ -179,6 +195,7 @@ impl ExecutionState {
             flags: AtomicU8::new(StateFlags::init().as_u8()),
             ext_contexts: Default::default(),
             node_timer: None,
+            node: None,
         }
     }
     pub(crate) fn set_schema(&self, schema: SchemaRef) {
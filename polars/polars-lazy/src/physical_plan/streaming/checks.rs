@@ -1,26 +1,35 @@
-use polars_core::prelude::{JoinArgs, JoinType};
+use polars_core::prelude::{DataType, JoinArgs, JoinType, Schema};
 use polars_plan::prelude::*;
+#[cfg(feature = "dynamic_groupby")]
+use polars_time::prelude::{Duration, StartBy};
 
-pub(super) fn is_streamable_sort(args: &SortArguments) -> bool {
-    // check if slice is positive or maintain order is true
-    match args {
-        SortArguments {
-            maintain_order: true,
-            ..
-        } => false,
-        SortArguments {
-            slice: Some((offset, _)),
-            ..
-        } => *offset >= 0,
-        SortArguments { slice: None, .. } => true,
+/// Explain why a sort is not streamable, `None` if it is.
+fn non_streamable_sort(
+    input: Node,
+    by_column: &[Node],
+    args: &SortArguments,
+    lp_arena: &Arena<ALogicalPlan>,
+    expr_arena: &Arena<AExpr>,
+) -> Option<String> {
+    // a stable sort orders equal keys by their position in the source
+    if args.maintain_order && !preserves_chunk_order(input, lp_arena) {
+        return Some(
+            "a sort with maintain_order is only streamed if its input keeps the order of the \
+            source"
+                .to_string(),
+        );
+    }
+    if args.slice.map_or(false, |(offset, _)| offset < 0) {
+        return Some("a sort with a negative slice offset is not streamable".to_string());
     }
+    if !all_column(by_column, expr_arena) {
+        return Some("a sort by expressions other than columns is not streamable".to_string());
+    }
+    None
 }
 
-pub(super) fn is_streamable(node: Node, expr_arena: &Arena<AExpr>, context: Context) -> bool {
-    // check whether leaf column is Col or Lit
-    let mut seen_column = false;
-    let mut seen_lit_range = false;
-    let all = expr_arena.iter(node).all(|(_, ae)| match ae {
+fn is_streamable_aexpr(ae: &AExpr, context: Context) -> bool {
+    match ae {
         AExpr::Function {
             function: FunctionExpr::SetSortedFlag(_),
             ..
@@ -33,22 +42,29 @@ pub(super) fn is_streamable(node: Node, expr_arena: &Arena<AExpr>, context: Cont
             ),
             Context::Aggregation => matches!(options.collect_groups, ApplyOptions::ApplyFlat),
         },
-        AExpr::Column(_) => {
-            seen_column = true;
-            true
-        }
-        AExpr::Ternary { .. }
+        AExpr::Column(_)
+        | AExpr::Ternary { .. }
         | AExpr::BinaryExpr { .. }
         | AExpr::Alias(_, _)
-        | AExpr::Cast { .. } => true,
-        AExpr::Literal(lv) => match lv {
-            LiteralValue::Series(_) | LiteralValue::Range { .. } => {
-                seen_lit_range = true;
-                true
-            }
-            _ => true,
-        },
+        | AExpr::Cast { .. }
+        | AExpr::Literal(_) => true,
         _ => false,
+    }
+}
+
+pub(super) fn is_streamable(node: Node, expr_arena: &Arena<AExpr>, context: Context) -> bool {
+    // check whether leaf column is Col or Lit
+    let mut seen_column = false;
+    let mut seen_lit_range = false;
+    let all = expr_arena.iter(node).all(|(_, ae)| {
+        match ae {
+            AExpr::Column(_) => seen_column = true,
+            AExpr::Literal(LiteralValue::Series(_) | LiteralValue::Range { .. }) => {
+                seen_lit_range = true
+            }
+            _ => {}
+        }
+        is_streamable_aexpr(ae, context)
     });
 
     if all {
@@ -68,19 +84,319 @@ pub(super) fn all_streamable(exprs: &[Node], expr_arena: &Arena<AExpr>, context:
         .all(|node| is_streamable(*node, expr_arena, context))
 }
 
+fn aexpr_name(ae: &AExpr) -> &'static str {
+    match ae {
+        AExpr::Explode(_) => "Explode",
+        AExpr::Alias(_, _) => "Alias",
+        AExpr::Column(_) => "Column",
+        AExpr::Literal(_) => "Literal",
+        AExpr::BinaryExpr { .. } => "BinaryExpr",
+        AExpr::Cast { .. } => "Cast",
+        AExpr::Sort { .. } => "Sort",
+        AExpr::Take { .. } => "Take",
+        AExpr::SortBy { .. } => "SortBy",
+        AExpr::Filter { .. } => "Filter",
+        AExpr::Agg(_) => "Agg",
+        AExpr::Ternary { .. } => "Ternary",
+        AExpr::AnonymousFunction { .. } => "AnonymousFunction",
+        AExpr::Function { .. } => "Function",
+        AExpr::Window { .. } => "Window",
+        AExpr::Wildcard => "Wildcard",
+        AExpr::Slice { .. } => "Slice",
+        AExpr::Count => "Count",
+        AExpr::Nth(_) => "Nth",
+        AExpr::Cache { .. } => "Cache",
+    }
+}
+
+/// Explain why an expression is not streamable, `None` if it is.
+fn non_streamable_expr(node: Node, expr_arena: &Arena<AExpr>, context: Context) -> Option<String> {
+    if is_streamable(node, expr_arena, context) {
+        return None;
+    }
+    let reason = match expr_arena
+        .iter(node)
+        .find(|(_, ae)| !is_streamable_aexpr(ae, context))
+    {
+        Some((_, AExpr::Function { function, .. })) => {
+            format!("AExpr::Function `{function}` not streamable")
+        }
+        Some((_, AExpr::AnonymousFunction { options, .. })) => {
+            format!(
+                "AExpr::AnonymousFunction `{}` not streamable",
+                options.fmt_str
+            )
+        }
+        Some((_, ae)) => format!("AExpr::{} not streamable", aexpr_name(ae)),
+        None => "a literal Series or range without a column is not streamable".to_string(),
+    };
+    Some(reason)
+}
+
+/// Explain why the streaming engine can't run `lp`, `None` if nothing about the node
+/// prevents it. The conversion to streaming nodes uses this to decide which nodes are
+/// streamed, so that the reason is the check that failed.
+pub(super) fn non_streamable_reason(
+    lp: &ALogicalPlan,
+    lp_arena: &Arena<ALogicalPlan>,
+    expr_arena: &Arena<AExpr>,
+) -> Option<String> {
+    use ALogicalPlan::*;
+    let first_non_streamable = |nodes: &[Node]| {
+        nodes
+            .iter()
+            .find_map(|node| non_streamable_expr(*node, expr_arena, Context::Default))
+    };
+    match lp {
+        Selection { predicate, .. } => {
+            non_streamable_expr(*predicate, expr_arena, Context::Default)
+        }
+        HStack { exprs, .. } => first_non_streamable(exprs.as_slice()),
+        Projection { expr, .. } => first_non_streamable(expr.as_slice()),
+        Slice { offset, .. } if *offset < 0 => {
+            Some("a slice with a negative offset is not streamable".to_string())
+        }
+        Sort {
+            input,
+            by_column,
+            args,
+        } => non_streamable_sort(*input, by_column, args, lp_arena, expr_arena),
+        MapFunction { function, .. } if !function.is_streamable() => {
+            Some(format!("function `{function}` not streamable"))
+        }
+        Scan { scan_type, .. } if !scan_type.streamable() => {
+            let name: &str = scan_type.into();
+            Some(format!("{name} scan not streamable"))
+        }
+        // anonymous scans, such as the NDJSON and Avro scans, are sources if they can be
+        // read in batches
+        AnonymousScan { function, .. } if !function.allows_streaming() => {
+            Some("scan can't be read in batches".to_string())
+        }
+        Join {
+            left_on,
+            right_on,
+            options,
+            ..
+        } => non_streamable_join(&options.args, left_on, right_on, expr_arena),
+        Union { options, .. } if options.slice.map_or(false, |(offset, _)| offset < 0) => {
+            Some("a union with a negative slice offset is not streamable".to_string())
+        }
+        Distinct { input, options }
+            if (options.maintain_order
+                || matches!(options.keep_strategy, UniqueKeepStrategy::None))
+                && !preserves_chunk_order(*input, lp_arena) =>
+        {
+            Some(
+                "unique with maintain_order or keep='none' is only streamed if its input keeps \
+                the order of the source"
+                    .to_string(),
+            )
+        }
+        Aggregate {
+            input,
+            keys,
+            aggs,
+            schema,
+            apply,
+            maintain_order,
+            options,
+        } => non_streamable_aggregate(
+            *input,
+            keys,
+            aggs,
+            schema,
+            apply.is_some(),
+            *maintain_order,
+            options,
+            lp_arena,
+            expr_arena,
+        ),
+        _ => None,
+    }
+}
+
+/// Explain why a groupby is not streamable, `None` if it is.
+#[allow(unused_variables, clippy::too_many_arguments)]
+fn non_streamable_aggregate(
+    input: Node,
+    keys: &[Node],
+    aggs: &[Node],
+    schema: &Schema,
+    has_apply: bool,
+    maintain_order: bool,
+    options: &GroupbyOptions,
+    lp_arena: &Arena<ALogicalPlan>,
+    expr_arena: &Arena<AExpr>,
+) -> Option<String> {
+    if has_apply {
+        return Some("a groupby with a custom apply is not streamable".to_string());
+    }
+    #[cfg(feature = "dynamic_groupby")]
+    if options.dynamic.is_some() || options.rolling.is_some() {
+        return if is_streamable_temporal_groupby(input, keys, aggs, options, lp_arena, expr_arena) {
+            None
+        } else {
+            Some(
+                "a groupby_dynamic or groupby_rolling is only streamed without `by` keys, \
+                with a fixed window step, a Datetime, Date or integer index, row-wise \
+                aggregations and an input that keeps its order"
+                    .to_string(),
+            )
+        };
+    }
+    if maintain_order {
+        return Some("a groupby with maintain_order is not streamable".to_string());
+    }
+
+    let input_schema = lp_arena.get(input).schema(lp_arena);
+    if let Some(agg) = aggs.iter().find(|node| {
+        !polars_pipe::pipeline::can_convert_to_hash_agg(**node, expr_arena, &input_schema)
+    }) {
+        return Some(format!(
+            "aggregation `{:?}` not streamable",
+            node_to_expr(*agg, expr_arena)
+        ));
+    }
+    let valid_key = keys.iter().all(|node| {
+        expr_arena
+            .get(*node)
+            .get_type(schema, Context::Default, expr_arena)
+            // ensure we don't groupby list
+            .map(|dt| !matches!(dt, DataType::List(_)))
+            .unwrap_or(false)
+    });
+    if !valid_key {
+        return Some("a groupby on a List key is not streamable".to_string());
+    }
+
+    #[cfg(feature = "dtype-categorical")]
+    let string_cache = polars_core::using_string_cache();
+    #[cfg(not(feature = "dtype-categorical"))]
+    let string_cache = true;
+    let valid_type = |dt: &DataType| match dt {
+        #[cfg(feature = "object")]
+        DataType::Object(_) => false,
+        #[cfg(feature = "dtype-categorical")]
+        DataType::Categorical(_) => string_cache,
+        _ => true,
+    };
+    if !schema.iter_dtypes().all(valid_type) {
+        return Some(
+            "Object columns or Categorical columns without a global string cache are not \
+            streamable"
+                .to_string(),
+        );
+    }
+    None
+}
+
 /// check if all expressions are a simple column projection
-pub(super) fn all_column(exprs: &[Node], expr_arena: &Arena<AExpr>) -> bool {
+fn all_column(exprs: &[Node], expr_arena: &Arena<AExpr>) -> bool {
     exprs
         .iter()
         .all(|node| matches!(expr_arena.get(*node), AExpr::Column(_)))
 }
 
-pub(super) fn streamable_join(args: &JoinArgs) -> bool {
+/// Explain why a join is not streamable, `None` if it is.
+fn non_streamable_join(
+    args: &JoinArgs,
+    left_on: &[Node],
+    right_on: &[Node],
+    expr_arena: &Arena<AExpr>,
+) -> Option<String> {
+    if args.validation.needs_checks() {
+        return Some("a join with validation is not streamable".to_string());
+    }
     let supported = match args.how {
         #[cfg(feature = "cross_join")]
         JoinType::Cross => true,
         JoinType::Inner | JoinType::Left => true,
+        // the join keys are coalesced by name
+        JoinType::Outer => all_column(left_on, expr_arena) && all_column(right_on, expr_arena),
+        #[cfg(feature = "semi_anti_join")]
+        JoinType::Semi | JoinType::Anti => true,
         _ => false,
     };
-    supported && !args.validation.needs_checks()
+    if supported {
+        None
+    } else if matches!(args.how, JoinType::Outer) {
+        Some("an outer join on expressions other than columns is not streamable".to_string())
+    } else {
+        Some(format!("{} join not streamable", args.how))
+    }
+}
+
+/// The operators between the source and the sink keep the chunks of the source in
+/// order, so that a sink can restore that order from the chunk indexes.
+fn preserves_chunk_order(mut node: Node, lp_arena: &Arena<ALogicalPlan>) -> bool {
+    use ALogicalPlan::*;
+    loop {
+        match lp_arena.get(node) {
+            Selection { input, .. } | HStack { input, .. } | Projection { input, .. } => {
+                node = *input
+            }
+            MapFunction { input, function } => match function {
+                FunctionNode::FastProjection { .. }
+                | FunctionNode::Rename { .. }
+                | FunctionNode::Drop { .. }
+                | FunctionNode::DropNulls { .. }
+                | FunctionNode::Rechunk => node = *input,
+                _ => return false,
+            },
+            // a join can produce many chunks per chunk and a union restarts the chunk indexes
+            Join { .. } | Union { .. } => return false,
+            // anything else ends the pipeline and is read in order
+            _ => return true,
+        }
+    }
+}
+
+/// Months don't have a fixed length and integer durations can't be combined with
+/// temporal ones.
+#[cfg(feature = "dynamic_groupby")]
+fn fixed_step(durations: &[&Duration]) -> bool {
+    let parsed_int = durations.iter().any(|d| d.parsed_int);
+    durations
+        .iter()
+        .all(|d| d.months() == 0 && (!parsed_int || d.parsed_int || d.is_zero()))
+}
+
+/// A `groupby_dynamic` or `groupby_rolling` without `by` keys is streamed if its
+/// windows advance with a fixed step and the chunks of the source arrive in order.
+#[cfg(feature = "dynamic_groupby")]
+fn is_streamable_temporal_groupby(
+    input: Node,
+    keys: &[Node],
+    aggs: &[Node],
+    options: &GroupbyOptions,
+    lp_arena: &Arena<ALogicalPlan>,
+    expr_arena: &Arena<AExpr>,
+) -> bool {
+    let index_column = match (&options.dynamic, &options.rolling) {
+        (Some(dynamic), _)
+            if !dynamic.every.negative()
+                && !dynamic.every.is_zero()
+                && matches!(dynamic.start_by, StartBy::WindowBound)
+                && fixed_step(&[&dynamic.every, &dynamic.period, &dynamic.offset]) =>
+        {
+            &dynamic.index_column
+        }
+        (None, Some(rolling)) if fixed_step(&[&rolling.period, &rolling.offset]) => {
+            &rolling.index_column
+        }
+        _ => return false,
+    };
+    let input_schema: &Schema = &lp_arena.get(input).schema(lp_arena);
+    let valid_index = matches!(
+        input_schema.get(index_column),
+        Some(DataType::Datetime(_, None) | DataType::Date | DataType::Int32 | DataType::Int64)
+    );
+
+    keys.is_empty()
+        && valid_index
+        && aggs
+            .iter()
+            .all(|node| polars_pipe::pipeline::can_convert_to_window_agg(*node, expr_arena))
+        && preserves_chunk_order(input, lp_arena)
 }
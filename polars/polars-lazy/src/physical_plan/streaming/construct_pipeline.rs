@@ -13,6 +13,7 @@ use polars_utils::IdxSize;
 
 use crate::physical_plan::planner::{create_physical_expr, ExpressionConversionState};
 use crate::physical_plan::state::ExecutionState;
+use crate::physical_plan::streaming::explain::StreamingExplain;
 use crate::physical_plan::streaming::tree::{PipelineNode, Tree};
 use crate::prelude::*;
 
@@ -88,9 +89,16 @@ pub(super) fn construct(
     lp_arena: &mut Arena<ALogicalPlan>,
     expr_arena: &mut Arena<AExpr>,
     fmt: bool,
+    config: &StreamingConfig,
+    explain: Option<&mut StreamingExplain>,
 ) -> PolarsResult<Option<Node>> {
     use ALogicalPlan::*;
 
+    // describe the nodes before the pipeline replaces them
+    let description = explain
+        .as_ref()
+        .map(|_| StreamingExplain::describe_pipeline(&tree, lp_arena, expr_arena));
+
     let mut pipelines = Vec::with_capacity(tree.len());
 
     let is_verbose = verbose();
@@ -176,6 +184,7 @@ pub(super) fn construct(
             to_physical_piped_expr,
             is_verbose,
             &mut sink_cache,
+            config,
         )?;
         pipelines.push((execution_id, pipeline));
     }
@@ -223,6 +232,9 @@ pub(super) fn construct(
     let pipeline_node = get_pipeline_node(lp_arena, most_left, schema, original_lp);
     lp_arena.replace(insertion_location, pipeline_node);
 
+    if let (Some(explain), Some(description)) = (explain, description) {
+        explain.add_pipeline(description)
+    }
     Ok(Some(final_sink))
 }
 
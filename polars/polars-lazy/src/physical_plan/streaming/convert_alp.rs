@@ -1,9 +1,12 @@
 use polars_core::error::PolarsResult;
 use polars_core::prelude::*;
 use polars_pipe::pipeline::swap_join_order;
+use polars_plan::frame::StreamingConfig;
 use polars_plan::prelude::*;
 
 use super::checks::*;
+use super::explain::StreamingExplain;
+use super::window::rewrite_windows;
 use crate::physical_plan::streaming::tree::*;
 
 // The index of the pipeline tree we are building at this moment
@@ -64,6 +67,7 @@ fn insert_slice(
     state.operators_sinks.push(PipelineNode::Sink(node));
 }
 
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn insert_streaming_nodes(
     root: Node,
     lp_arena: &mut Arena<ALogicalPlan>,
@@ -73,13 +77,24 @@ pub(crate) fn insert_streaming_nodes(
     // whether the full plan needs to be translated
     // to streaming
     allow_partial: bool,
+    config: &StreamingConfig,
+    // records the pipelines and the reasons other nodes are not streamed
+    mut explain: Option<&mut StreamingExplain>,
 ) -> PolarsResult<bool> {
+    // window expressions are streamed as an aggregation that is joined onto the input.
+    // We only do this if the pipelines are built to run, so that the plan that is
+    // formatted is the plan of the query.
+    if !fmt {
+        rewrite_windows(root, lp_arena, expr_arena);
+    }
+
     // this is needed to determine which side of the joins should be
     // traversed first
     set_estimated_row_counts(root, lp_arena, expr_arena, 0);
 
     scratch.clear();
 
+    let plan_root = root;
     // The pipelines always need to end in a SINK, we insert that here.
     // this allows us to split at joins/unions and share a sink
     let root = insert_file_sink(root, lp_arena);
@@ -136,20 +151,19 @@ pub(crate) fn insert_streaming_nodes(
         insert_file_sink_ptr = insert_file_sink_ptr.saturating_sub(1);
         state.execution_id = execution_id;
         execution_id += 1;
+        let reason = non_streamable_reason(lp_arena.get(root), lp_arena, expr_arena);
         match lp_arena.get(root) {
-            Selection { input, predicate }
-                if is_streamable(*predicate, expr_arena, Context::Default) =>
-            {
+            Selection { input, .. } if reason.is_none() => {
                 state.streamable = true;
                 state.operators_sinks.push(PipelineNode::Operator(root));
                 stack.push((*input, state, current_idx))
             }
-            HStack { input, exprs, .. } if all_streamable(exprs, expr_arena, Context::Default) => {
+            HStack { input, .. } if reason.is_none() => {
                 state.streamable = true;
                 state.operators_sinks.push(PipelineNode::Operator(root));
                 stack.push((*input, state, current_idx))
             }
-            Slice { input, offset, .. } if *offset >= 0 => {
+            Slice { input, .. } if reason.is_none() => {
                 state.streamable = true;
                 state.operators_sinks.push(PipelineNode::Sink(root));
                 stack.push((*input, state, current_idx))
@@ -159,18 +173,12 @@ pub(crate) fn insert_streaming_nodes(
                 state.operators_sinks.push(PipelineNode::Sink(root));
                 stack.push((*input, state, current_idx))
             }
-            Sort {
-                input,
-                by_column,
-                args,
-            } if is_streamable_sort(args) && all_column(by_column, expr_arena) => {
+            Sort { input, .. } if reason.is_none() => {
                 state.streamable = true;
                 state.operators_sinks.push(PipelineNode::Sink(root));
                 stack.push((*input, state, current_idx))
             }
-            Projection { input, expr, .. }
-                if all_streamable(expr, expr_arena, Context::Default) =>
-            {
+            Projection { input, .. } if reason.is_none() => {
                 state.streamable = true;
                 state.operators_sinks.push(PipelineNode::Operator(root));
                 stack.push((*input, state, current_idx))
@@ -184,28 +192,16 @@ pub(crate) fn insert_streaming_nodes(
                 stack.push((*input, state, current_idx))
             }
             // Streamable functions will be converted
-            lp @ MapFunction { input, function } => {
-                if function.is_streamable() {
-                    state.streamable = true;
-                    state.operators_sinks.push(PipelineNode::Operator(root));
-                    stack.push((*input, state, current_idx))
-                } else {
-                    process_non_streamable_node(
-                        &mut current_idx,
-                        &mut state,
-                        &mut stack,
-                        scratch,
-                        &mut pipeline_trees,
-                        lp,
-                        &mut insert_file_sink_ptr,
-                    )
-                }
+            MapFunction { input, .. } if reason.is_none() => {
+                state.streamable = true;
+                state.operators_sinks.push(PipelineNode::Operator(root));
+                stack.push((*input, state, current_idx))
             }
             Scan {
                 file_options: options,
                 scan_type,
                 ..
-            } if scan_type.streamable() => {
+            } if reason.is_none() => {
                 if state.streamable {
                     #[cfg(feature = "csv")]
                     if matches!(scan_type, FileScan::Csv { .. }) {
@@ -219,6 +215,12 @@ pub(crate) fn insert_streaming_nodes(
                     pipeline_trees[current_idx].push(state)
                 }
             }
+            AnonymousScan { .. } if reason.is_none() => {
+                if state.streamable {
+                    state.sources.push(root);
+                    pipeline_trees[current_idx].push(state)
+                }
+            }
             DataFrameScan { .. } => {
                 if state.streamable {
                     state.sources.push(root);
@@ -230,7 +232,7 @@ pub(crate) fn insert_streaming_nodes(
                 input_right,
                 options,
                 ..
-            } if streamable_join(&options.args) => {
+            } if reason.is_none() => {
                 let input_left = *input_left;
                 let input_right = *input_right;
                 state.streamable = true;
@@ -284,7 +286,7 @@ pub(crate) fn insert_streaming_nodes(
                         ..
                     },
                 ..
-            } if *offset >= 0 => {
+            } if reason.is_none() => {
                 insert_slice(root, *offset, *len as IdxSize, lp_arena, &mut state);
                 state.streamable = true;
                 let Union {inputs, ..} =  lp_arena.get(root) else {unreachable!()};
@@ -325,98 +327,23 @@ pub(crate) fn insert_streaming_nodes(
                     }
                 }
             }
-            Distinct { input, options }
-                if !options.maintain_order
-                    && !matches!(options.keep_strategy, UniqueKeepStrategy::None) =>
-            {
+            Distinct { input, .. } if reason.is_none() => {
                 state.streamable = true;
                 state.operators_sinks.push(PipelineNode::Sink(root));
                 stack.push((*input, state, current_idx))
             }
-            #[allow(unused_variables)]
-            lp @ Aggregate {
-                input,
-                keys,
-                aggs,
-                maintain_order: false,
-                apply: None,
-                schema,
-                options,
-                ..
-            } => {
-                #[cfg(feature = "dtype-categorical")]
-                let string_cache = polars_core::using_string_cache();
-                #[cfg(not(feature = "dtype-categorical"))]
-                let string_cache = true;
-
-                #[allow(unused_variables)]
-                fn allowed_dtype(dt: &DataType, string_cache: bool) -> bool {
-                    match dt {
-                        #[cfg(feature = "object")]
-                        DataType::Object(_) => false,
-                        #[cfg(feature = "dtype-categorical")]
-                        DataType::Categorical(_) => string_cache,
-                        _ => true,
-                    }
-                }
-                let input_schema = lp_arena.get(*input).schema(lp_arena);
-                #[allow(unused_mut)]
-                let mut can_stream = true;
-
-                #[cfg(feature = "dynamic_groupby")]
-                {
-                    if options.rolling.is_some() || options.dynamic.is_some() {
-                        can_stream = false
-                    }
-                }
-
-                let valid_agg = || {
-                    aggs.iter().all(|node| {
-                        polars_pipe::pipeline::can_convert_to_hash_agg(
-                            *node,
-                            expr_arena,
-                            &input_schema,
-                        )
-                    })
-                };
-
-                let valid_key = || {
-                    keys.iter().all(|node| {
-                        expr_arena
-                            .get(*node)
-                            .get_type(schema, Context::Default, expr_arena)
-                            // ensure we don't groupby list
-                            .map(|dt| !matches!(dt, DataType::List(_)))
-                            .unwrap_or(false)
-                    })
-                };
-
-                let valid_types = || {
-                    schema
-                        .iter_dtypes()
-                        .all(|dt| allowed_dtype(dt, string_cache))
-                };
-
-                if can_stream && valid_agg() && valid_key() && valid_types() {
-                    state.streamable = true;
-                    state.operators_sinks.push(PipelineNode::Sink(root));
-                    stack.push((*input, state, current_idx))
-                } else if allow_partial {
-                    process_non_streamable_node(
-                        &mut current_idx,
-                        &mut state,
-                        &mut stack,
-                        scratch,
-                        &mut pipeline_trees,
-                        lp,
-                        &mut insert_file_sink_ptr,
-                    )
-                } else {
-                    return Ok(false);
-                }
+            Aggregate { input, .. } if reason.is_none() => {
+                state.streamable = true;
+                state.operators_sinks.push(PipelineNode::Sink(root));
+                stack.push((*input, state, current_idx))
             }
             lp => {
                 if allow_partial {
+                    if let Some(explain) = explain.as_deref_mut() {
+                        let reason =
+                            reason.unwrap_or_else(|| format!("{} not streamable", lp.name()));
+                        explain.set_reason(root, reason);
+                    }
                     process_non_streamable_node(
                         &mut current_idx,
                         &mut state,
@@ -435,11 +362,22 @@ pub(crate) fn insert_streaming_nodes(
     let mut inserted = false;
     for tree in pipeline_trees {
         if is_valid_tree(&tree)
-            && super::construct_pipeline::construct(tree, lp_arena, expr_arena, fmt)?.is_some()
+            && super::construct_pipeline::construct(
+                tree,
+                lp_arena,
+                expr_arena,
+                fmt,
+                config,
+                explain.as_deref_mut(),
+            )?
+            .is_some()
         {
             inserted = true;
         }
     }
+    if let Some(explain) = explain {
+        explain.finish(plan_root, lp_arena, expr_arena);
+    }
 
     Ok(inserted)
 }
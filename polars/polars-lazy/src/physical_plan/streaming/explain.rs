@@ -0,0 +1,174 @@
+use std::fmt::{Display, Formatter};
+
+use polars_core::prelude::PlHashMap;
+use polars_plan::prelude::*;
+
+use super::tree::{Branch, PipelineNode};
+
+/// Records which nodes of the plan `insert_streaming_nodes` turned into pipelines
+/// and why the other nodes were not streamed. This is shown by `explain` if
+/// streaming is enabled.
+#[derive(Default)]
+pub(crate) struct StreamingExplain {
+    // the branches of every pipeline, every branch lists its nodes in execution order
+    pipelines: Vec<Vec<Vec<(&'static str, String)>>>,
+    // the reason why a node ended a pipeline
+    reasons: PlHashMap<Node, String>,
+    // the nodes that run in the default engine with the reason they do
+    not_streamed: Vec<(String, String)>,
+}
+
+impl StreamingExplain {
+    pub(super) fn set_reason(&mut self, node: Node, reason: String) {
+        self.reasons.insert(node, reason);
+    }
+
+    /// Describe the nodes of the branches of a pipeline. This must be called before
+    /// the pipeline replaces the nodes in the plan.
+    pub(super) fn describe_pipeline(
+        tree: &[Branch],
+        lp_arena: &Arena<ALogicalPlan>,
+        expr_arena: &Arena<AExpr>,
+    ) -> Vec<Vec<(&'static str, String)>> {
+        tree.iter()
+            .map(|branch| describe_branch(branch, lp_arena, expr_arena))
+            .collect()
+    }
+
+    pub(super) fn add_pipeline(&mut self, branches: Vec<Vec<(&'static str, String)>>) {
+        self.pipelines.push(branches)
+    }
+
+    /// Collect the nodes of the final plan that are not replaced by a pipeline.
+    pub(super) fn finish(
+        &mut self,
+        root: Node,
+        lp_arena: &Arena<ALogicalPlan>,
+        expr_arena: &Arena<AExpr>,
+    ) {
+        let mut stack = vec![root];
+        while let Some(node) = stack.pop() {
+            let lp = lp_arena.get(node);
+            if let ALogicalPlan::MapFunction {
+                function: FunctionNode::Pipeline { .. },
+                ..
+            } = lp
+            {
+                continue;
+            }
+            let reason = self
+                .reasons
+                .remove(&node)
+                .unwrap_or_else(|| "its input is not streamed".to_string());
+            self.not_streamed
+                .push((describe_node(node, lp_arena, expr_arena), reason));
+            // push the inputs in reverse, so that we describe the plan top-down and left to right
+            let offset = stack.len();
+            lp.copy_inputs(&mut stack);
+            stack[offset..].reverse();
+        }
+    }
+}
+
+fn describe_branch(
+    branch: &Branch,
+    lp_arena: &Arena<ALogicalPlan>,
+    expr_arena: &Arena<AExpr>,
+) -> Vec<(&'static str, String)> {
+    let sources = branch
+        .sources
+        .iter()
+        .map(|node| ("SOURCE", describe_node(*node, lp_arena, expr_arena)));
+    // the operators and sinks are stored in the order of discovery, that is from the root
+    let operators_sinks = branch.operators_sinks.iter().rev().map(|pl_node| {
+        let kind = match pl_node {
+            PipelineNode::Sink(_) => "SINK",
+            PipelineNode::Operator(_) | PipelineNode::Union(_) => "OPERATOR",
+            PipelineNode::RhsJoin(_) => "JOIN PROBE",
+        };
+        (kind, describe_node(pl_node.node(), lp_arena, expr_arena))
+    });
+    sources.chain(operators_sinks).collect()
+}
+
+/// A single line description of a node, without its inputs.
+fn describe_node(node: Node, lp_arena: &Arena<ALogicalPlan>, expr_arena: &Arena<AExpr>) -> String {
+    let exprs = |nodes: &[Node]| {
+        nodes
+            .iter()
+            .map(|node| node_to_expr(*node, expr_arena))
+            .collect::<Vec<_>>()
+    };
+
+    use ALogicalPlan::*;
+    match lp_arena.get(node) {
+        Scan {
+            path, scan_type, ..
+        } => {
+            let name: &str = scan_type.into();
+            format!("{} SCAN {}", name.to_uppercase(), path.display())
+        }
+        AnonymousScan { options, .. } => format!("{} SCAN", options.fmt_str),
+        DataFrameScan { schema, .. } => {
+            format!("DF {:?}", schema.iter_names().take(4).collect::<Vec<_>>())
+        }
+        Selection { predicate, .. } => format!("FILTER {:?}", node_to_expr(*predicate, expr_arena)),
+        Projection { expr, .. } => format!("SELECT {:?}", exprs(expr.default_exprs())),
+        HStack { exprs: e, .. } => format!("WITH_COLUMNS {:?}", exprs(e.default_exprs())),
+        Sort { by_column, .. } => format!("SORT BY {:?}", exprs(by_column)),
+        Aggregate { keys, aggs, .. } => {
+            format!("AGGREGATE {:?} BY {:?}", exprs(aggs), exprs(keys))
+        }
+        Join {
+            left_on,
+            right_on,
+            options,
+            ..
+        } => format!(
+            "{} JOIN LEFT ON {:?} RIGHT ON {:?}",
+            options.args.how,
+            exprs(left_on),
+            exprs(right_on)
+        ),
+        Distinct { options, .. } => format!("UNIQUE BY {:?}", options.subset),
+        Slice { offset, len, .. } => format!("SLICE[offset: {offset}, len: {len}]"),
+        MapFunction { function, .. } => format!("{function}"),
+        Union { inputs, .. } => format!("UNION OF {} PLANS", inputs.len()),
+        FileSink { payload, .. } => match payload.file_type {
+            FileType::Memory => "IN MEMORY".to_string(),
+            _ => format!("FILE_SINK {}", payload.path.display()),
+        },
+        lp => lp.name().to_uppercase(),
+    }
+}
+
+impl Display for StreamingExplain {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "STREAMING:")?;
+        if self.pipelines.is_empty() {
+            write!(f, "\n  no part of the query is streamed")?;
+        }
+        for (i, branches) in self.pipelines.iter().enumerate() {
+            write!(f, "\n  PIPELINE {i}")?;
+            for (j, nodes) in branches.iter().enumerate() {
+                // a single branch needs no header
+                let indent = if branches.len() > 1 {
+                    write!(f, "\n    BRANCH {j}")?;
+                    6
+                } else {
+                    4
+                };
+                for (kind, description) in nodes {
+                    write!(f, "\n{:indent$}{kind:<10} {description}", "")?;
+                }
+            }
+        }
+        if !self.not_streamed.is_empty() {
+            write!(f, "\n  NOT STREAMED")?;
+            for (description, reason) in &self.not_streamed {
+                write!(f, "\n    {description}: {reason}")?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,6 +1,9 @@
 mod checks;
 mod construct_pipeline;
 mod convert_alp;
+mod explain;
 mod tree;
+mod window;
 
 pub(crate) use convert_alp::insert_streaming_nodes;
+pub(crate) use explain::StreamingExplain;
@@ -0,0 +1,240 @@
+use std::sync::Arc;
+
+use polars_core::prelude::*;
+use polars_pipe::pipeline::can_convert_to_hash_agg;
+use polars_plan::prelude::*;
+
+use super::checks::{all_streamable, is_streamable};
+
+const WINDOW_PREFIX: &str = "__POLARS_WINDOW_";
+
+/// Rewrite the `over()` expressions in projections to an aggregation that is
+/// joined back onto the input. Both the aggregation and the join can run in the
+/// streaming engine, whereas a window expression needs all data in memory.
+///
+/// The input is read twice, so we only do this if it is a scan that is cheap to read
+/// again and if the projection is streamable after the rewrite.
+pub(super) fn rewrite_windows(
+    root: Node,
+    lp_arena: &mut Arena<ALogicalPlan>,
+    expr_arena: &mut Arena<AExpr>,
+) {
+    let mut stack = vec![root];
+    let mut projections = vec![];
+    while let Some(node) = stack.pop() {
+        let lp = lp_arena.get(node);
+        if matches!(
+            lp,
+            ALogicalPlan::HStack { .. } | ALogicalPlan::Projection { .. }
+        ) {
+            projections.push(node)
+        }
+        lp.copy_inputs(&mut stack);
+    }
+
+    let mut window_count = 0;
+    for node in projections {
+        rewrite_projection(node, lp_arena, expr_arena, &mut window_count);
+    }
+}
+
+/// The input is a streamable chain of row-wise operations on a scan.
+fn is_cheap_to_rescan(
+    mut node: Node,
+    lp_arena: &Arena<ALogicalPlan>,
+    expr_arena: &Arena<AExpr>,
+) -> bool {
+    use ALogicalPlan::*;
+    loop {
+        match lp_arena.get(node) {
+            Selection { input, predicate }
+                if is_streamable(*predicate, expr_arena, Context::Default) =>
+            {
+                node = *input
+            }
+            HStack { input, exprs, .. } if all_streamable(exprs, expr_arena, Context::Default) => {
+                node = *input
+            }
+            Projection { input, expr, .. }
+                if all_streamable(expr, expr_arena, Context::Default) =>
+            {
+                node = *input
+            }
+            MapFunction { input, function } if function.is_streamable() => node = *input,
+            Scan { .. } | DataFrameScan { .. } | AnonymousScan { .. } => return true,
+            _ => return false,
+        }
+    }
+}
+
+/// Returns the partition columns if the window is an aggregation over columns
+/// that is mapped back to the rows of its group.
+fn window_partition(
+    node: Node,
+    expr_arena: &Arena<AExpr>,
+    input_schema: &Schema,
+) -> Option<(Vec<Arc<str>>, Node)> {
+    match expr_arena.get(node) {
+        AExpr::Window {
+            function,
+            partition_by,
+            order_by: None,
+            options:
+                WindowOptions {
+                    mapping: WindowMapping::GroupsToRows,
+                },
+        } => {
+            if has_aexpr_window(*function, expr_arena)
+                || !can_convert_to_hash_agg(*function, expr_arena, input_schema)
+            {
+                return None;
+            }
+            let keys = partition_by
+                .iter()
+                .map(|node| match expr_arena.get(*node) {
+                    AExpr::Column(name) => Some(name.clone()),
+                    _ => None,
+                })
+                .collect::<Option<Vec<_>>>()?;
+            Some((keys, *function))
+        }
+        _ => None,
+    }
+}
+
+fn rewrite_projection(
+    node: Node,
+    lp_arena: &mut Arena<ALogicalPlan>,
+    expr_arena: &mut Arena<AExpr>,
+    window_count: &mut usize,
+) {
+    let (input, exprs) = match lp_arena.get(node) {
+        ALogicalPlan::HStack { input, exprs, .. }
+        | ALogicalPlan::Projection {
+            input, expr: exprs, ..
+        } if exprs.cse_exprs().is_empty() => (*input, exprs.default_exprs().to_vec()),
+        _ => return,
+    };
+    if !is_cheap_to_rescan(input, lp_arena, expr_arena) {
+        return;
+    }
+    let input_schema = lp_arena.get(input).schema(lp_arena).into_owned();
+
+    let windows = exprs
+        .iter()
+        .flat_map(|e| {
+            expr_arena
+                .iter(*e)
+                .filter(|(_, ae)| matches!(ae, AExpr::Window { .. }))
+                .map(|(node, _)| node)
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    // the aggregations per set of partition columns
+    let mut partitions: Vec<(Vec<Arc<str>>, Vec<Node>)> = vec![];
+    // the nodes we swapped, so that we can restore them
+    let mut swapped = vec![];
+    for window in windows {
+        let Some((keys, function)) = window_partition(window, expr_arena, &input_schema) else {
+            continue
+        };
+        let Ok(field) =
+            expr_arena
+                .get(window)
+                .to_field(&input_schema, Context::Default, expr_arena)
+        else {
+            continue
+        };
+        let name: Arc<str> = Arc::from(format!("{WINDOW_PREFIX}{window_count}"));
+        *window_count += 1;
+
+        // the window is replaced by the aggregated column that is joined onto the input
+        let column = expr_arena.add(AExpr::Column(name.clone()));
+        let aggregated = expr_arena.add(AExpr::Alias(column, Arc::from(field.name.as_str())));
+        expr_arena.swap(window, aggregated);
+        swapped.push((window, aggregated));
+
+        let agg = expr_arena.add(AExpr::Alias(function, name));
+        match partitions.iter_mut().find(|(k, _)| *k == keys) {
+            Some((_, aggs)) => aggs.push(agg),
+            None => partitions.push((keys, vec![agg])),
+        }
+    }
+    if partitions.is_empty() {
+        return;
+    }
+    if !all_streamable(&exprs, expr_arena, Context::Default) {
+        for (window, aggregated) in swapped {
+            expr_arena.swap(window, aggregated);
+        }
+        return;
+    }
+
+    let mut joined = input;
+    for (keys, aggs) in partitions {
+        let mut columns = |keys: &[Arc<str>]| {
+            keys.iter()
+                .map(|name| expr_arena.add(AExpr::Column(name.clone())))
+                .collect::<Vec<_>>()
+        };
+        let by = columns(&keys);
+        let left_on = columns(&keys);
+        let right_on = columns(&keys);
+
+        let lp = ALogicalPlanBuilder::new(input, expr_arena, lp_arena)
+            .groupby(by, aggs, None, false, Default::default())
+            .build();
+        let aggregated = lp_arena.add(lp);
+        // a left join streams the input and builds the aggregated table, so the rows
+        // keep the order of the input, as `over()` requires
+        let options = JoinOptions {
+            args: JoinArgs::new(JoinType::Left),
+            ..Default::default()
+        };
+        let lp = ALogicalPlanBuilder::new(joined, expr_arena, lp_arena)
+            .join(aggregated, left_on, right_on, Arc::new(options))
+            .build();
+        joined = lp_arena.add(lp);
+    }
+
+    match lp_arena.take(node) {
+        ALogicalPlan::Projection { expr, schema, .. } => {
+            let lp = ALogicalPlan::Projection {
+                input: joined,
+                expr,
+                schema,
+            };
+            lp_arena.replace(node, lp);
+        }
+        ALogicalPlan::HStack { exprs, schema, .. } => {
+            // the hstack also outputs the joined columns, so we project them away
+            let joined_schema = lp_arena.get(joined).schema(lp_arena).into_owned();
+            let mut new_schema = (*joined_schema).clone();
+            for e in exprs.default_exprs() {
+                let field = expr_arena
+                    .get(*e)
+                    .to_field(&joined_schema, Context::Default, expr_arena)
+                    .unwrap();
+                new_schema.with_column(field.name, field.dtype);
+            }
+            let hstack = lp_arena.add(ALogicalPlan::HStack {
+                input: joined,
+                exprs,
+                schema: Arc::new(new_schema),
+            });
+            let columns = schema
+                .iter_names()
+                .map(|name| Arc::from(name.as_str()))
+                .collect();
+            lp_arena.replace(
+                node,
+                ALogicalPlan::MapFunction {
+                    input: hstack,
+                    function: FunctionNode::FastProjection { columns },
+                },
+            );
+        }
+        _ => unreachable!(),
+    }
+}
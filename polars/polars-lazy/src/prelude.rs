@@ -1,12 +1,19 @@
 pub(crate) use polars_ops::prelude::*;
 pub use polars_plan::logical_plan::{
-    AnonymousScan, AnonymousScanOptions, Literal, LiteralValue, LogicalPlan, Null, NULL,
+    AnonymousScan, AnonymousScanOptions, BatchedScan, Literal, LiteralValue, LogicalPlan, Null,
+    NULL,
 };
+#[cfg(feature = "csv")]
+pub use polars_plan::prelude::CsvWriterOptions;
 #[cfg(feature = "ipc")]
 pub use polars_plan::prelude::IpcWriterOptions;
-#[cfg(feature = "parquet")]
-pub use polars_plan::prelude::ParquetWriteOptions;
+#[cfg(feature = "json")]
+pub use polars_plan::prelude::JsonWriterOptions;
 pub(crate) use polars_plan::prelude::*;
+#[cfg(feature = "parquet")]
+pub use polars_plan::prelude::{ParquetWriteOptions, PartitionedParquetWriteOptions};
+#[cfg(feature = "temporal")]
+pub use polars_time::TruncateOptions;
 #[cfg(feature = "rolling_window")]
 pub use polars_time::{prelude::RollingOptions, Duration};
 #[cfg(feature = "dynamic_groupby")]
@@ -567,3 +567,33 @@ fn test_take_in_groups() -> PolarsResult<()> {
     );
     Ok(())
 }
+
+#[test]
+fn test_groupby_rollup_cube() -> PolarsResult<()> {
+    let df = df![
+        "region" => ["eu", "eu", "us"],
+        "product" => ["a", "b", "a"],
+        "sales" => [1i64, 2, 4],
+    ]?;
+
+    let out = df
+        .clone()
+        .lazy()
+        .groupby_rollup([col("region"), col("product")], &[col("sales").sum()])?
+        .sort_by_exprs([col("region"), col("product")], [false, false], true, false)
+        .collect()?;
+    let expected = df![
+        "region" => [Some("eu"), Some("eu"), Some("eu"), Some("us"), Some("us"), None],
+        "product" => [Some("a"), Some("b"), None, Some("a"), None, None],
+        "sales" => [1i64, 2, 3, 4, 4, 7],
+    ]?;
+    assert!(out.frame_equal_missing(&expected));
+
+    let out = df
+        .lazy()
+        .groupby_cube([col("region"), col("product")], &[col("sales").sum()])?
+        .collect()?;
+    // 3 groups, 2 region subtotals, 2 product subtotals and the grand total
+    assert_eq!(out.shape(), (8, 3));
+    Ok(())
+}
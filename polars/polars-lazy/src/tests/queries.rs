@@ -37,6 +37,35 @@ fn test_lazy_exec() {
     assert!(check.all())
 }
 
+#[test]
+fn test_lazy_profile_with_row_counts() -> PolarsResult<()> {
+    let df = df! {
+        "a" => [1, 2, 1, 3, 2],
+        "b" => [1, 2, 3, 4, 5],
+    }?;
+    let (out, profile) = df
+        .lazy()
+        .groupby_stable([col("a")])
+        .agg([col("b").sum()])
+        .profile_with_row_counts()?;
+    assert_eq!(out.height(), 3);
+
+    assert_eq!(
+        profile.get_column_names(),
+        &["node", "start", "end", "rows", "plan"]
+    );
+    let nodes = profile.column("node")?.utf8()?;
+    let rows = profile.column("rows")?.idx()?;
+    let plan = profile.column("plan")?.utf8()?;
+    assert_eq!(nodes.get(0), Some("optimization"));
+    assert_eq!(rows.get(0), None);
+    assert_eq!(plan.get(0), None);
+    assert!(nodes.get(1).unwrap().starts_with("groupby"));
+    assert_eq!(rows.get(1), Some(3));
+    assert_eq!(plan.get(1), Some("AGGREGATE"));
+    Ok(())
+}
+
 #[test]
 fn test_lazy_alias() {
     let df = get_df();
@@ -1906,3 +1935,34 @@ fn test_sort_maintain_order_true() -> PolarsResult<()> {
     ]?));
     Ok(())
 }
+
+#[test]
+fn test_sort_nulls_last_length_mismatch() -> PolarsResult<()> {
+    let q = df![
+        "A" => [Some(1), None, Some(1)],
+        "B" => [Some(2), Some(1), None],
+        "C" => [1, 2, 3],
+    ]?
+    .lazy();
+
+    let empty = q
+        .clone()
+        .sort_by_exprs([col("A")], [false], Vec::<bool>::new(), false)
+        .collect();
+    assert!(empty.is_err());
+    let broadcast = q
+        .clone()
+        .sort_by_exprs([col("A"), col("B")], [false], vec![true], false)
+        .collect();
+    assert!(broadcast.is_ok());
+    let mismatch = q
+        .sort_by_exprs(
+            [col("A"), col("B"), col("C")],
+            [false],
+            vec![true, false],
+            false,
+        )
+        .collect();
+    assert!(mismatch.is_err());
+    Ok(())
+}
@@ -128,6 +128,41 @@ fn test_streaming_first_sum() -> PolarsResult<()> {
     Ok(())
 }
 
+#[test]
+fn test_streaming_groupby_std_var_quantile_n_unique() -> PolarsResult<()> {
+    let q = get_csv_file();
+
+    let q = q
+        .groupby([col("sugars_g")])
+        .agg([
+            col("calories").std(1).alias("calories_std"),
+            col("calories").var(0).alias("calories_var"),
+            col("fats_g").median().alias("fats_median"),
+            col("calories")
+                .quantile(lit(0.25), QuantileInterpolOptions::Nearest)
+                .alias("calories_q25"),
+            col("category").n_unique().alias("category_n_unique"),
+        ])
+        .sort("sugars_g", Default::default());
+
+    assert_streaming_with_default(q, true, false);
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "approx_unique")]
+fn test_streaming_groupby_approx_unique() -> PolarsResult<()> {
+    let q = get_csv_file();
+
+    let q = q
+        .groupby([col("sugars_g")])
+        .agg([col("calories").approx_unique()])
+        .sort("sugars_g", Default::default());
+
+    assert_streaming_with_default(q, true, false);
+    Ok(())
+}
+
 #[test]
 fn test_streaming_unique() -> PolarsResult<()> {
     let q = get_csv_file();
@@ -141,6 +176,43 @@ fn test_streaming_unique() -> PolarsResult<()> {
     Ok(())
 }
 
+#[test]
+fn test_streaming_unique_maintain_order() -> PolarsResult<()> {
+    for keep in [
+        UniqueKeepStrategy::First,
+        UniqueKeepStrategy::Last,
+        UniqueKeepStrategy::None,
+    ] {
+        let q = get_csv_file().unique_stable(Some(vec!["category".to_string()]), keep);
+        assert_streaming_with_default(q, true, false);
+
+        let q = get_csv_file()
+            .select([col("category"), col("sugars_g")])
+            .unique_stable(None, keep);
+        assert_streaming_with_default(q, true, false);
+    }
+
+    // keep none doesn't need to maintain the order, but it does
+    let q = get_csv_file().unique(Some(vec!["sugars_g".to_string()]), UniqueKeepStrategy::None);
+    assert_streaming_with_default(q, true, false);
+    Ok(())
+}
+
+#[test]
+fn test_streaming_stable_sort() -> PolarsResult<()> {
+    let q = get_csv_file().sort_by_exprs([col("category")], [false], false, true);
+    assert_streaming_with_default(q, true, false);
+
+    let q = get_csv_file().sort_by_exprs(
+        [col("category"), col("sugars_g")],
+        [true, false],
+        false,
+        true,
+    );
+    assert_streaming_with_default(q, true, false);
+    Ok(())
+}
+
 #[test]
 fn test_streaming_aggregate_slice() -> PolarsResult<()> {
     let q = get_parquet_file();
@@ -252,6 +324,135 @@ fn test_streaming_left_join() -> PolarsResult<()> {
     Ok(())
 }
 
+#[test]
+fn test_streaming_outer_join() -> PolarsResult<()> {
+    let lf_left = df![
+           "a"=> [0, 0, 0, 3, 0, 1, 3, 3, 3, 1, 4, 4, 2, 1, 1, 3, 1, 4, 2, 2],
+    "b"=> [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19]
+       ]?
+    .lazy();
+
+    let lf_right = df![
+           "a"=> [10, 18, 13, 9, 1, 13, 14, 12, 15, 11],
+    "b"=> [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
+       ]?
+    .lazy();
+
+    // the unmatched rows of the build table are emitted last,
+    // so we sort to get a deterministic order
+    let by = [col("a"), col("b"), col("b_right")];
+    let q = lf_left
+        .clone()
+        .outer_join(lf_right.clone(), col("a"), col("a"))
+        .sort_by_exprs(by.clone(), [false, false, false], false, false);
+    assert_streaming_with_default(q, true, false);
+
+    // the right table is the larger one, so now the left table is the build table
+    let q = lf_right
+        .outer_join(lf_left, col("a"), col("a"))
+        .sort_by_exprs(by, [false, false, false], false, false);
+    assert_streaming_with_default(q, true, false);
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "semi_anti_join")]
+fn test_streaming_semi_anti_join() -> PolarsResult<()> {
+    let lf_left = df![
+           "a"=> [0, 0, 0, 3, 0, 1, 3, 3, 3, 1, 4, 4, 2, 1, 1, 3, 1, 4, 2, 2],
+    "b"=> [0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19]
+       ]?
+    .lazy();
+
+    let lf_right = df![
+           "a"=> [10, 18, 13, 9, 1, 13, 14, 12, 15, 3],
+    "b"=> [0, 1, 2, 3, 4, 5, 6, 7, 8, 9]
+       ]?
+    .lazy();
+
+    for how in [JoinType::Semi, JoinType::Anti] {
+        let q = lf_left
+            .clone()
+            .join_builder()
+            .with(lf_right.clone())
+            .left_on([col("a")])
+            .right_on([col("a")])
+            .how(how)
+            .finish();
+        assert_streaming_with_default(q, true, false);
+    }
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "semi_anti_join")]
+fn test_streaming_ooc_join() -> PolarsResult<()> {
+    let n = 1000;
+    let lf_left = df![
+        "a" => (0..n).map(|i| i % 37).collect::<Vec<i32>>(),
+        "b" => (0..n).collect::<Vec<i32>>(),
+    ]?
+    .lazy();
+    let lf_right = df![
+        "a" => (0..n / 2).map(|i| i % 53).collect::<Vec<i32>>(),
+        "c" => (0..n / 2).collect::<Vec<i32>>(),
+    ]?
+    .lazy();
+
+    let mut spill_dir = std::env::temp_dir();
+    spill_dir.push(format!("polars_test_ooc_join_{}", std::process::id()));
+    // a budget of zero spills the build table to disk right away
+    let config = StreamingConfig::default()
+        .with_spill_dir(&spill_dir)
+        .with_memory_budget(0);
+
+    for how in [
+        JoinType::Inner,
+        JoinType::Left,
+        JoinType::Outer,
+        JoinType::Semi,
+        JoinType::Anti,
+    ] {
+        // the partitions are joined one by one, so we sort to get a deterministic order
+        let by = match how {
+            JoinType::Semi | JoinType::Anti => vec![col("a"), col("b")],
+            _ => vec![col("a"), col("b"), col("c")],
+        };
+        let descending = vec![false; by.len()];
+        let q = lf_left
+            .clone()
+            .join_builder()
+            .with(lf_right.clone())
+            .left_on([col("a")])
+            .right_on([col("a")])
+            .how(how.clone())
+            .finish()
+            .sort_by_exprs(by, descending, false, false);
+
+        let expected = q.clone().collect()?;
+        let out = q
+            .with_streaming(true)
+            .with_streaming_config(config.clone())
+            .collect()?;
+        assert!(out.frame_equal_missing(&expected), "{how:?}");
+    }
+    let _ = std::fs::remove_dir_all(&spill_dir);
+    Ok(())
+}
+
+#[test]
+fn test_is_streamable() -> PolarsResult<()> {
+    let q = get_csv_file();
+    assert!(q
+        .clone()
+        .filter(col("calories").gt(lit(100)))
+        .select([col("category"), col("fats_g")])
+        .is_streamable()?);
+    // reversing a column needs all of its values
+    assert!(!q.select([col("category").reverse()]).is_streamable()?);
+    Ok(())
+}
+
 #[test]
 #[cfg(feature = "cross_join")]
 fn test_streaming_slice() -> PolarsResult<()> {
@@ -287,11 +488,12 @@ fn test_streaming_partial() -> PolarsResult<()> {
 
     // we add a join that is not supported streaming (for now)
     // so we can test if the partial query is executed without panics
+    // an outer join on an expression is not streamable as the keys cannot be coalesced
     let q = q
         .join_builder()
         .with(lf_left.clone())
-        .left_on([col("a")])
-        .right_on([col("a")])
+        .left_on([col("a") + lit(0)])
+        .right_on([col("a") + lit(0)])
         .suffix("_foo")
         .how(JoinType::Outer)
         .finish();
@@ -388,3 +590,318 @@ fn test_sort_maintain_order_streaming() -> PolarsResult<()> {
     ]?));
     Ok(())
 }
+
+#[test]
+fn test_sort_nulls_last_per_column_streaming() -> PolarsResult<()> {
+    let q = df![
+        "A" => [Some(1), None, Some(1), Some(2), None],
+        "B" => [Some(1), Some(2), None, Some(3), None],
+    ]?
+    .lazy();
+
+    let expected = df![
+        "A" => [Some(1), Some(1), Some(2), None, None],
+        "B" => [None, Some(1), Some(3), None, Some(2)],
+    ]?;
+    for streaming in [false, true] {
+        let res = q
+            .clone()
+            .sort_by_exprs(
+                [col("A"), col("B")],
+                [false, false],
+                vec![true, false],
+                false,
+            )
+            .with_streaming(streaming)
+            .collect()?;
+        assert!(res.frame_equal_missing(&expected));
+    }
+
+    let res = q
+        .sort_by_exprs([col("A")], [true], vec![true], false)
+        .with_streaming(true)
+        .collect()?;
+    assert_eq!(res.column("A")?.null_count(), 2);
+    assert_eq!(res.column("A")?.get(0)?, AnyValue::Int32(2));
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "csv")]
+fn test_sink_csv() -> PolarsResult<()> {
+    let mut path = std::env::temp_dir();
+    path.push(format!("polars_test_sink_{}.csv", std::process::id()));
+
+    let q = get_csv_file().filter(col("calories").gt(lit(100)));
+    let expected = q.clone().collect()?;
+    q.sink_csv(
+        path.clone(),
+        CsvWriterOptions {
+            maintain_order: true,
+            ..Default::default()
+        },
+    )?;
+
+    let out = LazyCsvReader::new(&path).finish()?.collect()?;
+    std::fs::remove_file(&path)?;
+    assert!(out.frame_equal_missing(&expected));
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "parquet")]
+fn test_sink_parquet_partitioned() -> PolarsResult<()> {
+    // the directory is removed when `tmp` is dropped, also if an assertion fails
+    let tmp = tempdir::TempDir::new("polars-sink-partitioned")?;
+    let root = tmp.path().to_path_buf();
+
+    let q = get_csv_file();
+    let expected = q
+        .clone()
+        .groupby([col("category")])
+        .agg([count()])
+        .collect()?;
+    let options = PartitionedParquetWriteOptions {
+        max_rows_per_file: Some(2),
+        max_open_files: 2,
+        ..PartitionedParquetWriteOptions::new(["category"])
+    };
+    q.sink_parquet_partitioned(root.clone(), options)?;
+
+    let categories = expected.column("category")?.utf8()?;
+    let counts = expected.column("count")?.idx()?;
+    for (category, count) in categories.into_iter().zip(counts.into_iter()) {
+        let dir = root.join(format!("category={}", category.unwrap()));
+        let mut n_rows = 0;
+        for entry in std::fs::read_dir(&dir)? {
+            let path = entry?.path();
+            let df = LazyFrame::scan_parquet(&path, Default::default())?.collect()?;
+            assert!(df.height() <= 2);
+            let values = df.column("category")?.utf8()?;
+            assert!(values.into_iter().all(|v| v == category));
+            n_rows += df.height();
+        }
+        assert_eq!(n_rows as IdxSize, count.unwrap());
+    }
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "parquet")]
+fn test_sink_parquet_partitioned_escaped_values() -> PolarsResult<()> {
+    let tmp = tempdir::TempDir::new("polars-sink-partitioned-escaped")?;
+    let root = tmp.path().to_path_buf();
+
+    let df = df![
+        "key" => [Some("a/b"), Some(".."), Some("."), Some("x:y*"), Some("50%"), Some("k=v"),
+                  Some("c\\d"), Some("tab\t"), Some(""), None, Some("a/b")],
+        "value" => [1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11],
+    ]?;
+    df.lazy()
+        .sink_parquet_partitioned(root.clone(), PartitionedParquetWriteOptions::new(["key"]))?;
+
+    let expected = [
+        ("key=a%2Fb", 2),
+        ("key=%2E%2E", 1),
+        ("key=%2E", 1),
+        ("key=x%3Ay%2A", 1),
+        ("key=50%25", 1),
+        ("key=k%3Dv", 1),
+        ("key=c%5Cd", 1),
+        ("key=tab%09", 1),
+        // the empty string and null are both written to the default partition
+        ("key=__HIVE_DEFAULT_PARTITION__", 2),
+    ];
+    // every value got its own directory right under the root
+    assert_eq!(std::fs::read_dir(&root)?.count(), expected.len());
+    for (dir, count) in expected {
+        let mut n_rows = 0;
+        for entry in std::fs::read_dir(root.join(dir))? {
+            let path = entry?.path();
+            let df = LazyFrame::scan_parquet(&path, Default::default())?.collect()?;
+            n_rows += df.height();
+        }
+        assert_eq!(n_rows, count, "{dir}");
+    }
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "json")]
+fn test_streaming_ndjson() -> PolarsResult<()> {
+    init_files();
+    let q = LazyJsonLineReader::new("../../examples/datasets/foods1.ndjson")
+        .finish()?
+        .filter(col("sugars_g").gt(lit(1)))
+        .select([col("category"), col("calories")]);
+    assert_streaming_with_default(q, true, false);
+
+    let q = LazyJsonLineReader::new("../../examples/datasets/foods1.ndjson")
+        .finish()?
+        .limit(5);
+    assert_streaming_with_default(q, true, false);
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "avro")]
+fn test_streaming_avro() -> PolarsResult<()> {
+    use polars_io::avro::AvroWriter;
+
+    let mut path = std::env::temp_dir();
+    path.push(format!("polars_test_streaming_{}.avro", std::process::id()));
+    let mut df = get_csv_file().collect()?;
+    AvroWriter::new(std::fs::File::create(&path)?).finish(&mut df)?;
+
+    let q = LazyAvroReader::new(&path)
+        .finish()?
+        .filter(col("calories").gt(lit(100)))
+        .select([col("category"), col("fats_g")]);
+    assert_streaming_with_default(q, true, false);
+    std::fs::remove_file(&path)?;
+    Ok(())
+}
+
+#[test]
+fn test_streaming_spill_dir_is_cleaned() -> PolarsResult<()> {
+    fn count_files(dir: &std::path::Path) -> usize {
+        std::fs::read_dir(dir)
+            .map(|entries| {
+                entries
+                    .map(|entry| {
+                        let path = entry.unwrap().path();
+                        if path.is_dir() {
+                            count_files(&path)
+                        } else {
+                            1
+                        }
+                    })
+                    .sum()
+            })
+            .unwrap_or(0)
+    }
+
+    let tmp = tempdir::TempDir::new("polars-spill")?;
+    let spill_dir = tmp.path();
+    // a budget of zero spills every operator to disk
+    let config = StreamingConfig::default()
+        .with_spill_dir(spill_dir)
+        .with_memory_budget(0);
+
+    let q = get_csv_file()
+        .groupby([col("category"), col("calories")])
+        .agg([col("fats_g").sum()])
+        .sort_by_exprs(
+            [col("category"), col("calories")],
+            [false, false],
+            false,
+            false,
+        );
+    let expected = q.clone().collect()?;
+    let out = q
+        .with_streaming(true)
+        .with_streaming_config(config)
+        .collect()?;
+    assert_eq!(out, expected);
+
+    // the groupby spilled, its spill files are removed when the query is done
+    let groupby_dir = spill_dir.join("polars").join("groupby");
+    assert!(groupby_dir.is_dir());
+    assert_eq!(count_files(spill_dir), 0);
+    Ok(())
+}
+
+#[test]
+#[cfg(feature = "dynamic_groupby")]
+fn test_streaming_groupby_dynamic_rolling() -> PolarsResult<()> {
+    let df = df![
+        "t" => [0i64, 1, 1, 2, 4, 7, 8, 8, 12, 13],
+        "x" => [1i32, 2, 3, 4, 5, 6, 7, 8, 9, 10],
+    ]?;
+
+    for closed_window in [
+        polars_time::ClosedWindow::Left,
+        polars_time::ClosedWindow::Right,
+        polars_time::ClosedWindow::Both,
+        polars_time::ClosedWindow::None,
+    ] {
+        let q = df
+            .clone()
+            .lazy()
+            .groupby_dynamic(
+                col("t"),
+                [],
+                DynamicGroupOptions {
+                    every: polars_time::Duration::parse("3i"),
+                    period: polars_time::Duration::parse("4i"),
+                    offset: polars_time::Duration::parse("0i"),
+                    include_boundaries: true,
+                    closed_window,
+                    ..Default::default()
+                },
+            )
+            .agg([
+                col("x").sum().alias("sum"),
+                col("x").mean().alias("mean"),
+                col("x").count().alias("count"),
+            ]);
+        assert_streaming_with_default(q, true, false);
+
+        let q = df
+            .clone()
+            .lazy()
+            .groupby_rolling(
+                col("t"),
+                [],
+                RollingGroupOptions {
+                    period: polars_time::Duration::parse("3i"),
+                    offset: polars_time::Duration::parse("-3i"),
+                    closed_window,
+                    ..Default::default()
+                },
+            )
+            .agg([col("x").sum().alias("sum"), col("x").max().alias("max")]);
+        assert_streaming_with_default(q, true, false);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_streaming_window_over() -> PolarsResult<()> {
+    let q = get_parquet_file().with_columns([
+        col("calories").sum().over([col("category")]).alias("total"),
+        col("fats_g")
+            .max()
+            .over([col("category")])
+            .alias("max_fats"),
+    ]);
+
+    // the rows must keep the order of the input
+    assert_streaming_with_default(q, true, false);
+    Ok(())
+}
+
+#[test]
+fn test_streaming_explain() -> PolarsResult<()> {
+    let q = get_parquet_file()
+        .groupby([col("category")])
+        .agg([col("calories").sum()])
+        .sort_by_exprs([col("category")], [false], false, true)
+        .with_column(col("calories").cumsum(false).alias("cum_calories"));
+
+    let plan = q.with_streaming(true).explain(true)?;
+    let (_, streaming) = plan.split_once("STREAMING:").unwrap();
+    let (pipelines, not_streamed) = streaming.split_once("NOT STREAMED").unwrap();
+    assert!(pipelines.contains("PIPELINE 0"));
+    assert!(pipelines.contains("SOURCE"));
+    assert!(pipelines.contains("SINK"));
+    // the stable sort runs in the same pipeline as the groupby
+    assert!(pipelines.contains("SORT BY [col(\"category\")]"));
+    // only the projection with the cumsum is run in the default engine
+    let not_streamed = not_streamed.trim().lines().collect::<Vec<_>>();
+    assert_eq!(not_streamed.len(), 1);
+    assert!(not_streamed[0].starts_with("WITH_COLUMNS"));
+    assert!(not_streamed[0].ends_with(": AExpr::Function `cumsum` not streamable"));
+    Ok(())
+}
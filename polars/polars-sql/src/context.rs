@@ -7,22 +7,63 @@ use polars_lazy::prelude::*;
 use polars_plan::prelude::*;
 use polars_plan::utils::expressions_to_schema;
 use sqlparser::ast::{
-    Distinct, ExcludeSelectItem, Expr as SqlExpr, FunctionArg, JoinOperator, ObjectName,
-    ObjectType, Offset, OrderByExpr, Query, Select, SelectItem, SetExpr, SetOperator,
-    SetQuantifier, Statement, TableAlias, TableFactor, TableWithJoins, Value as SQLValue,
-    WildcardAdditionalOptions,
+    AlterTableOperation, ColumnOption, Distinct, ExcludeSelectItem, Expr as SqlExpr, FunctionArg,
+    JoinConstraint, JoinOperator, NamedWindowDefinition, ObjectName, ObjectType, Offset,
+    OrderByExpr, Query, Select, SelectItem, SetExpr, SetOperator, SetQuantifier, Statement,
+    TableAlias, TableFactor, TableWithJoins, Value as SQLValue, Values, WildcardAdditionalOptions,
+    WindowSpec,
 };
 use sqlparser::dialect::GenericDialect;
+#[cfg(any(
+    feature = "parquet",
+    feature = "ipc",
+    feature = "csv",
+    feature = "json"
+))]
+use sqlparser::keywords::Keyword;
 use sqlparser::parser::{Parser, ParserOptions};
+use sqlparser::tokenizer::{Token, Tokenizer};
 
-use crate::sql_expr::{parse_sql_expr, process_join_constraint};
+#[cfg(any(
+    feature = "parquet",
+    feature = "ipc",
+    feature = "csv",
+    feature = "json"
+))]
+use crate::copy::{CopySource, CopyStatement};
+use crate::explain::explain_analyze;
+use crate::params::{placeholder_key, PreparedStatement, SQLParams};
+use crate::sql_expr::{
+    map_sql_polars_datatype, parse_sql_expr, process_join_constraint, split_conjunction,
+};
 use crate::table_functions::PolarsTableFunctions;
 
+/// Name of the helper column that numbers duplicate rows in INTERSECT ALL / EXCEPT ALL.
+const SET_OPERATION_OCCURRENCE: &str = "__POLARS_SQL_OCCURRENCE";
+/// Names of the helper columns that hold the HAVING and QUALIFY predicates.
+const HAVING_COLUMN: &str = "__POLARS_SQL_HAVING";
+const QUALIFY_COLUMN: &str = "__POLARS_SQL_QUALIFY";
+/// Prefix that marks references to projection aliases while they are being expanded.
+const ALIAS_MARKER: &str = "__POLARS_SQL_ALIAS_";
+/// Names of the helper columns that hold whether a `NOT IN` subquery has nulls and rows.
+const NOT_IN_HAS_NULLS: &str = "__POLARS_SQL_NOT_IN_HAS_NULLS";
+const NOT_IN_COUNT: &str = "__POLARS_SQL_NOT_IN_COUNT";
+/// Name of the helper column that holds the grouping id of GROUPING SETS, ROLLUP and CUBE.
+const GROUPING_ID: &str = "__POLARS_SQL_GROUPING_ID";
+/// Prefix of the helper columns that `GROUPING(key)` refers to, one per groupby key.
+pub(crate) const GROUPING_PREFIX: &str = "__POLARS_SQL_GROUPING_KEY_";
+
 /// The SQLContext is the main entry point for executing SQL queries.
 #[derive(Default, Clone)]
 pub struct SQLContext {
     pub(crate) table_map: PlHashMap<String, LazyFrame>,
     cte_map: RefCell<PlHashMap<String, LazyFrame>>,
+    subquery_frames: RefCell<Vec<(String, LazyFrame)>>,
+    params: PlHashMap<String, LiteralValue>,
+    pub(crate) named_windows: PlHashMap<String, WindowSpec>,
+    /// Schema of the frame the expressions of the current statement are evaluated on,
+    /// used to check the types of the parameters that are compared with its columns.
+    pub(crate) input_schema: Option<SchemaRef>,
 }
 
 impl SQLContext {
@@ -37,6 +78,10 @@ impl SQLContext {
         Self {
             table_map: PlHashMap::new(),
             cte_map: RefCell::new(PlHashMap::new()),
+            subquery_frames: RefCell::new(Vec::new()),
+            params: PlHashMap::new(),
+            named_windows: PlHashMap::new(),
+            input_schema: None,
         }
     }
 
@@ -95,17 +140,125 @@ impl SQLContext {
             trailing_commas: true,
         });
 
-        let ast = parser
-            .try_with_sql(query)
-            .map_err(to_compute_err)?
-            .parse_statements()
-            .map_err(to_compute_err)?;
+        let mut parser = parser.try_with_sql(query).map_err(to_compute_err)?;
+        #[cfg(any(
+            feature = "parquet",
+            feature = "ipc",
+            feature = "csv",
+            feature = "json"
+        ))]
+        if parser.parse_keyword(Keyword::COPY) {
+            let stmt = CopyStatement::parse(&mut parser).map_err(to_compute_err)?;
+            let res = self.execute_copy(stmt);
+            self.cte_map.borrow_mut().clear();
+            self.subquery_frames.borrow_mut().clear();
+            return res;
+        }
+        let ast = parser.parse_statements().map_err(to_compute_err)?;
         polars_ensure!(ast.len() == 1, ComputeError: "One and only one statement at a time please");
         let res = self.execute_statement(ast.get(0).unwrap());
         // every execution should clear the cte map
         self.cte_map.borrow_mut().clear();
+        self.subquery_frames.borrow_mut().clear();
         res
     }
+
+    /// Parse a SQL query once, so that it can be executed repeatedly with different
+    /// parameters bound to its `$1`, `?` or `:name` placeholders.
+    ///
+    /// The query may also be written as `PREPARE name (type, ...) AS <statement>`,
+    /// in which case the positional parameters must match the declared types. A parameter
+    /// that is compared with a column must fit the type of that column, and a parameter
+    /// that is cast must be castable.
+    /// ```rust
+    /// # use polars_sql::{SQLContext, SQLParams};
+    /// # use polars_core::prelude::*;
+    /// # use polars_lazy::prelude::*;
+    /// # use polars_plan::prelude::LiteralValue;
+    /// # fn main() {
+    ///
+    /// let mut ctx = SQLContext::new();
+    /// let df = df! {
+    ///    "a" =>  [1, 2, 3],
+    /// }
+    /// .unwrap();
+    ///
+    /// ctx.register("df", df.lazy());
+    /// let stmt = ctx.prepare("SELECT * FROM df WHERE a > $1").unwrap();
+    /// for min in [1, 2] {
+    ///     let params = SQLParams::new().with(LiteralValue::Int32(min));
+    ///     let out = ctx.execute_prepared(&stmt, &params).unwrap().collect().unwrap();
+    ///     assert_eq!(out.height() as i32, 3 - min);
+    /// }
+    /// # }
+    ///```
+    pub fn prepare(&self, query: &str) -> PolarsResult<PreparedStatement> {
+        let dialect = GenericDialect;
+        let mut tokens = Tokenizer::new(&dialect, query)
+            .tokenize()
+            .map_err(to_compute_err)?;
+
+        // number anonymous `?` placeholders in order of appearance
+        let mut n_anonymous = 0;
+        let mut n_numbered = 0;
+        let mut n_positional = 0;
+        for token in tokens.iter_mut() {
+            if let Token::Placeholder(placeholder) = token {
+                if placeholder == "?" {
+                    n_anonymous += 1;
+                    *placeholder = format!("${}", n_anonymous);
+                } else if placeholder_key(placeholder).parse::<usize>().is_ok() {
+                    n_numbered += 1;
+                }
+                if let Ok(idx) = placeholder_key(placeholder).parse::<usize>() {
+                    n_positional = n_positional.max(idx);
+                }
+            }
+        }
+        // `?` and `$n` would be bound to the same positional parameters
+        polars_ensure!(
+            n_anonymous == 0 || n_numbered == 0,
+            ComputeError: "SQL query can't mix `?` and numbered `$n` placeholders"
+        );
+
+        let ast = Parser::new(&dialect)
+            .with_options(ParserOptions {
+                trailing_commas: true,
+            })
+            .with_tokens(tokens)
+            .parse_statements()
+            .map_err(to_compute_err)?;
+        polars_ensure!(ast.len() == 1, ComputeError: "One and only one statement at a time please");
+        PreparedStatement::new(ast.into_iter().next().unwrap(), n_positional)
+    }
+
+    /// Execute a prepared statement with the given parameters, returning a LazyFrame.
+    pub fn execute_prepared(
+        &mut self,
+        stmt: &PreparedStatement,
+        params: &SQLParams,
+    ) -> PolarsResult<LazyFrame> {
+        self.params = stmt.bind(params)?;
+        let res = self.execute_statement(&stmt.statement);
+        // every execution should clear the cte map and the bound parameters
+        self.cte_map.borrow_mut().clear();
+        self.subquery_frames.borrow_mut().clear();
+        self.params.clear();
+        self.input_schema = None;
+        res
+    }
+
+    /// Execute a SQL query with parameters bound to its placeholders, returning a LazyFrame.
+    ///
+    /// See [`SQLContext::prepare`] for the supported placeholders.
+    pub fn execute_with_params(
+        &mut self,
+        query: &str,
+        params: &SQLParams,
+    ) -> PolarsResult<LazyFrame> {
+        let stmt = self.prepare(query)?;
+        self.execute_prepared(&stmt, params)
+    }
 }
 
 impl SQLContext {
@@ -113,6 +266,63 @@ impl SQLContext {
         self.cte_map.borrow_mut().insert(name.to_owned(), lf);
     }
 
+    /// Execute an (uncorrelated) subquery in a child context that sees the same
+    /// tables and CTEs, but keeps its own scalar subqueries.
+    pub(crate) fn execute_subquery(&self, query: &Query) -> PolarsResult<LazyFrame> {
+        let mut ctx = Self {
+            table_map: self.table_map.clone(),
+            cte_map: RefCell::new(self.cte_map.borrow().clone()),
+            subquery_frames: RefCell::new(Vec::new()),
+            params: self.params.clone(),
+            named_windows: PlHashMap::new(),
+            input_schema: None,
+        };
+        ctx.execute_query(query)
+    }
+
+    /// Register a subquery whose result is reduced to a single row by `agg`.
+    /// The returned column name can be referenced in the expressions of the
+    /// current SELECT; the one-row frame is cross joined onto its input.
+    pub(crate) fn register_subquery(&self, lf: LazyFrame, agg: Expr) -> String {
+        let mut frames = self.subquery_frames.borrow_mut();
+        let name = format!("__POLARS_SQL_SUBQUERY_{}", frames.len());
+        frames.push((name.clone(), lf.select([agg.alias(&name)])));
+        name
+    }
+
+    /// Cross join all pending subquery frames onto `lf`, returning the names
+    /// of the columns that were added.
+    fn join_subqueries(&self, mut lf: LazyFrame) -> (LazyFrame, Vec<String>) {
+        let frames = std::mem::take(&mut *self.subquery_frames.borrow_mut());
+        let mut names = Vec::with_capacity(frames.len());
+        for (name, subquery_lf) in frames {
+            lf = lf.cross_join(subquery_lf);
+            names.push(name);
+        }
+        (lf, names)
+    }
+
+    /// Keep the schema of the frame the expressions of a statement are evaluated on, if
+    /// parameters are bound, so that the types of compared parameters can be checked.
+    fn set_input_schema(&mut self, lf: &LazyFrame) -> PolarsResult<()> {
+        self.input_schema = if self.params.is_empty() {
+            None
+        } else {
+            Some(lf.schema()?)
+        };
+        Ok(())
+    }
+
+    /// Get the value bound to a placeholder.
+    pub(crate) fn get_param(&self, placeholder: &str) -> PolarsResult<LiteralValue> {
+        self.params
+            .get(placeholder_key(placeholder))
+            .cloned()
+            .ok_or_else(
+                || polars_err!(ComputeError: "no value bound to SQL parameter {}", placeholder),
+            )
+    }
+
     fn get_table_from_current_scope(&mut self, name: &str) -> Option<LazyFrame> {
         if let Some(lf) = self.table_map.get(name) {
             Some(lf.clone())
@@ -132,6 +342,11 @@ impl SQLContext {
                 ..
             } => self.execute_drop_table(stmt)?,
             stmt @ Statement::Explain { .. } => self.execute_explain(stmt)?,
+            stmt @ Statement::Insert { .. } => self.execute_insert(stmt)?,
+            stmt @ Statement::Delete { .. } => self.execute_delete(stmt)?,
+            stmt @ Statement::Update { .. } => self.execute_update(stmt)?,
+            stmt @ Statement::Truncate { .. } => self.execute_truncate(stmt)?,
+            stmt @ Statement::AlterTable { .. } => self.execute_alter_table(stmt)?,
             _ => polars_bail!(
                 ComputeError: "SQL statement type {:?} is not supported", ast,
             ),
@@ -141,7 +356,13 @@ impl SQLContext {
     pub(crate) fn execute_query(&mut self, query: &Query) -> PolarsResult<LazyFrame> {
         self.register_ctes(query)?;
 
-        let lf = self.process_set_expr(&query.body, query)?;
+        let mut lf = self.process_set_expr(&query.body, query)?;
+
+        // the ORDER BY of a set operation applies to its combined result
+        if matches!(query.body.as_ref(), SetExpr::SetOperation { .. }) && !query.order_by.is_empty()
+        {
+            lf = self.process_order_by(lf, &query.order_by)?;
+        }
 
         self.process_limit_offset(lf, &query.limit, &query.offset)
     }
@@ -156,9 +377,16 @@ impl SQLContext {
                 left,
                 right,
             } => self.process_union(left, right, set_quantifier, query),
+            SetExpr::SetOperation {
+                op: op @ (SetOperator::Intersect | SetOperator::Except),
+                set_quantifier,
+                left,
+                right,
+            } => self.process_intersect_except(left, right, op, set_quantifier, query),
             SetExpr::SetOperation { op, .. } => {
                 polars_bail!(InvalidOperation: "{} operation not yet supported", op)
             }
+            SetExpr::Values(values) => self.process_values(values),
             op => polars_bail!(InvalidOperation: "{} operation not yet supported", op),
         }
     }
@@ -186,11 +414,120 @@ impl SQLContext {
             _ => concatenated.map(|lf| lf.unique(None, UniqueKeepStrategy::Any)),
         }
     }
-    // EXPLAIN SELECT * FROM DF
+
+    /// VALUES (...), (...): every row is evaluated as a select of literals on its
+    /// own and the rows are cast to a common supertype before they are concatenated.
+    fn process_values(&mut self, values: &Values) -> PolarsResult<LazyFrame> {
+        let mut rows = Vec::with_capacity(values.rows.len());
+        let mut schema: Option<Schema> = None;
+        for row in &values.rows {
+            let exprs = row
+                .iter()
+                .enumerate()
+                .map(|(i, e)| Ok(parse_sql_expr(e, self)?.alias(&format!("column_{i}"))))
+                .collect::<PolarsResult<Vec<_>>>()?;
+            let lf = DataFrame::empty().lazy().select(exprs);
+            let row_schema = lf.schema()?;
+            schema = Some(match schema {
+                None => row_schema.as_ref().clone(),
+                Some(schema) => {
+                    polars_ensure!(
+                        schema.len() == row_schema.len(),
+                        ComputeError: "VALUES rows must all have the same number of columns"
+                    );
+                    schema
+                        .iter()
+                        .zip(row_schema.iter_dtypes())
+                        .map(|((name, l), r)| Ok(Field::new(name, try_get_supertype(l, r)?)))
+                        .collect::<PolarsResult<Schema>>()?
+                }
+            });
+            rows.push(lf);
+        }
+        let schema = schema
+            .ok_or_else(|| polars_err!(ComputeError: "VALUES must contain at least one row"))?;
+        let rows = rows
+            .into_iter()
+            .map(|lf| {
+                lf.select(
+                    schema
+                        .iter()
+                        .map(|(name, dtype)| col(name).cast(dtype.clone()))
+                        .collect::<Vec<_>>(),
+                )
+            })
+            .collect::<Vec<_>>();
+        polars_lazy::dsl::concat(rows, UnionArgs::default())
+    }
+
+    /// INTERSECT [ALL] and EXCEPT [ALL] are executed as semi and anti joins on all
+    /// columns. NULLs compare equal, as SQL prescribes for set operations.
+    ///
+    /// For the ALL variants every row is numbered within its group of duplicates,
+    /// so that the n-th duplicate on the left only matches the n-th on the right.
+    fn process_intersect_except(
+        &mut self,
+        left: &SetExpr,
+        right: &SetExpr,
+        op: &SetOperator,
+        quantifier: &SetQuantifier,
+        query: &Query,
+    ) -> PolarsResult<LazyFrame> {
+        let left = self.process_set_expr(left, query)?;
+        let right = self.process_set_expr(right, query)?;
+
+        // columns are matched by position, not by name
+        let left_names = left
+            .schema()?
+            .iter_names()
+            .map(|name| name.to_string())
+            .collect::<Vec<_>>();
+        let right_names = right
+            .schema()?
+            .iter_names()
+            .map(|name| name.to_string())
+            .collect::<Vec<_>>();
+        polars_ensure!(
+            left_names.len() == right_names.len(),
+            ComputeError: "{} requires both sides to have the same number of columns, got {} and {}",
+            op, left_names.len(), right_names.len()
+        );
+        let right = right.rename(&right_names, &left_names);
+
+        let how = match op {
+            SetOperator::Intersect => JoinType::Semi,
+            _ => JoinType::Anti,
+        };
+        let mut on = left_names.iter().map(|name| col(name)).collect::<Vec<_>>();
+        Ok(match quantifier {
+            SetQuantifier::All => {
+                let occurrence = col(&left_names[0])
+                    .cumcount(false)
+                    .over(&on)
+                    .alias(SET_OPERATION_OCCURRENCE);
+                let left = left.with_column(occurrence.clone());
+                let right = right.with_column(occurrence);
+                on.push(col(SET_OPERATION_OCCURRENCE));
+                left.join(right, &on, &on, JoinArgs::new(how))
+                    .drop_columns([SET_OPERATION_OCCURRENCE])
+            }
+            _ => {
+                left.unique(None, UniqueKeepStrategy::Any)
+                    .join(right, &on, &on, JoinArgs::new(how))
+            }
+        })
+    }
+
+    // EXPLAIN [ANALYZE] SELECT * FROM DF
     fn execute_explain(&mut self, stmt: &Statement) -> PolarsResult<LazyFrame> {
         match stmt {
-            Statement::Explain { statement, .. } => {
+            Statement::Explain {
+                statement, analyze, ..
+            } => {
                 let lf = self.execute_statement(statement)?;
+                if *analyze {
+                    return Ok(explain_analyze(lf)?.lazy());
+                }
                 let plan = lf.describe_optimized_plan()?;
                 let mut plan = plan.split('\n').collect::<Series>();
                 plan.rename("Logical Plan");
@@ -237,27 +574,48 @@ impl SQLContext {
 
     /// execute the 'FROM' part of the query
     fn execute_from_statement(&mut self, tbl_expr: &TableWithJoins) -> PolarsResult<LazyFrame> {
-        let (tbl_name, mut lf) = self.get_table(&tbl_expr.relation)?;
+        let (_, mut lf) = self.get_table(&tbl_expr.relation)?;
         if !tbl_expr.joins.is_empty() {
             for tbl in &tbl_expr.joins {
                 let (join_tbl_name, join_tbl) = self.get_table(&tbl.relation)?;
-                match &tbl.join_operator {
-                    JoinOperator::Inner(constraint) => {
-                        let (left_on, right_on) =
-                            process_join_constraint(constraint, &tbl_name, &join_tbl_name)?;
-                        lf = lf.inner_join(join_tbl, left_on, right_on)
-                    }
+                lf = match &tbl.join_operator {
+                    JoinOperator::Inner(constraint) => self.process_join(
+                        lf,
+                        join_tbl,
+                        constraint,
+                        &join_tbl_name,
+                        JoinType::Inner,
+                    )?,
                     JoinOperator::LeftOuter(constraint) => {
+                        self.process_join(lf, join_tbl, constraint, &join_tbl_name, JoinType::Left)?
+                    }
+                    JoinOperator::RightOuter(constraint) => {
+                        self.process_right_join(lf, join_tbl, constraint, &join_tbl_name)?
+                    }
+                    JoinOperator::FullOuter(constraint) => self.process_join(
+                        lf,
+                        join_tbl,
+                        constraint,
+                        &join_tbl_name,
+                        JoinType::Outer,
+                    )?,
+                    JoinOperator::LeftSemi(constraint) => {
+                        self.process_join(lf, join_tbl, constraint, &join_tbl_name, JoinType::Semi)?
+                    }
+                    JoinOperator::LeftAnti(constraint) => {
+                        self.process_join(lf, join_tbl, constraint, &join_tbl_name, JoinType::Anti)?
+                    }
+                    JoinOperator::RightSemi(constraint) => {
                         let (left_on, right_on) =
-                            process_join_constraint(constraint, &tbl_name, &join_tbl_name)?;
-                        lf = lf.left_join(join_tbl, left_on, right_on)
+                            self.process_join_keys(&lf, &join_tbl, constraint, &join_tbl_name)?;
+                        join_tbl.join(lf, right_on, left_on, JoinArgs::new(JoinType::Semi))
                     }
-                    JoinOperator::FullOuter(constraint) => {
+                    JoinOperator::RightAnti(constraint) => {
                         let (left_on, right_on) =
-                            process_join_constraint(constraint, &tbl_name, &join_tbl_name)?;
-                        lf = lf.outer_join(join_tbl, left_on, right_on)
+                            self.process_join_keys(&lf, &join_tbl, constraint, &join_tbl_name)?;
+                        join_tbl.join(lf, right_on, left_on, JoinArgs::new(JoinType::Anti))
                     }
-                    JoinOperator::CrossJoin => lf = lf.cross_join(join_tbl),
+                    JoinOperator::CrossJoin => lf.cross_join(join_tbl),
                     join_type => {
                         polars_bail!(
                             InvalidOperation:
@@ -270,6 +628,108 @@ impl SQLContext {
 
         Ok(lf)
     }
+
+    fn process_join_keys(
+        &self,
+        left: &LazyFrame,
+        right: &LazyFrame,
+        constraint: &JoinConstraint,
+        right_name: &str,
+    ) -> PolarsResult<(Vec<Expr>, Vec<Expr>)> {
+        if let JoinConstraint::Natural = constraint {
+            let left_schema = left.schema()?;
+            let right_schema = right.schema()?;
+            let on = left_schema
+                .iter_names()
+                .filter(|name| right_schema.contains(name))
+                .map(|name| col(name))
+                .collect::<Vec<_>>();
+            polars_ensure!(
+                !on.is_empty(),
+                ComputeError: "NATURAL JOIN requires at least one common column"
+            );
+            Ok((on.clone(), on))
+        } else {
+            process_join_constraint(constraint, right_name)
+        }
+    }
+
+    fn process_join(
+        &self,
+        left: LazyFrame,
+        right: LazyFrame,
+        constraint: &JoinConstraint,
+        right_name: &str,
+        how: JoinType,
+    ) -> PolarsResult<LazyFrame> {
+        let (left_on, right_on) = self.process_join_keys(&left, &right, constraint, right_name)?;
+        Ok(left.join(right, left_on, right_on, JoinArgs::new(how)))
+    }
+
+    /// A RIGHT JOIN is executed as a LEFT JOIN with the sides swapped, after which
+    /// the columns of the left table are moved to the front again. The swapped join
+    /// suffixes the overlapping columns of the left table, so these are renamed to
+    /// give the columns of the right table the suffix, as in any other join. The swapped
+    /// join also drops the keys of the left table. These are rebuilt from the matching
+    /// keys of the right table, and are null where no row of the left table matched.
+    fn process_right_join(
+        &self,
+        left: LazyFrame,
+        right: LazyFrame,
+        constraint: &JoinConstraint,
+        right_name: &str,
+    ) -> PolarsResult<LazyFrame> {
+        let (left_on, right_on) = self.process_join_keys(&left, &right, constraint, right_name)?;
+        let left_schema = left.schema()?;
+        let right_schema = right.schema()?;
+        const MATCHED: &str = "__POLARS_RIGHT_JOIN_MATCHED";
+        let left = left.with_column(lit(true).alias(MATCHED));
+        let joined = right.join(
+            left,
+            right_on.clone(),
+            left_on.clone(),
+            JoinArgs::new(JoinType::Left),
+        );
+        let joined_schema = joined.schema()?;
+        let dropped_key = |name: &str, dtype: &DataType| {
+            left_on
+                .iter()
+                .position(|e| matches!(e, Expr::Column(key) if key.as_ref() == name))
+                .map(|i| {
+                    when(col(MATCHED).is_not_null())
+                        .then(right_on[i].clone())
+                        .otherwise(lit(Null {}))
+                        .cast(dtype.clone())
+                        .alias(name)
+                })
+        };
+        // the overlapping columns that are not join keys
+        let suffixed = |name: &str| {
+            let suffixed = format!("{name}_right");
+            (left_schema.contains(name)
+                && right_schema.contains(name)
+                && joined_schema.contains(&suffixed))
+            .then_some(suffixed)
+        };
+
+        let mut exprs = vec![];
+        for (name, dtype) in left_schema.iter() {
+            match suffixed(name) {
+                Some(suffixed) => exprs.push(col(&suffixed).alias(name)),
+                None if joined_schema.contains(name) => exprs.push(col(name)),
+                None => exprs.extend(dropped_key(name, dtype)),
+            }
+        }
+        for name in right_schema.iter_names() {
+            match suffixed(name) {
+                Some(suffixed) => exprs.push(col(name).alias(&suffixed)),
+                None if !left_schema.contains(name) => exprs.push(col(name)),
+                None => {}
+            }
+        }
+        Ok(joined.select(exprs))
+    }
+
     /// execute the 'SELECT' part of the query
     fn execute_select(&mut self, select_stmt: &Select, query: &Query) -> PolarsResult<LazyFrame> {
         // Determine involved dataframe
@@ -280,16 +740,40 @@ impl SQLContext {
             .ok_or_else(|| polars_err!(ComputeError: "no table name provided in query"))?;
 
         let mut lf = self.execute_from_statement(sql_tbl)?;
+        self.set_input_schema(&lf)?;
         let mut contains_wildcard = false;
+        self.named_windows = select_stmt
+            .named_window
+            .iter()
+            .map(|NamedWindowDefinition(name, spec)| (name.value.clone(), spec.clone()))
+            .collect();
+        let order_by: &[OrderByExpr] = match query.body.as_ref() {
+            SetExpr::SetOperation { .. } => &[],
+            _ => query.order_by.as_slice(),
+        };
 
         // Filter Expression
-        lf = match select_stmt.selection.as_ref() {
-            Some(expr) => {
-                let filter_expression = parse_sql_expr(expr, self)?;
-                lf.filter(filter_expression)
+        // Top-level `[NOT] IN (subquery)` predicates are lowered to semi/anti joins,
+        // the remaining predicates are applied as a regular filter.
+        let mut filter_expression: Option<Expr> = None;
+        if let Some(expr) = select_stmt.selection.as_ref() {
+            for predicate in split_conjunction(expr) {
+                match predicate {
+                    SqlExpr::InSubquery {
+                        expr,
+                        subquery,
+                        negated,
+                    } => lf = self.process_in_subquery(lf, expr, subquery, *negated)?,
+                    _ => {
+                        let e = parse_sql_expr(predicate, self)?;
+                        filter_expression = Some(match filter_expression {
+                            Some(acc) => acc.and(e),
+                            None => e,
+                        });
+                    }
+                }
             }
-            None => lf,
-        };
+        }
 
         // Column Projections
         let projections: Vec<_> = select_stmt
@@ -316,39 +800,124 @@ impl SQLContext {
 
         // Check for group by
         // After projection since there might be number.
-        let groupby_keys: Vec<Expr> = select_stmt
-            .group_by
-            .iter()
-            .map(|e| match e {
-                SqlExpr::Value(SQLValue::Number(idx, _)) => {
-                    let idx = match idx.parse::<usize>() {
-                        Ok(0) | Err(_) => Err(polars_err!(
-                            ComputeError:
-                            "groupby error: a positive number or an expression expected, got {}",
-                            idx
-                        )),
-                        Ok(idx) => Ok(idx),
-                    }?;
-                    Ok(projections[idx].clone())
+        // Every item expands to one or more sets of keys, the grouping sets of the
+        // query are the cartesian product of those.
+        let mut grouping_sets: Vec<Vec<Expr>> = vec![vec![]];
+        let mut has_grouping_sets = false;
+        for e in &select_stmt.group_by {
+            let item_sets: Vec<Vec<Expr>> = match e {
+                SqlExpr::Rollup(elements) => {
+                    let elements = self.process_grouping_elements(elements, &projections)?;
+                    (0..=elements.len())
+                        .rev()
+                        .map(|n| elements[..n].concat())
+                        .collect()
                 }
-                SqlExpr::Value(_) => Err(polars_err!(
-                    ComputeError:
-                    "groupby error: a positive number or an expression expected",
-                )),
-                _ => parse_sql_expr(e, self),
-            })
-            .collect::<PolarsResult<_>>()?;
+                SqlExpr::Cube(elements) => {
+                    let elements = self.process_grouping_elements(elements, &projections)?;
+                    polars_ensure!(
+                        elements.len() < 32,
+                        ComputeError: "CUBE supports at most 31 elements, got {}", elements.len()
+                    );
+                    // every subset of the elements, from all of them down to none
+                    elements.iter().fold(vec![vec![]], |sets, element| {
+                        sets.iter()
+                            .flat_map(|set| {
+                                [[set.as_slice(), element.as_slice()].concat(), set.clone()]
+                            })
+                            .collect()
+                    })
+                }
+                SqlExpr::GroupingSets(sets) => {
+                    self.process_grouping_elements(sets, &projections)?
+                }
+                _ => vec![vec![self.process_groupby_key(e, &projections)?]],
+            };
+            has_grouping_sets |= matches!(
+                e,
+                SqlExpr::Rollup(_) | SqlExpr::Cube(_) | SqlExpr::GroupingSets(_)
+            );
+            grouping_sets = grouping_sets
+                .iter()
+                .flat_map(|prefix| {
+                    item_sets
+                        .iter()
+                        .map(move |set| [prefix.as_slice(), set.as_slice()].concat())
+                })
+                .collect();
+        }
 
-        if groupby_keys.is_empty() {
-            lf = lf.select(projections)
-        } else {
-            lf = self.process_groupby(lf, contains_wildcard, &groupby_keys, &projections)?;
+        let having = select_stmt
+            .having
+            .as_ref()
+            .map(|expr| parse_sql_expr(expr, self))
+            .transpose()?;
+        let qualify = select_stmt
+            .qualify
+            .as_ref()
+            .map(|expr| parse_sql_expr(expr, self))
+            .transpose()?;
+
+        // Scalar subqueries referenced above are cross joined as one-row frames.
+        let (joined, subquery_columns) = self.join_subqueries(lf);
+        lf = joined;
+        if let Some(filter_expression) = filter_expression {
+            lf = lf.filter(filter_expression);
+        }
 
-            // Apply optional 'having' clause, post-aggregation
-            lf = match select_stmt.having.as_ref() {
-                Some(expr) => lf.filter(parse_sql_expr(expr, self)?),
-                None => lf,
+        if select_stmt.group_by.is_empty() {
+            // 'having' without 'group by' aggregates over the whole table, and 'qualify'
+            // is evaluated on the same rows as the window functions of the projections.
+            // Both are computed alongside the projections and filtered on afterwards.
+            let mut exprs = projections;
+            let mut hidden_columns = vec![];
+            for (predicate, name) in [(having, HAVING_COLUMN), (qualify, QUALIFY_COLUMN)] {
+                if let Some(predicate) = predicate {
+                    exprs.push(expand_projection_aliases(predicate, &exprs).alias(name));
+                    hidden_columns.push(name);
+                }
+            }
+            lf = lf.select(exprs);
+            if !hidden_columns.is_empty() {
+                let predicate = hidden_columns
+                    .iter()
+                    .map(|name| col(name))
+                    .reduce(|acc, e| acc.and(e))
+                    .unwrap();
+                lf = lf.filter(predicate).drop_columns(hidden_columns);
+            }
+            if contains_wildcard && !subquery_columns.is_empty() {
+                lf = lf.drop_columns(subquery_columns);
+            }
+        } else {
+            // Apply optional 'having' clause. This filters the rows of the groups that do not
+            // qualify before the aggregation, by evaluating the predicate over each group.
+            // With grouping sets the groups differ per set, so there it can only refer to the
+            // output columns and is applied post-aggregation.
+            let post_aggregation_having = match having {
+                Some(having) if !has_grouping_sets => {
+                    let having = expand_projection_aliases(having, &projections);
+                    lf = lf.filter(having.over(&grouping_sets[0]));
+                    None
+                }
+                having => having,
             };
+
+            lf = self.process_groupby(
+                lf,
+                contains_wildcard,
+                &grouping_sets,
+                has_grouping_sets,
+                &projections,
+            )?;
+
+            if let Some(having) = post_aggregation_having {
+                lf = lf.filter(having);
+            }
+            // Apply optional 'qualify' clause, on the aggregated result
+            if let Some(qualify) = qualify {
+                lf = lf.filter(qualify);
+            }
         };
 
         // Apply optional 'distinct' clause
@@ -372,18 +941,18 @@ impl SQLContext {
                     .collect::<PolarsResult<Vec<_>>>()?;
 
                 // DISTINCT ON applies the ORDER BY before the operation.
-                if !query.order_by.is_empty() {
-                    lf = self.process_order_by(lf, &query.order_by)?;
+                if !order_by.is_empty() {
+                    lf = self.process_order_by(lf, order_by)?;
                 }
                 return Ok(lf.unique_stable(Some(cols), UniqueKeepStrategy::First));
             }
             None => lf,
         };
 
-        if query.order_by.is_empty() {
+        if order_by.is_empty() {
             Ok(lf)
         } else {
-            self.process_order_by(lf, &query.order_by)
+            self.process_order_by(lf, order_by)
         }
     }
 
@@ -418,6 +987,264 @@ impl SQLContext {
         }
     }
 
+    /// Get a table that was registered in the context, as the target of a DML statement.
+    fn get_registered_table(&self, name: &ObjectName) -> PolarsResult<(String, LazyFrame)> {
+        let tbl_name = name.0.get(0).unwrap().value.as_str();
+        match self.table_map.get(tbl_name) {
+            Some(lf) => Ok((tbl_name.to_string(), lf.clone())),
+            None => polars_bail!(ComputeError: "relation '{}' was not found", tbl_name),
+        }
+    }
+
+    fn dml_response(response: &str) -> LazyFrame {
+        df! {
+            "Response" => [response]
+        }
+        .unwrap()
+        .lazy()
+    }
+
+    /// COPY { (query) | table } TO 'path' [(option value, ...)]
+    ///
+    /// The result is written with the streaming engine where possible.
+    #[cfg(any(
+        feature = "parquet",
+        feature = "ipc",
+        feature = "csv",
+        feature = "json"
+    ))]
+    fn execute_copy(&mut self, stmt: CopyStatement) -> PolarsResult<LazyFrame> {
+        let lf = match &stmt.source {
+            CopySource::Query(query) => self.execute_query(query)?,
+            CopySource::Table(name) => self.get_registered_table(name)?.1,
+        };
+        stmt.sink(lf)?;
+        Ok(Self::dml_response("Copy"))
+    }
+
+    /// INSERT INTO t [(columns)] SELECT ... | VALUES ...
+    ///
+    /// The rows are appended to the registered table. Columns of the table that are
+    /// not listed get null values and the inserted values are cast to the table's types.
+    fn execute_insert(&mut self, stmt: &Statement) -> PolarsResult<LazyFrame> {
+        let Statement::Insert {
+            table_name,
+            columns,
+            source,
+            overwrite,
+            ..
+        } = stmt else {
+            unreachable!()
+        };
+        let (tbl_name, lf) = self.get_registered_table(table_name)?;
+        let schema = lf.schema()?;
+        let source = self.execute_query(source)?;
+        let source_names = source.schema()?.iter_names().cloned().collect::<Vec<_>>();
+
+        let target_names = if columns.is_empty() {
+            schema
+                .iter_names()
+                .map(|name| name.to_string())
+                .collect::<Vec<_>>()
+        } else {
+            columns.iter().map(|c| c.value.clone()).collect::<Vec<_>>()
+        };
+        polars_ensure!(
+            target_names.len() == source_names.len(),
+            ComputeError: "INSERT INTO {} expects {} columns, got {}",
+            tbl_name, target_names.len(), source_names.len()
+        );
+        for name in &target_names {
+            polars_ensure!(
+                schema.contains(name),
+                ColumnNotFound: "column '{}' not found in relation '{}'", name, tbl_name
+            );
+        }
+
+        // match the source columns positionally to the target columns
+        let exprs = schema
+            .iter()
+            .map(|(name, dtype)| {
+                let value = match target_names.iter().position(|n| n == name.as_str()) {
+                    Some(i) => col(&source_names[i]),
+                    None => lit(Null {}),
+                };
+                value.cast(dtype.clone()).alias(name)
+            })
+            .collect::<Vec<_>>();
+        let inserted = source.select(exprs);
+        let lf = if *overwrite {
+            inserted
+        } else {
+            polars_lazy::dsl::concat([lf, inserted], UnionArgs::default())?
+        };
+        self.table_map.insert(tbl_name, lf);
+        Ok(Self::dml_response("Insert Into"))
+    }
+
+    /// DELETE FROM t [WHERE ...]
+    ///
+    /// Rows for which the predicate is not true are kept.
+    fn execute_delete(&mut self, stmt: &Statement) -> PolarsResult<LazyFrame> {
+        let Statement::Delete {
+            from, selection, ..
+        } = stmt else {
+            unreachable!()
+        };
+        let name = match from.as_slice() {
+            [TableWithJoins {
+                relation: TableFactor::Table { name, .. },
+                joins,
+            }] if joins.is_empty() => name,
+            _ => polars_bail!(ComputeError: "DELETE is only supported on a single table"),
+        };
+        let (tbl_name, lf) = self.get_registered_table(name)?;
+        self.set_input_schema(&lf)?;
+        let lf = match selection {
+            Some(selection) => {
+                let predicate = parse_sql_expr(selection, self)?;
+                let (lf, subquery_columns) = self.join_subqueries(lf);
+                let lf = lf.filter(predicate.fill_null(lit(false)).not());
+                if subquery_columns.is_empty() {
+                    lf
+                } else {
+                    lf.drop_columns(subquery_columns)
+                }
+            }
+            None => lf.limit(0),
+        };
+        self.table_map.insert(tbl_name, lf);
+        Ok(Self::dml_response("Delete From"))
+    }
+
+    /// UPDATE t SET c = expr, ... [WHERE ...]
+    ///
+    /// All assignments see the values of the row before the update, and the
+    /// updated columns keep their type.
+    fn execute_update(&mut self, stmt: &Statement) -> PolarsResult<LazyFrame> {
+        let Statement::Update {
+            table,
+            assignments,
+            from,
+            selection,
+            ..
+        } = stmt else {
+            unreachable!()
+        };
+        let name = match table {
+            TableWithJoins {
+                relation: TableFactor::Table { name, .. },
+                joins,
+            } if joins.is_empty() && from.is_none() => name,
+            _ => polars_bail!(ComputeError: "UPDATE is only supported on a single table"),
+        };
+        let (tbl_name, lf) = self.get_registered_table(name)?;
+        let schema = lf.schema()?;
+        self.set_input_schema(&lf)?;
+        let predicate = selection
+            .as_ref()
+            .map(|selection| parse_sql_expr(selection, self))
+            .transpose()?;
+
+        let mut updates = Vec::with_capacity(assignments.len());
+        for assignment in assignments {
+            let column = match assignment.id.as_slice() {
+                [column] | [_, column] => column.value.as_str(),
+                _ => polars_bail!(
+                    ComputeError: "invalid column in UPDATE: {}",
+                    assignment.id.iter().map(|i| i.value.as_str()).collect::<Vec<_>>().join(".")
+                ),
+            };
+            let dtype = schema.try_get(column)?;
+            let value = parse_sql_expr(&assignment.value, self)?;
+            let value = match &predicate {
+                Some(predicate) => when(predicate.clone().fill_null(lit(false)))
+                    .then(value)
+                    .otherwise(col(column)),
+                None => value,
+            };
+            updates.push(value.cast(dtype.clone()).alias(column));
+        }
+        let (lf, subquery_columns) = self.join_subqueries(lf);
+        let mut lf = lf.with_columns(updates);
+        if !subquery_columns.is_empty() {
+            lf = lf.drop_columns(subquery_columns);
+        }
+        self.table_map.insert(tbl_name, lf);
+        Ok(Self::dml_response("Update"))
+    }
+
+    /// TRUNCATE [TABLE] t
+    fn execute_truncate(&mut self, stmt: &Statement) -> PolarsResult<LazyFrame> {
+        let Statement::Truncate { table_name, .. } = stmt else {
+            unreachable!()
+        };
+        let (tbl_name, lf) = self.get_registered_table(table_name)?;
+        self.table_map.insert(tbl_name, lf.limit(0));
+        Ok(Self::dml_response("Truncate"))
+    }
+
+    /// ALTER TABLE t RENAME TO u | RENAME COLUMN a TO b | ADD COLUMN c type [DEFAULT expr] | DROP COLUMN c
+    fn execute_alter_table(&mut self, stmt: &Statement) -> PolarsResult<LazyFrame> {
+        let Statement::AlterTable { name, operation } = stmt else {
+            unreachable!()
+        };
+        let (tbl_name, lf) = self.get_registered_table(name)?;
+        match operation {
+            AlterTableOperation::RenameTable { table_name } => {
+                let new_name = table_name.0.get(0).unwrap().value.as_str();
+                polars_ensure!(
+                    !self.table_map.contains_key(new_name),
+                    ComputeError: "relation {} already exists", new_name
+                );
+                self.table_map.remove(&tbl_name);
+                self.table_map.insert(new_name.to_string(), lf);
+            }
+            AlterTableOperation::RenameColumn {
+                old_column_name,
+                new_column_name,
+            } => {
+                let schema = lf.schema()?;
+                schema.try_get(&old_column_name.value)?;
+                polars_ensure!(
+                    !schema.contains(&new_column_name.value),
+                    Duplicate: "column '{}' already exists in relation '{}'",
+                    new_column_name.value, tbl_name
+                );
+                let lf = lf.rename([&old_column_name.value], [&new_column_name.value]);
+                self.table_map.insert(tbl_name, lf);
+            }
+            AlterTableOperation::AddColumn { column_def, .. } => {
+                let column = column_def.name.value.as_str();
+                polars_ensure!(
+                    !lf.schema()?.contains(column),
+                    Duplicate: "column '{}' already exists in relation '{}'", column, tbl_name
+                );
+                let dtype = map_sql_polars_datatype(&column_def.data_type)?;
+                let default = column_def
+                    .options
+                    .iter()
+                    .find_map(|option| match &option.option {
+                        ColumnOption::Default(e) => Some(e),
+                        _ => None,
+                    });
+                let value = match default {
+                    Some(e) => parse_sql_expr(e, self)?,
+                    None => lit(Null {}),
+                };
+                let lf = lf.with_column(value.cast(dtype).alias(column));
+                self.table_map.insert(tbl_name, lf);
+            }
+            AlterTableOperation::DropColumn { column_name, .. } => {
+                lf.schema()?.try_get(&column_name.value)?;
+                let lf = lf.drop_columns([&column_name.value]);
+                self.table_map.insert(tbl_name, lf);
+            }
+            op => polars_bail!(ComputeError: "ALTER TABLE {} is not supported", op),
+        }
+        Ok(Self::dml_response("Alter Table"))
+    }
+
     fn get_table(&mut self, relation: &TableFactor) -> PolarsResult<(String, LazyFrame)> {
         match relation {
             TableFactor::Table {
@@ -460,10 +1287,55 @@ impl SQLContext {
         Ok((tbl_name, lf))
     }
 
+    fn process_in_subquery(
+        &mut self,
+        lf: LazyFrame,
+        expr: &SqlExpr,
+        subquery: &Query,
+        negated: bool,
+    ) -> PolarsResult<LazyFrame> {
+        let left_on = parse_sql_expr(expr, self)?;
+        let subquery_lf = self.execute_subquery(subquery)?;
+        let name = subquery_column(&subquery_lf)?;
+        let right_on = col(&name);
+        if !negated {
+            // a NULL is not IN any set
+            return Ok(lf.filter(left_on.clone().is_not_null()).join(
+                subquery_lf,
+                [left_on],
+                [right_on],
+                JoinArgs::new(JoinType::Semi),
+            ));
+        }
+
+        // `a NOT IN (...)` is NULL, so the row is filtered out, if `a` is NULL or if the
+        // subquery returns a NULL. Both don't matter if the subquery returns no rows.
+        let stats = subquery_lf.clone().select([
+            col(&name).null_count().gt(lit(0)).alias(NOT_IN_HAS_NULLS),
+            count().alias(NOT_IN_COUNT),
+        ]);
+        Ok(lf
+            .join(
+                subquery_lf,
+                [left_on.clone()],
+                [right_on],
+                JoinArgs::new(JoinType::Anti),
+            )
+            .cross_join(stats)
+            .filter(
+                col(NOT_IN_COUNT)
+                    .eq(lit(0))
+                    .or(col(NOT_IN_HAS_NULLS).not().and(left_on.is_not_null())),
+            )
+            .drop_columns([NOT_IN_HAS_NULLS, NOT_IN_COUNT]))
+    }
+
     fn process_order_by(&mut self, lf: LazyFrame, ob: &[OrderByExpr]) -> PolarsResult<LazyFrame> {
         let mut by = Vec::with_capacity(ob.len());
         let mut descending = Vec::with_capacity(ob.len());
 
+        let mut nulls_last = Vec::with_capacity(ob.len());
+
         for ob in ob {
             by.push(parse_sql_expr(&ob.expr, self)?);
             if let Some(false) = ob.asc {
@@ -471,20 +1343,58 @@ impl SQLContext {
             } else {
                 descending.push(false)
             }
-            polars_ensure!(
-                ob.nulls_first.is_none(),
-                ComputeError: "nulls first/last is not yet supported",
-            );
+            // nulls are placed first, unless 'NULLS LAST' is given
+            nulls_last.push(matches!(ob.nulls_first, Some(false)));
+        }
+
+        Ok(lf.sort_by_exprs(&by, descending, nulls_last, false))
+    }
+
+    /// Parse a GROUP BY key, which is either an expression or the number of a projection.
+    fn process_groupby_key(&self, e: &SqlExpr, projections: &[Expr]) -> PolarsResult<Expr> {
+        match e {
+            SqlExpr::Value(SQLValue::Number(idx, _)) => {
+                let idx = match idx.parse::<usize>() {
+                    Ok(0) | Err(_) => Err(polars_err!(
+                        ComputeError:
+                        "groupby error: a positive number or an expression expected, got {}",
+                        idx
+                    )),
+                    Ok(idx) => Ok(idx),
+                }?;
+                Ok(projections[idx].clone())
+            }
+            SqlExpr::Value(_) => Err(polars_err!(
+                ComputeError:
+                "groupby error: a positive number or an expression expected",
+            )),
+            _ => parse_sql_expr(e, self),
         }
+    }
 
-        Ok(lf.sort_by_exprs(&by, descending, false, false))
+    /// Parse the elements of a `ROLLUP`, `CUBE` or `GROUPING SETS` clause,
+    /// each of which is a (possibly empty) list of keys.
+    fn process_grouping_elements(
+        &self,
+        elements: &[Vec<SqlExpr>],
+        projections: &[Expr],
+    ) -> PolarsResult<Vec<Vec<Expr>>> {
+        elements
+            .iter()
+            .map(|keys| {
+                keys.iter()
+                    .map(|e| self.process_groupby_key(e, projections))
+                    .collect()
+            })
+            .collect()
     }
 
     fn process_groupby(
         &mut self,
         lf: LazyFrame,
         contains_wildcard: bool,
-        groupby_keys: &[Expr],
+        grouping_sets: &[Vec<Expr>],
+        has_grouping_sets: bool,
         projections: &[Expr],
     ) -> PolarsResult<LazyFrame> {
         // check groupby and projection due to difference between SQL and polars
@@ -495,14 +1405,40 @@ impl SQLContext {
         );
         let schema_before = lf.schema()?;
 
-        let groupby_keys_schema =
-            expressions_to_schema(groupby_keys, &schema_before, Context::Default)?;
+        // the keys of all grouping sets, in order of first appearance
+        let mut groupby_keys = vec![];
+        let mut groupby_keys_schema = Schema::new();
+        for e in grouping_sets.iter().flatten() {
+            let field = e.to_field(&schema_before, Context::Default)?;
+            if groupby_keys_schema.get(&field.name).is_none() {
+                groupby_keys.push(e.clone());
+                groupby_keys_schema.with_column(field.name, field.dtype);
+            }
+        }
+
+        // `GROUPING(key)` refers to an indicator column per key, which is added after the groupby
+        let uses_grouping = |e: &Expr| {
+            expr_to_leaf_column_names(e)
+                .iter()
+                .any(|name| name.starts_with(GROUPING_PREFIX))
+        };
+        let grouping_columns: Vec<String> = groupby_keys_schema
+            .iter_names()
+            .map(|name| format!("{GROUPING_PREFIX}{name}"))
+            .collect();
+        let mut schema_with_grouping = (*schema_before).clone();
+        for name in &grouping_columns {
+            schema_with_grouping.with_column(name.as_str().into(), DataType::Int32);
+        }
 
         // remove the groupby keys as polars adds those implicitly
         let mut aggregation_projection = Vec::with_capacity(projections.len());
         let mut aliases: BTreeSet<&str> = BTreeSet::new();
 
         for mut e in projections {
+            if uses_grouping(e) {
+                continue;
+            }
             // if it is a simple expression & has alias,
             // we must defer the aliasing until after the groupby
             if e.clone().meta().is_simple_projection() {
@@ -518,15 +1454,42 @@ impl SQLContext {
             }
         }
 
-        let aggregated = lf.groupby(groupby_keys).agg(&aggregation_projection);
+        let aggregated = if has_grouping_sets {
+            // each bit of the grouping id tells whether a key was aggregated away
+            let n = grouping_columns.len();
+            let indicators = grouping_columns
+                .iter()
+                .enumerate()
+                .map(|(i, name)| {
+                    (col(GROUPING_ID).floor_div(lit(1i32 << (n - 1 - i))) % lit(2i32)).alias(name)
+                })
+                .collect::<Vec<_>>();
+            lf.groupby_grouping_sets(grouping_sets, &aggregation_projection, Some(GROUPING_ID))?
+                .with_columns(indicators)
+        } else {
+            let aggregated = lf.groupby(&groupby_keys).agg(&aggregation_projection);
+            if projections.iter().any(uses_grouping) {
+                aggregated.with_columns(
+                    grouping_columns
+                        .iter()
+                        .map(|name| lit(0i32).alias(name))
+                        .collect::<Vec<_>>(),
+                )
+            } else {
+                aggregated
+            }
+        };
         let projection_schema =
-            expressions_to_schema(projections, &schema_before, Context::Default)?;
+            expressions_to_schema(projections, &schema_with_grouping, Context::Default)?;
         // a final projection to get the proper order
         let final_projection = projection_schema
             .iter_names()
             .zip(projections)
             .map(|(name, projection_expr)| {
-                if groupby_keys_schema.get(name).is_some() || aliases.contains(name.as_str()) {
+                if groupby_keys_schema.get(name).is_some()
+                    || aliases.contains(name.as_str())
+                    || uses_grouping(projection_expr)
+                {
                     projection_expr.clone()
                 } else {
                     col(name)
@@ -625,6 +1588,55 @@ impl SQLContext {
     }
 }
 
+/// Replace references to the aliases of `projections` by the aliased expressions, so that
+/// `expr` can be evaluated on the input of the projections.
+fn expand_projection_aliases(mut expr: Expr, projections: &[Expr]) -> Expr {
+    let aliases: PlHashMap<&str, &Expr> = projections
+        .iter()
+        .filter_map(|e| match e {
+            Expr::Alias(inner, name) => Some((name.as_ref(), inner.as_ref())),
+            _ => None,
+        })
+        .collect();
+    if aliases.is_empty() {
+        return expr;
+    }
+
+    // mark the references first, as an alias may shadow a column
+    // that is used by the aliased expression itself
+    expr.mutate().apply(|e| {
+        if let Expr::Column(name) = e {
+            if aliases.contains_key(name.as_ref()) {
+                *e = Expr::Column(format!("{ALIAS_MARKER}{name}").into());
+            }
+        }
+        true
+    });
+    expr.mutate().apply(|e| {
+        let aliased = match e {
+            Expr::Column(name) => name
+                .strip_prefix(ALIAS_MARKER)
+                .map(|name| aliases[name].clone()),
+            _ => None,
+        };
+        if let Some(aliased) = aliased {
+            *e = aliased;
+        }
+        true
+    });
+    expr
+}
+
+/// Get the name of the only column a subquery returns.
+pub(crate) fn subquery_column(lf: &LazyFrame) -> PolarsResult<String> {
+    let schema = lf.schema()?;
+    polars_ensure!(
+        schema.len() == 1,
+        ComputeError: "subquery must return a single column, got {}", schema.len()
+    );
+    Ok(schema.get_at_index(0).unwrap().0.to_string())
+}
+
 impl SQLContext {
     /// Get internal table map. For internal use only.
     pub fn get_table_map(&self) -> PlHashMap<String, LazyFrame> {
@@ -636,6 +1648,10 @@ impl SQLContext {
         Self {
             table_map,
             cte_map: RefCell::new(PlHashMap::new()),
+            subquery_frames: RefCell::new(Vec::new()),
+            params: PlHashMap::new(),
+            named_windows: PlHashMap::new(),
+            input_schema: None,
         }
     }
 }
@@ -0,0 +1,280 @@
+use std::path::{Path, PathBuf};
+
+use polars_core::prelude::*;
+use polars_lazy::prelude::*;
+use sqlparser::ast::{ObjectName, Query};
+use sqlparser::keywords::Keyword;
+use sqlparser::parser::{Parser, ParserError};
+use sqlparser::tokenizer::Token;
+
+/// What a COPY statement writes.
+pub(crate) enum CopySource {
+    Query(Box<Query>),
+    Table(ObjectName),
+}
+
+/// `COPY { (query) | table } TO 'path' [WITH] [(option value, ...)]`
+///
+/// The statement is not supported by the SQL parser, so it is parsed here.
+pub(crate) struct CopyStatement {
+    pub(crate) source: CopySource,
+    pub(crate) target: PathBuf,
+    options: Vec<(String, CopyOptionValue)>,
+}
+
+enum CopyOptionValue {
+    Single(String),
+    List(Vec<String>),
+}
+
+impl CopyStatement {
+    /// Parse the remainder of a COPY statement, after the COPY keyword.
+    pub(crate) fn parse(parser: &mut Parser) -> Result<Self, ParserError> {
+        let source = if parser.consume_token(&Token::LParen) {
+            let query = parser.parse_query()?;
+            parser.expect_token(&Token::RParen)?;
+            CopySource::Query(Box::new(query))
+        } else {
+            CopySource::Table(parser.parse_object_name()?)
+        };
+        parser.expect_keyword(Keyword::TO)?;
+        let target = parser.parse_literal_string()?.into();
+
+        let mut options = vec![];
+        let has_with = parser.parse_keyword(Keyword::WITH);
+        if parser.consume_token(&Token::LParen) {
+            options = parser.parse_comma_separated(Self::parse_option)?;
+            parser.expect_token(&Token::RParen)?;
+        } else if has_with {
+            return parser.expected("(", parser.peek_token());
+        }
+
+        parser.consume_token(&Token::SemiColon);
+        if parser.peek_token().token != Token::EOF {
+            return parser.expected("end of statement", parser.peek_token());
+        }
+        Ok(Self {
+            source,
+            target,
+            options,
+        })
+    }
+
+    fn parse_option(parser: &mut Parser) -> Result<(String, CopyOptionValue), ParserError> {
+        let name = parser.parse_identifier()?.value.to_uppercase();
+        let value = if parser.consume_token(&Token::LParen) {
+            let values = parser.parse_comma_separated(|p| Ok(p.parse_identifier()?.value))?;
+            parser.expect_token(&Token::RParen)?;
+            CopyOptionValue::List(values)
+        } else {
+            let token = parser.next_token();
+            match token.token {
+                Token::Word(w) => CopyOptionValue::Single(w.value),
+                Token::SingleQuotedString(s) => CopyOptionValue::Single(s),
+                Token::Number(n, _) => CopyOptionValue::Single(n),
+                _ => return parser.expected("an option value", token),
+            }
+        };
+        Ok((name, value))
+    }
+
+    fn take_option(&mut self, name: &str) -> Option<CopyOptionValue> {
+        let idx = self.options.iter().position(|(n, _)| n == name)?;
+        Some(self.options.remove(idx).1)
+    }
+
+    fn take_single(&mut self, name: &str) -> PolarsResult<Option<String>> {
+        match self.take_option(name) {
+            None => Ok(None),
+            Some(CopyOptionValue::Single(value)) => Ok(Some(value)),
+            Some(CopyOptionValue::List(_)) => {
+                polars_bail!(ComputeError: "COPY option {} expects a single value", name)
+            }
+        }
+    }
+
+    fn take_bool(&mut self, name: &str) -> PolarsResult<Option<bool>> {
+        self.take_single(name)?
+            .map(|value| match value.to_lowercase().as_str() {
+                "true" | "on" | "1" => Ok(true),
+                "false" | "off" | "0" => Ok(false),
+                _ => polars_bail!(
+                    ComputeError: "COPY option {} expects a boolean, got '{}'", name, value
+                ),
+            })
+            .transpose()
+    }
+
+    fn take_usize(&mut self, name: &str) -> PolarsResult<Option<usize>> {
+        self.take_single(name)?
+            .map(|value| {
+                value.parse::<usize>().map_err(|_| {
+                    polars_err!(
+                        ComputeError: "COPY option {} expects a positive integer, got '{}'",
+                        name, value
+                    )
+                })
+            })
+            .transpose()
+    }
+
+    /// Write `lf` to the target of the statement. The result is streamed to the file
+    /// if the query can be run in streaming fashion, otherwise it is collected first.
+    pub(crate) fn sink(mut self, lf: LazyFrame) -> PolarsResult<()> {
+        let partition_by = match self.take_option("PARTITION_BY") {
+            None => None,
+            Some(CopyOptionValue::List(columns)) => Some(columns),
+            Some(CopyOptionValue::Single(column)) => Some(vec![column]),
+        };
+        let format = match self.take_single("FORMAT")? {
+            Some(format) => format.to_lowercase(),
+            // a partitioned dataset is a directory
+            None if partition_by.is_some() => "parquet".to_string(),
+            None => infer_format(&self.target)?,
+        };
+        polars_ensure!(
+            partition_by.is_none() || format == "parquet",
+            ComputeError: "COPY with PARTITION_BY is only supported for FORMAT parquet"
+        );
+
+        let sink: Box<dyn Fn(LazyFrame) -> PolarsResult<()>> = match format.as_str() {
+            #[cfg(feature = "parquet")]
+            "parquet" => {
+                let mut options = ParquetWriteOptions {
+                    maintain_order: true,
+                    ..Default::default()
+                };
+                if let Some(compression) = self.take_single("COMPRESSION")? {
+                    options.compression = parse_parquet_compression(&compression)?;
+                }
+                options.row_group_size = self.take_usize("ROW_GROUP_SIZE")?;
+                if let Some(statistics) = self.take_bool("STATISTICS")? {
+                    options.statistics = statistics;
+                }
+                let target = self.target.clone();
+                match partition_by {
+                    Some(partition_by) => {
+                        let options = PartitionedParquetWriteOptions {
+                            parquet: options,
+                            ..PartitionedParquetWriteOptions::new(partition_by)
+                        };
+                        Box::new(move |lf: LazyFrame| {
+                            lf.sink_parquet_partitioned(target.clone(), options.clone())
+                        })
+                    }
+                    None => Box::new(move |lf: LazyFrame| lf.sink_parquet(target.clone(), options)),
+                }
+            }
+            #[cfg(feature = "ipc")]
+            "ipc" | "arrow" => {
+                let mut options = IpcWriterOptions {
+                    maintain_order: true,
+                    ..Default::default()
+                };
+                if let Some(compression) = self.take_single("COMPRESSION")? {
+                    options.compression = parse_ipc_compression(&compression)?;
+                }
+                let target = self.target.clone();
+                Box::new(move |lf: LazyFrame| lf.sink_ipc(target.clone(), options))
+            }
+            #[cfg(feature = "csv")]
+            "csv" => {
+                let mut options = CsvWriterOptions {
+                    maintain_order: true,
+                    ..Default::default()
+                };
+                if let Some(header) = self.take_bool("HEADER")? {
+                    options.include_header = header;
+                }
+                if let Some(delimiter) = self.take_single("DELIMITER")? {
+                    polars_ensure!(
+                        delimiter.len() == 1,
+                        ComputeError: "COPY option DELIMITER expects a single byte, got '{}'",
+                        delimiter
+                    );
+                    options.serialize_options.delimiter = delimiter.as_bytes()[0];
+                }
+                let target = self.target.clone();
+                Box::new(move |lf: LazyFrame| lf.sink_csv(target.clone(), options.clone()))
+            }
+            #[cfg(feature = "json")]
+            "json" | "ndjson" => {
+                let options = JsonWriterOptions {
+                    maintain_order: true,
+                };
+                let target = self.target.clone();
+                Box::new(move |lf: LazyFrame| lf.sink_ndjson(target.clone(), options))
+            }
+            _ => polars_bail!(ComputeError: "COPY does not support FORMAT {}", format),
+        };
+        if let Some((name, _)) = self.options.first() {
+            polars_bail!(
+                ComputeError: "COPY option {} is not supported for FORMAT {}", name, format
+            );
+        }
+
+        sink_or_collect(lf, sink)
+    }
+}
+
+/// Write the result of `lf` with one of the `sink_*` methods of [`LazyFrame`]. The result is
+/// streamed if the query can be run in streaming fashion, otherwise it is collected first.
+pub fn sink_or_collect<F>(lf: LazyFrame, sink: F) -> PolarsResult<()>
+where
+    F: FnOnce(LazyFrame) -> PolarsResult<()>,
+{
+    if lf.is_streamable()? {
+        sink(lf)
+    } else {
+        sink(lf.collect()?.lazy())
+    }
+}
+
+fn infer_format(path: &Path) -> PolarsResult<String> {
+    let extension = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.to_lowercase());
+    Ok(match extension.as_deref() {
+        Some("parquet" | "pq") => "parquet",
+        Some("ipc" | "arrow" | "feather") => "ipc",
+        Some("csv") => "csv",
+        Some("json" | "ndjson" | "jsonl") => "ndjson",
+        _ => polars_bail!(
+            ComputeError: "cannot infer the format of '{}'; add a FORMAT option", path.display()
+        ),
+    }
+    .to_string())
+}
+
+#[cfg(feature = "parquet")]
+fn parse_parquet_compression(
+    compression: &str,
+) -> PolarsResult<polars_io::parquet::ParquetCompression> {
+    use polars_io::parquet::ParquetCompression;
+
+    Ok(match compression.to_lowercase().as_str() {
+        "uncompressed" => ParquetCompression::Uncompressed,
+        "snappy" => ParquetCompression::Snappy,
+        "gzip" => ParquetCompression::Gzip(None),
+        "lzo" => ParquetCompression::Lzo,
+        "brotli" => ParquetCompression::Brotli(None),
+        "zstd" => ParquetCompression::Zstd(None),
+        "lz4" => ParquetCompression::Lz4Raw,
+        _ => polars_bail!(ComputeError: "unsupported parquet COMPRESSION '{}'", compression),
+    })
+}
+
+#[cfg(feature = "ipc")]
+fn parse_ipc_compression(
+    compression: &str,
+) -> PolarsResult<Option<polars_io::ipc::IpcCompression>> {
+    use polars_io::ipc::IpcCompression;
+
+    Ok(match compression.to_lowercase().as_str() {
+        "uncompressed" => None,
+        "lz4" => Some(IpcCompression::LZ4),
+        "zstd" => Some(IpcCompression::ZSTD),
+        _ => polars_bail!(ComputeError: "unsupported ipc COMPRESSION '{}'", compression),
+    })
+}
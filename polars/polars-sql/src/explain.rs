@@ -0,0 +1,48 @@
+use polars_core::prelude::*;
+use polars_lazy::prelude::*;
+use polars_plan::logical_plan::node_to_lp;
+use polars_plan::prelude::*;
+
+/// Run the query and annotate the lines of its optimized plan with the number of rows
+/// and the time in microseconds of the node they describe. Lines that don't start a
+/// node, or whose node wasn't profiled, have no annotation.
+pub(crate) fn explain_analyze(lf: LazyFrame) -> PolarsResult<DataFrame> {
+    let mut expr_arena = Arena::with_capacity(64);
+    let mut lp_arena = Arena::with_capacity(64);
+    let lp_top = lf.clone().optimize(&mut lp_arena, &mut expr_arena)?;
+    let plan = node_to_lp(lp_top, &expr_arena, &mut lp_arena);
+
+    let (_, profile) = lf.profile_with_row_counts()?;
+    let plan_nodes = profile.column("plan")?.utf8()?;
+    let start = profile.column("start")?.u64()?;
+    let end = profile.column("end")?.u64()?;
+    let rows = profile.column("rows")?.idx()?;
+
+    let description = plan.describe();
+    let lines = description.split('\n').collect::<Vec<_>>();
+    let mut line_rows = vec![None; lines.len()];
+    let mut line_time = vec![None; lines.len()];
+    let mut matched = vec![false; lines.len()];
+    // the profile has the first line of the description of every node that ran. Nodes
+    // that are described the same way can't be told apart, they are assigned in the
+    // order they ran, which is bottom up
+    for j in 0..profile.height() {
+        let Some(plan_node) = plan_nodes.get(j) else {
+            continue;
+        };
+        if let Some(i) = (0..lines.len())
+            .rev()
+            .find(|&i| !matched[i] && lines[i].trim() == plan_node)
+        {
+            matched[i] = true;
+            line_rows[i] = rows.get(j);
+            line_time[i] = end.get(j).zip(start.get(j)).map(|(end, start)| end - start);
+        }
+    }
+
+    DataFrame::new(vec![
+        Series::new("Logical Plan", lines),
+        Series::new("rows", line_rows),
+        Series::new("time (µs)", line_time),
+    ])
+}
@@ -1,13 +1,19 @@
-use polars_core::prelude::{polars_bail, polars_err, PolarsError, PolarsResult};
+use polars_core::prelude::{
+    polars_bail, polars_ensure, polars_err, DataType, IdxSize, Null, PolarsError, PolarsResult,
+    RankMethod, RankOptions, TimeUnit, IDX_DTYPE,
+};
 use polars_lazy::dsl::Expr;
-use polars_plan::dsl::count;
+use polars_lazy::prelude::{Duration, RollingOptions, StrptimeOptions, TruncateOptions};
+use polars_plan::dsl::{arg_sort_by, count, int_range};
 use polars_plan::logical_plan::LiteralValue;
-use polars_plan::prelude::lit;
+use polars_plan::prelude::{col, lit, when};
+use polars_plan::utils::expr_output_name;
 use sqlparser::ast::{
-    Expr as SqlExpr, Function as SQLFunction, FunctionArg, FunctionArgExpr, Value as SqlValue,
-    WindowSpec, WindowType,
+    Expr as SqlExpr, Function as SQLFunction, FunctionArg, FunctionArgExpr, OrderByExpr,
+    Value as SqlValue, WindowFrameBound, WindowFrameUnits, WindowSpec, WindowType,
 };
 
+use crate::context::GROUPING_PREFIX;
 use crate::sql_expr::parse_sql_expr;
 use crate::SQLContext;
 
@@ -223,6 +229,52 @@ pub(crate) enum PolarsSqlFunctions {
     /// ```
     Upper,
 
+    // ----
+    // Temporal functions
+    // ----
+    /// SQL 'date_part' function
+    /// Extracts a part of a date or datetime, also available as `EXTRACT(part FROM column_1)`
+    /// ```sql
+    /// SELECT DATE_PART('year', column_1) from df;
+    /// ```
+    DatePart,
+    /// SQL 'date_trunc' function
+    /// Truncates a date or datetime to the given precision
+    /// ```sql
+    /// SELECT DATE_TRUNC('month', column_1) from df;
+    /// ```
+    DateTrunc,
+    /// SQL 'datediff' function
+    /// Returns the number of part boundaries crossed between two dates or datetimes
+    /// ```sql
+    /// SELECT DATEDIFF('day', column_1, column_2) from df;
+    /// ```
+    DateDiff,
+    /// SQL 'now' function
+    /// Returns the current local datetime, also available as `CURRENT_TIMESTAMP`
+    /// ```sql
+    /// SELECT NOW() from df;
+    /// ```
+    Now,
+    /// SQL 'current_date' function
+    /// Returns the current local date
+    /// ```sql
+    /// SELECT CURRENT_DATE from df;
+    /// ```
+    CurrentDate,
+    /// SQL 'strftime' function
+    /// Formats a date or datetime as a string
+    /// ```sql
+    /// SELECT STRFTIME(column_1, '%Y-%m-%d') from df;
+    /// ```
+    Strftime,
+    /// SQL 'strptime' function
+    /// Parses a string to a datetime
+    /// ```sql
+    /// SELECT STRPTIME(column_1, '%Y-%m-%d %H:%M:%S') from df;
+    /// ```
+    Strptime,
+
     // ----
     // Aggregate functions
     // ----
@@ -274,6 +326,53 @@ pub(crate) enum PolarsSqlFunctions {
     /// SELECT LAST(column_1) from df;
     /// ```
     Last,
+    /// SQL 'grouping' function
+    /// Returns a bitmask of the given keys that are aggregated away in the row,
+    /// for use with `ROLLUP`, `CUBE` and `GROUPING SETS`
+    /// ```sql
+    /// SELECT GROUPING(column_1), SUM(column_2) from df GROUP BY ROLLUP(column_1);
+    /// ```
+    Grouping,
+
+    // ----
+    // Window functions
+    // ----
+    /// SQL 'row_number' function
+    /// Returns the number of the row within its window, starting at 1
+    /// ```sql
+    /// SELECT ROW_NUMBER() OVER (PARTITION BY column_1 ORDER BY column_2) from df;
+    /// ```
+    RowNumber,
+    /// SQL 'rank' function
+    /// Returns the rank of the row within its window, with gaps for ties
+    /// ```sql
+    /// SELECT RANK() OVER (ORDER BY column_1) from df;
+    /// ```
+    Rank,
+    /// SQL 'dense_rank' function
+    /// Returns the rank of the row within its window, without gaps for ties
+    /// ```sql
+    /// SELECT DENSE_RANK() OVER (ORDER BY column_1) from df;
+    /// ```
+    DenseRank,
+    /// SQL 'lag' function
+    /// Returns the value of the row `offset` rows before the current row within its window
+    /// ```sql
+    /// SELECT LAG(column_1, 1, 0) OVER (ORDER BY column_2) from df;
+    /// ```
+    Lag,
+    /// SQL 'lead' function
+    /// Returns the value of the row `offset` rows after the current row within its window
+    /// ```sql
+    /// SELECT LEAD(column_1) OVER (ORDER BY column_2) from df;
+    /// ```
+    Lead,
+    /// SQL 'ntile' function
+    /// Divides the rows of the window into `n` buckets, numbered from 1
+    /// ```sql
+    /// SELECT NTILE(4) OVER (ORDER BY column_1) from df;
+    /// ```
+    Ntile,
 
     // ----
     // Array functions
@@ -377,12 +476,20 @@ impl PolarsSqlFunctions {
             "cot",
             "cotd",
             "count",
+            "current_date",
+            "date_part",
+            "date_trunc",
+            "datediff",
             "degrees",
+            "dense_rank",
             "ends_with",
             "exp",
             "first",
             "floor",
+            "grouping",
+            "lag",
             "last",
+            "lead",
             "len",
             "length",
             "ln",
@@ -394,18 +501,24 @@ impl PolarsSqlFunctions {
             "ltrim",
             "max",
             "min",
+            "now",
+            "ntile",
             "octet_length",
             "pi",
             "pow",
             "power",
             "radians",
+            "rank",
             "round",
+            "row_number",
             "rtrim",
             "sin",
             "sind",
             "sqrt",
             "starts_with",
             "stddev",
+            "strftime",
+            "strptime",
             "sum",
             "tan",
             "tan",
@@ -472,12 +585,24 @@ impl TryFrom<&'_ SQLFunction> for PolarsSqlFunctions {
             "substr" => Self::Substring,
             "upper" => Self::Upper,
 
+            // ----
+            // Temporal functions
+            // ----
+            "current_date" => Self::CurrentDate,
+            "date_part" => Self::DatePart,
+            "date_trunc" => Self::DateTrunc,
+            "datediff" | "date_diff" => Self::DateDiff,
+            "now" | "current_timestamp" => Self::Now,
+            "strftime" => Self::Strftime,
+            "strptime" => Self::Strptime,
+
             // ----
             // Aggregate functions
             // ----
             "avg" => Self::Avg,
             "count" => Self::Count,
             "first" => Self::First,
+            "grouping" => Self::Grouping,
             "last" => Self::Last,
             "max" => Self::Max,
             "min" => Self::Min,
@@ -485,6 +610,16 @@ impl TryFrom<&'_ SQLFunction> for PolarsSqlFunctions {
             "sum" => Self::Sum,
             "variance" | "var_samp" => Self::Variance,
 
+            // ----
+            // Window functions
+            // ----
+            "dense_rank" => Self::DenseRank,
+            "lag" => Self::Lag,
+            "lead" => Self::Lead,
+            "ntile" => Self::Ntile,
+            "rank" => Self::Rank,
+            "row_number" => Self::RowNumber,
+
             // ----
             // Array functions
             // ----
@@ -637,17 +772,49 @@ impl SqlFunctionVisitor<'_> {
             }
             Upper => self.visit_unary(|e| e.str().to_uppercase()),
             // ----
+            // Temporal functions
+            // ----
+            CurrentDate => self.visit_nullary(|| lit(chrono::Local::now().naive_local().date())),
+            DatePart => self.visit_date_part(date_part),
+            DateTrunc => self.visit_date_part(date_trunc),
+            DateDiff => self.visit_datediff(),
+            Now => self.visit_nullary(|| lit(chrono::Local::now().naive_local())),
+            Strftime => self.visit_binary(|e, fmt: String| e.dt().strftime(&fmt)),
+            Strptime => self.visit_binary(|e, fmt: String| {
+                e.str().strptime(
+                    DataType::Datetime(TimeUnit::Microseconds, None),
+                    StrptimeOptions {
+                        format: Some(fmt),
+                        ..Default::default()
+                    },
+                )
+            }),
+            // ----
             // Aggregate functions
             // ----
-            Avg => self.visit_unary(Expr::mean),
+            Avg => self.visit_unary_with_opt_frame(Expr::mean, Expr::rolling_mean, None),
             Count => self.visit_count(),
             First => self.visit_unary(Expr::first),
+            Grouping => self.visit_grouping(),
             Last => self.visit_unary(Expr::last),
-            Max => self.visit_unary_with_opt_cumulative(Expr::max, Expr::cummax),
-            Min => self.visit_unary_with_opt_cumulative(Expr::min, Expr::cummin),
-            StdDev => self.visit_unary(|e| e.std(1)),
-            Sum => self.visit_unary_with_opt_cumulative(Expr::sum, Expr::cumsum),
-            Variance => self.visit_unary(|e| e.var(1)),
+            Max => self.visit_unary_with_opt_frame(Expr::max, Expr::rolling_max, Some(Expr::cummax)),
+            Min => self.visit_unary_with_opt_frame(Expr::min, Expr::rolling_min, Some(Expr::cummin)),
+            StdDev => self.visit_unary_with_opt_frame(|e| e.std(1), Expr::rolling_std, None),
+            Sum => self.visit_unary_with_opt_frame(Expr::sum, Expr::rolling_sum, Some(Expr::cumsum)),
+            Variance => self.visit_unary_with_opt_frame(|e| e.var(1), Expr::rolling_var, None),
+
+            // ----
+            // Window functions
+            // ----
+            RowNumber => self.visit_window_nullary(|order_by| match order_by {
+                Some((by, descending)) => arg_sort_by(by, descending).arg_sort(Default::default()) + lit(1 as IdxSize),
+                None => int_range(lit(1 as IdxSize), count().cast(IDX_DTYPE) + lit(1 as IdxSize), 1),
+            }),
+            Rank => self.visit_rank(RankMethod::Min),
+            DenseRank => self.visit_rank(RankMethod::Dense),
+            Lag => self.visit_lag_lead(1),
+            Lead => self.visit_lag_lead(-1),
+            Ntile => self.visit_ntile(),
             // ----
             // Array functions
             // ----
@@ -671,27 +838,161 @@ impl SqlFunctionVisitor<'_> {
 
     /// Some functions have cumulative equivalents that can be applied to window specs
     /// e.g. SUM(a) OVER (ORDER BY b DESC) -> CUMSUM(a, false)
-    /// visit_unary_with_cumulative_window will take in a function & a cumulative function
-    /// if there is a cumulative window spec, it will apply the cumulative function,
-    /// otherwise it will apply the function
-    fn visit_unary_with_opt_cumulative(
+    /// visit_unary_with_opt_frame will take in a function, a rolling function and an optional
+    /// cumulative function. If the window spec has a frame, the rolling (or cumulative) function
+    /// is applied over that frame; if there is a cumulative window spec, it will apply the
+    /// cumulative function, otherwise it will apply the function
+    fn visit_unary_with_opt_frame(
         &self,
         f: impl Fn(Expr) -> Expr,
-        cumulative_f: impl Fn(Expr, bool) -> Expr,
+        rolling_f: impl Fn(Expr, RollingOptions) -> Expr,
+        cumulative_f: Option<fn(Expr, bool) -> Expr>,
     ) -> PolarsResult<Expr> {
-        match self.func.over.as_ref() {
-            Some(WindowType::WindowSpec(spec)) => {
-                self.apply_cumulative_window(f, cumulative_f, spec)
+        match (self.window_spec()?, cumulative_f) {
+            (Some(spec), _) if spec.window_frame.is_some() => {
+                self.apply_window_frame(f, rolling_f, cumulative_f, &spec)
+            }
+            (Some(spec), Some(cumulative_f)) => {
+                self.apply_cumulative_window(f, cumulative_f, &spec)
             }
-            Some(WindowType::NamedWindow(named_window)) => polars_bail!(
-                InvalidOperation: "Named windows are not supported yet. Got {:?}",
-                named_window
-            ),
             _ => self.visit_unary(f),
         }
     }
+
+    /// Apply an aggregation over the frame of a window spec, e.g.
+    /// `SUM(a) OVER (ORDER BY b ROWS BETWEEN 2 PRECEDING AND CURRENT ROW)`.
+    ///
+    /// Bounded frames are mapped onto the rolling kernels, frames that are unbounded
+    /// on one side onto the cumulative function (if there is one). RANGE frames are only
+    /// supported when unbounded on at least one side and bounded by the current row (if at
+    /// all); the rows that are peers of the current row share its value, like they do for the
+    /// default frame.
+    fn apply_window_frame(
+        &self,
+        f: impl Fn(Expr) -> Expr,
+        rolling_f: impl Fn(Expr, RollingOptions) -> Expr,
+        cumulative_f: Option<fn(Expr, bool) -> Expr>,
+        spec: &WindowSpec,
+    ) -> PolarsResult<Expr> {
+        let frame = spec.window_frame.as_ref().unwrap();
+        let start = self.frame_offset(&frame.start_bound)?;
+        let end = match &frame.end_bound {
+            Some(bound) => self.frame_offset(bound)?,
+            None => Some(0),
+        };
+        let rolling_options = |window_size: i64, center: bool| RollingOptions {
+            window_size: Duration::new(window_size),
+            min_periods: 1,
+            center,
+            ..Default::default()
+        };
+        let unsupported = || {
+            let end_bound = frame
+                .end_bound
+                .as_ref()
+                .unwrap_or(&WindowFrameBound::CurrentRow);
+            polars_err!(
+                InvalidOperation: "window frame '{} BETWEEN {} AND {}' is not yet supported",
+                frame.units, frame.start_bound, end_bound
+            )
+        };
+        if frame.units == WindowFrameUnits::Groups {
+            return Err(unsupported());
+        }
+        if frame.units == WindowFrameUnits::Range && (start, end) != (None, None) {
+            let reverse = match (start, end) {
+                (None, Some(0)) => false,
+                (Some(0), None) => true,
+                _ => return Err(unsupported()),
+            };
+            let cumulative_f = cumulative_f.ok_or_else(unsupported)?;
+            let (by, descending) = self.visit_window_order_by(&spec.order_by)?;
+            let expr = self.visit_unary_no_window(|e| {
+                let framed = cumulative_over_peers(e, &by, &descending, &cumulative_f, reverse);
+                if by.is_empty() {
+                    framed
+                } else {
+                    framed.take(arg_sort_by(&by, &descending).arg_sort(Default::default()))
+                }
+            })?;
+            return self.apply_partition(expr, &spec.partition_by);
+        }
+
+        let framed: Box<dyn Fn(Expr) -> Expr + '_> = match (start, end) {
+            // the whole window
+            (None, None) => {
+                let expr = self.visit_unary_no_window(f)?;
+                return self.apply_partition(expr, &spec.partition_by);
+            }
+            (None, Some(0)) => {
+                let cumulative_f = cumulative_f.ok_or_else(unsupported)?;
+                Box::new(move |e| cumulative_f(e, false))
+            }
+            (Some(0), None) => {
+                let cumulative_f = cumulative_f.ok_or_else(unsupported)?;
+                Box::new(move |e| cumulative_f(e, true))
+            }
+            // n PRECEDING AND CURRENT ROW
+            (Some(start), Some(0)) if start <= 0 => {
+                let options = rolling_options(1 - start, false);
+                Box::new(move |e| rolling_f(e, options.clone()))
+            }
+            // CURRENT ROW AND n FOLLOWING
+            (Some(0), Some(end)) if end >= 0 => {
+                let options = rolling_options(end + 1, false);
+                Box::new(move |e| rolling_f(e.reverse(), options.clone()).reverse())
+            }
+            // n PRECEDING AND n FOLLOWING
+            (Some(start), Some(end)) if start == -end => {
+                let options = rolling_options(2 * end + 1, true);
+                Box::new(move |e| rolling_f(e, options.clone()))
+            }
+            // n PRECEDING AND m FOLLOWING is a window of n + m + 1 rows that ends m rows
+            // after the current row. The last m rows take the window that starts n rows
+            // before them instead, and if neither fits the frame is the whole column.
+            (Some(start), Some(end)) if start <= 0 && end >= 0 => {
+                let options = rolling_options(end - start + 1, false);
+                Box::new(move |e| {
+                    let in_bounds = |periods: i64| e.clone().is_null().shift(periods).is_not_null();
+                    let ending = rolling_f(e.clone(), options.clone());
+                    let starting = rolling_f(e.clone().reverse(), options.clone()).reverse();
+                    when(in_bounds(-end))
+                        .then(ending.shift(-end))
+                        .when(in_bounds(-start))
+                        .then(starting.clone().shift(-start))
+                        .otherwise(starting.first())
+                })
+            }
+            _ => return Err(unsupported()),
+        };
+
+        let expr = self.visit_unary_no_window(|e| e)?;
+        let expr = self.apply_ordered(expr, &spec.order_by, framed)?;
+        self.apply_partition(expr, &spec.partition_by)
+    }
+
+    /// Get the offset of a window frame bound relative to the current row,
+    /// or `None` if it is unbounded.
+    fn frame_offset(&self, bound: &WindowFrameBound) -> PolarsResult<Option<i64>> {
+        let offset = |e: &SqlExpr| match e {
+            SqlExpr::Value(SqlValue::Number(n, _)) => n
+                .parse::<i64>()
+                .map_err(|_| polars_err!(ComputeError: "invalid window frame offset: {}", n)),
+            _ => polars_bail!(ComputeError: "window frame offset must be a number, got {}", e),
+        };
+        Ok(match bound {
+            WindowFrameBound::CurrentRow => Some(0),
+            WindowFrameBound::Preceding(Some(e)) => Some(-offset(e)?),
+            WindowFrameBound::Following(Some(e)) => Some(offset(e)?),
+            WindowFrameBound::Preceding(None) | WindowFrameBound::Following(None) => None,
+        })
+    }
+
     /// Window specs without partition bys are essentially cumulative functions
     /// e.g. SUM(a) OVER (ORDER BY b DESC) -> CUMSUM(a, false)
+    ///
+    /// This is the default frame, `RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW`,
+    /// so rows with equal ORDER BY values (peers) share the value of the last of them.
     fn apply_cumulative_window(
         &self,
         f: impl Fn(Expr) -> Expr,
@@ -703,19 +1004,10 @@ impl SqlFunctionVisitor<'_> {
         }: &WindowSpec,
     ) -> PolarsResult<Expr> {
         if !order_by.is_empty() && partition_by.is_empty() {
-            let (order_by, desc): (Vec<Expr>, Vec<bool>) = order_by
-                .iter()
-                .map(|o| {
-                    let expr = parse_sql_expr(&o.expr, self.ctx)?;
-                    Ok(match o.asc {
-                        Some(b) => (expr, !b),
-                        None => (expr, false),
-                    })
-                })
-                .collect::<PolarsResult<Vec<_>>>()?
-                .into_iter()
-                .unzip();
-            self.visit_unary_no_window(|e| cumulative_f(e.sort_by(&order_by, &desc), false))
+            let (by, descending) = self.visit_window_order_by(order_by)?;
+            self.visit_unary_no_window(|e| {
+                cumulative_over_peers(e, &by, &descending, &cumulative_f, false)
+            })
         } else {
             self.visit_unary(f)
         }
@@ -788,6 +1080,54 @@ impl SqlFunctionVisitor<'_> {
         Ok(f())
     }
 
+    /// Visit a function of the form `F('part', expr)`, e.g. `DATE_PART('year', column_1)`
+    fn visit_date_part(&self, f: impl Fn(Expr, &str) -> PolarsResult<Expr>) -> PolarsResult<Expr> {
+        match extract_args(self.func).as_slice() {
+            [FunctionArgExpr::Expr(part), FunctionArgExpr::Expr(e)] => {
+                let part = date_part_name(part)?;
+                f(parse_sql_expr(e, self.ctx)?, &part)
+            }
+            _ => self.not_supported_error(),
+        }
+    }
+
+    /// Visit `DATEDIFF(part, start, end)`
+    fn visit_datediff(&self) -> PolarsResult<Expr> {
+        let (part, start, end) = match extract_args(self.func).as_slice() {
+            [FunctionArgExpr::Expr(part), FunctionArgExpr::Expr(start), FunctionArgExpr::Expr(end)] => {
+                (
+                    date_part_name(part)?,
+                    parse_sql_expr(start, self.ctx)?,
+                    parse_sql_expr(end, self.ctx)?,
+                )
+            }
+            _ => return self.not_supported_error(),
+        };
+        let calendar_diff = |f: fn(Expr) -> Expr, n: i32| {
+            let year_diff = end.clone().dt().year() - start.clone().dt().year();
+            (year_diff * lit(n) + f(end.clone()) - f(start.clone())).cast(DataType::Int64)
+        };
+        let unit_us: i64 = match part.as_str() {
+            "year" => return Ok(calendar_diff(|_| lit(0), 1)),
+            "quarter" => return Ok(calendar_diff(|e| e.dt().quarter().cast(DataType::Int32), 4)),
+            "month" => return Ok(calendar_diff(|e| e.dt().month().cast(DataType::Int32), 12)),
+            "week" => 7 * 86_400_000_000,
+            "day" => 86_400_000_000,
+            "hour" => 3_600_000_000,
+            "minute" => 60_000_000,
+            "second" => 1_000_000,
+            "millisecond" | "milliseconds" => 1_000,
+            "microsecond" | "microseconds" => 1,
+            _ => polars_bail!(InvalidOperation: "DATEDIFF does not support part '{}'", part),
+        };
+        // count the boundaries that are crossed by truncating both sides first
+        let epoch_us = |e: Expr| -> PolarsResult<Expr> {
+            let e = e.cast(DataType::Datetime(TimeUnit::Microseconds, None));
+            Ok(date_trunc(e, &part)?.cast(DataType::Int64))
+        };
+        Ok((epoch_us(end)? - epoch_us(start)?).floor_div(lit(unit_us)))
+    }
+
     fn visit_count(&self) -> PolarsResult<Expr> {
         let args = extract_args(self.func);
         match (self.func.distinct, args.as_slice()) {
@@ -811,11 +1151,203 @@ impl SqlFunctionVisitor<'_> {
         }
     }
 
+    /// Visit a window function without arguments, e.g. `ROW_NUMBER() OVER (...)`.
+    ///
+    /// `f` gets the ORDER BY of the window (if any) and is evaluated per partition.
+    fn visit_window_nullary(
+        &self,
+        f: impl Fn(Option<(&[Expr], &[bool])>) -> Expr,
+    ) -> PolarsResult<Expr> {
+        if !extract_args(self.func).is_empty() {
+            return self.not_supported_error();
+        }
+        let spec = self.required_window_spec()?;
+        let (by, descending) = self.visit_window_order_by(&spec.order_by)?;
+        let expr = if by.is_empty() {
+            f(None)
+        } else {
+            f(Some((&by, &descending)))
+        };
+        self.apply_partition(expr, &spec.partition_by)
+    }
+
+    /// Visit `RANK() OVER (...)` or `DENSE_RANK() OVER (...)`
+    fn visit_rank(&self, method: RankMethod) -> PolarsResult<Expr> {
+        if !extract_args(self.func).is_empty() {
+            return self.not_supported_error();
+        }
+        let spec = self.required_window_spec()?;
+        let (by, descending) = self.visit_window_order_by(&spec.order_by)?;
+        let expr = match (by.as_slice(), descending.as_slice()) {
+            ([by], [descending]) => by.clone().rank(
+                RankOptions {
+                    method,
+                    descending: *descending,
+                },
+                None,
+            ),
+            _ => polars_bail!(
+                InvalidOperation: "{} requires a window with a single ORDER BY expression",
+                self.func
+            ),
+        };
+        self.apply_partition(expr, &spec.partition_by)
+    }
+
+    /// Visit `LAG(expr [, offset [, default]]) OVER (...)` or `LEAD(...)`.
+    /// `direction` is 1 for LAG and -1 for LEAD.
+    fn visit_lag_lead(&self, direction: i64) -> PolarsResult<Expr> {
+        let args = extract_args(self.func);
+        let (expr, offset, default) = match args.as_slice() {
+            [FunctionArgExpr::Expr(e)] => (e, 1, None),
+            [FunctionArgExpr::Expr(e), FunctionArgExpr::Expr(offset)] => {
+                (e, i64::from_sql_expr(offset, self.ctx)?, None)
+            }
+            [FunctionArgExpr::Expr(e), FunctionArgExpr::Expr(offset), FunctionArgExpr::Expr(default)] => {
+                (
+                    e,
+                    i64::from_sql_expr(offset, self.ctx)?,
+                    Some(parse_sql_expr(default, self.ctx)?),
+                )
+            }
+            _ => return self.not_supported_error(),
+        };
+        let spec = self.required_window_spec()?;
+        let periods = direction * offset;
+        let expr = parse_sql_expr(expr, self.ctx)?;
+        let expr = self.apply_ordered(expr, &spec.order_by, |e| match default {
+            Some(default) => e.shift_and_fill(periods, default),
+            None => e.shift(periods),
+        })?;
+        self.apply_partition(expr, &spec.partition_by)
+    }
+
+    /// Visit `GROUPING(key [, ...])`.
+    /// The keys are looked up in the indicator columns that are added by the groupby,
+    /// with the first key being the most significant bit of the result.
+    fn visit_grouping(&self) -> PolarsResult<Expr> {
+        let args = extract_args(self.func);
+        polars_ensure!(
+            !args.is_empty() && args.len() < 32 && self.func.over.is_none(),
+            InvalidOperation: "Invalid arguments for Grouping: {}", self.func
+        );
+        let n = args.len();
+        args.into_iter()
+            .enumerate()
+            .map(|(i, arg)| match arg {
+                FunctionArgExpr::Expr(e) => {
+                    let name = expr_output_name(&parse_sql_expr(e, self.ctx)?)?;
+                    Ok(col(&format!("{GROUPING_PREFIX}{name}")) * lit(1i32 << (n - 1 - i)))
+                }
+                _ => self.not_supported_error(),
+            })
+            .reduce(|acc, e| Ok(acc? + e?))
+            .unwrap()
+            .map(|e| e.alias("grouping"))
+    }
+
+    /// Visit `NTILE(n) OVER (...)`
+    fn visit_ntile(&self) -> PolarsResult<Expr> {
+        let n = match extract_args(self.func).as_slice() {
+            [FunctionArgExpr::Expr(n)] => i64::from_sql_expr(n, self.ctx)?,
+            _ => return self.not_supported_error(),
+        };
+        polars_ensure!(n > 0, ComputeError: "NTILE requires a positive number of buckets, got {}", n);
+        self.visit_window_nullary(|order_by| {
+            let row_idx = match order_by {
+                Some((by, descending)) => arg_sort_by(by, descending).arg_sort(Default::default()),
+                None => int_range(lit(0 as IdxSize), count().cast(IDX_DTYPE), 1),
+            }
+            .cast(DataType::Int64);
+            let count = count().cast(DataType::Int64);
+            // the first `count % n` buckets get one row more than the others
+            let size = count.clone().floor_div(lit(n));
+            let n_large = count % lit(n);
+            let large_rows = n_large.clone() * (size.clone() + lit(1i64));
+            // guard against dividing by zero if there are less rows than buckets,
+            // in which case all rows are in the large buckets anyway
+            let small_size = when(size.clone().eq(lit(0i64)))
+                .then(lit(1i64))
+                .otherwise(size.clone());
+            when(row_idx.clone().lt(large_rows.clone()))
+                .then(row_idx.clone().floor_div(size + lit(1i64)))
+                .otherwise(n_large + (row_idx - large_rows).floor_div(small_size))
+                + lit(1i64)
+        })
+    }
+
+    /// Resolve the window spec of the function, looking up named windows
+    /// defined in the `WINDOW` clause of the query.
+    fn window_spec(&self) -> PolarsResult<Option<WindowSpec>> {
+        Ok(match &self.func.over {
+            Some(WindowType::WindowSpec(spec)) => Some(spec.clone()),
+            Some(WindowType::NamedWindow(name)) => Some(
+                self.ctx
+                    .named_windows
+                    .get(&name.value)
+                    .cloned()
+                    .ok_or_else(
+                        || polars_err!(ComputeError: "no window named '{}' found", name.value),
+                    )?,
+            ),
+            None => None,
+        })
+    }
+
+    fn required_window_spec(&self) -> PolarsResult<WindowSpec> {
+        self.window_spec()?
+            .ok_or_else(|| polars_err!(InvalidOperation: "{} requires an OVER clause", self.func))
+    }
+
+    fn visit_window_order_by(
+        &self,
+        order_by: &[OrderByExpr],
+    ) -> PolarsResult<(Vec<Expr>, Vec<bool>)> {
+        let mut by = Vec::with_capacity(order_by.len());
+        let mut descending = Vec::with_capacity(order_by.len());
+        for o in order_by {
+            by.push(parse_sql_expr(&o.expr, self.ctx)?);
+            descending.push(o.asc == Some(false));
+        }
+        Ok((by, descending))
+    }
+
+    /// Evaluate `f` on the rows of a window in the order of its ORDER BY, and
+    /// put the results back in the original order of the rows.
+    fn apply_ordered(
+        &self,
+        expr: Expr,
+        order_by: &[OrderByExpr],
+        f: impl FnOnce(Expr) -> Expr,
+    ) -> PolarsResult<Expr> {
+        let (by, descending) = self.visit_window_order_by(order_by)?;
+        if by.is_empty() {
+            return Ok(f(expr));
+        }
+        let idx = arg_sort_by(&by, &descending);
+        Ok(f(expr.take(idx.clone())).take(idx.arg_sort(Default::default())))
+    }
+
+    fn apply_partition(&self, expr: Expr, partition_by: &[SqlExpr]) -> PolarsResult<Expr> {
+        if partition_by.is_empty() {
+            return Ok(expr);
+        }
+        let partition_by = partition_by
+            .iter()
+            .map(|p| parse_sql_expr(p, self.ctx))
+            .collect::<PolarsResult<Vec<_>>>()?;
+        Ok(expr.over(partition_by))
+    }
+
     fn apply_window_spec(
         &self,
         expr: Expr,
         window_type: &Option<WindowType>,
     ) -> PolarsResult<Expr> {
+        let window_type = match window_type {
+            Some(WindowType::NamedWindow(_)) => self.window_spec()?.map(WindowType::WindowSpec),
+            _ => window_type.clone(),
+        };
         Ok(match &window_type {
             Some(WindowType::WindowSpec(window_spec)) => {
                 if window_spec.partition_by.is_empty() {
@@ -841,11 +1373,7 @@ impl SqlFunctionVisitor<'_> {
                     expr.over(partition_by)
                 }
             }
-            Some(WindowType::NamedWindow(named_window)) => polars_bail!(
-                InvalidOperation: "Named windows are not supported yet. Got: {:?}",
-                named_window
-            ),
-            None => expr,
+            _ => expr,
         })
     }
 
@@ -858,6 +1386,111 @@ impl SqlFunctionVisitor<'_> {
     }
 }
 
+/// Apply a cumulative function to `expr` in the order of `by`, where the peers of a row,
+/// the rows with equal `by` values, all get the value of the last of them (or of the first,
+/// if `reverse`). The result is in sorted order.
+fn cumulative_over_peers(
+    expr: Expr,
+    by: &[Expr],
+    descending: &[bool],
+    cumulative_f: impl Fn(Expr, bool) -> Expr,
+    reverse: bool,
+) -> Expr {
+    if by.is_empty() {
+        // every row is a peer of every other row
+        let cumulative = cumulative_f(expr, reverse);
+        return if reverse {
+            cumulative.first()
+        } else {
+            cumulative.last()
+        };
+    }
+    let sorted = |e: Expr| e.sort_by(by, descending);
+    let cumulative = cumulative_f(sorted(expr), reverse);
+
+    // the peers of a row are adjacent once sorted, so take the value at the boundary of
+    // each run of equal keys
+    let periods = if reverse { 1 } else { -1 };
+    let keys = by.iter().cloned().map(sorted).collect::<Vec<_>>();
+    let boundary = keys
+        .iter()
+        .map(|k| k.clone().neq_missing(k.clone().shift(periods)))
+        .fold(
+            keys[0].clone().is_null().shift(periods).is_null(),
+            |acc, b| acc.or(b),
+        );
+    let idx = when(boundary)
+        .then(keys[0].clone().cumcount(false))
+        .otherwise(lit(Null {}));
+    let idx = if reverse {
+        idx.forward_fill(None)
+    } else {
+        idx.backward_fill(None)
+    };
+    cumulative.take(idx)
+}
+
+/// Get the name of a date part argument, given as string or identifier,
+/// e.g. `'year'` or `year`.
+fn date_part_name(expr: &SqlExpr) -> PolarsResult<String> {
+    match expr {
+        SqlExpr::Value(SqlValue::SingleQuotedString(s)) => Ok(s.to_lowercase()),
+        SqlExpr::Identifier(ident) => Ok(ident.value.to_lowercase()),
+        _ => polars_bail!(ComputeError: "invalid date part: {}", expr),
+    }
+}
+
+/// Extract a part of a date or datetime, as in `EXTRACT(part FROM expr)`.
+pub(crate) fn date_part(expr: Expr, part: &str) -> PolarsResult<Expr> {
+    let dt = expr.dt();
+    Ok(match part.to_lowercase().as_str() {
+        "millennium" | "millenium" => (dt.year() - lit(1)).floor_div(lit(1000)) + lit(1),
+        "century" => (dt.year() - lit(1)).floor_div(lit(100)) + lit(1),
+        "decade" => dt.year().floor_div(lit(10)),
+        "isoyear" => dt.iso_year(),
+        "year" => dt.year(),
+        "quarter" => dt.quarter(),
+        "month" => dt.month(),
+        "week" => dt.week(),
+        "doy" => dt.ordinal_day(),
+        // days of the week are numbered from sunday (0) to saturday (6)
+        "dow" => dt.weekday() % lit(7u32),
+        "isodow" => dt.weekday(),
+        "day" => dt.day(),
+        "hour" => dt.hour(),
+        "minute" => dt.minute(),
+        "second" => dt.second(),
+        "millisecond" | "milliseconds" => dt.millisecond(),
+        "microsecond" | "microseconds" => dt.microsecond(),
+        "nanosecond" | "nanoseconds" => dt.nanosecond(),
+        "date" => dt.date(),
+        "epoch" => dt.timestamp(TimeUnit::Microseconds).cast(DataType::Float64) / lit(1_000_000.0),
+        _ => polars_bail!(InvalidOperation: "date part '{}' is not supported", part),
+    })
+}
+
+/// Truncate a date or datetime to a date part, as in `DATE_TRUNC('part', expr)`.
+pub(crate) fn date_trunc(expr: Expr, part: &str) -> PolarsResult<Expr> {
+    let every = match part.to_lowercase().as_str() {
+        "year" => "1y",
+        "quarter" => "1q",
+        "month" => "1mo",
+        "week" => "1w",
+        "day" => "1d",
+        "hour" => "1h",
+        "minute" => "1m",
+        "second" => "1s",
+        "millisecond" | "milliseconds" => "1ms",
+        "microsecond" | "microseconds" => "1us",
+        _ => polars_bail!(InvalidOperation: "DATE_TRUNC does not support part '{}'", part),
+    };
+    Ok(expr.dt().truncate(TruncateOptions {
+        every: every.to_string(),
+        offset: "0ns".to_string(),
+        use_earliest: None,
+    }))
+}
+
 fn extract_args(sql_function: &SQLFunction) -> Vec<&FunctionArgExpr> {
     sql_function
         .args
@@ -892,6 +1525,20 @@ impl FromSqlExpr for f64 {
     }
 }
 
+impl FromSqlExpr for i64 {
+    fn from_sql_expr(expr: &SqlExpr, _ctx: &SQLContext) -> PolarsResult<Self>
+    where
+        Self: Sized,
+    {
+        match expr {
+            SqlExpr::Value(SqlValue::Number(s, _)) => s
+                .parse()
+                .map_err(|_| polars_err!(ComputeError: "can't parse literal {:?}", s)),
+            _ => polars_bail!(ComputeError: "can't parse literal {:?}", expr),
+        }
+    }
+}
+
 impl FromSqlExpr for String {
     fn from_sql_expr(expr: &SqlExpr, _: &SQLContext) -> PolarsResult<Self>
     where
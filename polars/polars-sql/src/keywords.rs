@@ -24,6 +24,7 @@ pub fn all_keywords() -> Vec<&'static str> {
         keywords::BY,
         keywords::CASE,
         keywords::CREATE,
+        keywords::CUBE,
         keywords::DATE,
         keywords::DATETIME,
         keywords::DESC,
@@ -35,6 +36,7 @@ pub fn all_keywords() -> Vec<&'static str> {
         keywords::FROM,
         keywords::FULL,
         keywords::GROUP,
+        keywords::GROUPING,
         keywords::HAVING,
         keywords::IN,
         keywords::INNER,
@@ -50,7 +52,9 @@ pub fn all_keywords() -> Vec<&'static str> {
         keywords::ORDER,
         keywords::OUTER,
         keywords::RIGHT,
+        keywords::ROLLUP,
         keywords::SELECT,
+        keywords::SETS,
         keywords::SHOW,
         keywords::TABLE,
         keywords::TABLES,
@@ -2,10 +2,27 @@
 //! This crate provides a SQL interface for Polars DataFrames
 #![deny(missing_docs)]
 mod context;
+#[cfg(any(
+    feature = "parquet",
+    feature = "ipc",
+    feature = "csv",
+    feature = "json"
+))]
+mod copy;
+mod explain;
 mod functions;
 pub mod keywords;
+mod params;
 mod sql_expr;
 mod table_functions;
 
 pub use context::SQLContext;
+#[cfg(any(
+    feature = "parquet",
+    feature = "ipc",
+    feature = "csv",
+    feature = "json"
+))]
+pub use copy::sink_or_collect;
+pub use params::{PreparedStatement, SQLParams};
 pub use sql_expr::sql_expr;
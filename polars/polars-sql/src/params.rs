@@ -0,0 +1,199 @@
+use polars_core::prelude::*;
+use polars_plan::prelude::LiteralValue;
+use sqlparser::ast::Statement;
+
+use crate::sql_expr::map_sql_polars_datatype;
+
+/// Values bound to the placeholders of a parameterised SQL query.
+///
+/// Positional values are bound to `$1`, `$2`, ... (or `?` in order of appearance, a query
+/// can't mix both), named values are bound to `:name` or `$name`.
+/// ```rust
+/// # use polars_sql::SQLParams;
+/// # use polars_plan::prelude::LiteralValue;
+/// # fn main() {
+/// let params = SQLParams::new()
+///     .with(LiteralValue::Int64(10))
+///     .with_named("name", LiteralValue::Utf8("foo".into()));
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct SQLParams {
+    positional: Vec<LiteralValue>,
+    named: PlHashMap<String, LiteralValue>,
+}
+
+impl SQLParams {
+    /// Create an empty set of parameters.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Bind the next positional parameter.
+    pub fn with(mut self, value: LiteralValue) -> Self {
+        self.positional.push(value);
+        self
+    }
+
+    /// Bind a named parameter.
+    pub fn with_named(mut self, name: &str, value: LiteralValue) -> Self {
+        self.named.insert(name.to_string(), value);
+        self
+    }
+}
+
+impl From<Vec<LiteralValue>> for SQLParams {
+    fn from(positional: Vec<LiteralValue>) -> Self {
+        Self {
+            positional,
+            ..Default::default()
+        }
+    }
+}
+
+/// A parsed SQL statement that can be executed multiple times with different parameters.
+///
+/// Created by [`SQLContext::prepare`](crate::SQLContext::prepare). If the query is written
+/// as `PREPARE name (INT, VARCHAR) AS SELECT ...`, the positional parameters are checked
+/// against the declared types when they are bound.
+#[derive(Clone, Debug)]
+pub struct PreparedStatement {
+    pub(crate) statement: Statement,
+    pub(crate) param_types: Vec<DataType>,
+    pub(crate) n_positional: usize,
+}
+
+impl PreparedStatement {
+    pub(crate) fn new(statement: Statement, n_positional: usize) -> PolarsResult<Self> {
+        match statement {
+            Statement::Prepare {
+                data_types,
+                statement,
+                ..
+            } => {
+                let param_types = data_types
+                    .iter()
+                    .map(map_sql_polars_datatype)
+                    .collect::<PolarsResult<Vec<_>>>()?;
+                Ok(Self {
+                    statement: *statement,
+                    n_positional: n_positional.max(param_types.len()),
+                    param_types,
+                })
+            }
+            statement => Ok(Self {
+                statement,
+                param_types: vec![],
+                n_positional,
+            }),
+        }
+    }
+
+    /// Resolve the given parameters to the values of the placeholders in this statement.
+    pub(crate) fn bind(&self, params: &SQLParams) -> PolarsResult<PlHashMap<String, LiteralValue>> {
+        polars_ensure!(
+            params.positional.len() == self.n_positional,
+            ComputeError: "SQL query expects {} positional parameters, got {}",
+            self.n_positional, params.positional.len()
+        );
+        let mut bound = params.named.clone();
+        for (i, value) in params.positional.iter().enumerate() {
+            let value = match self.param_types.get(i) {
+                Some(dtype) => check_param_type(&format!("${}", i + 1), value.clone(), dtype)?,
+                None => value.clone(),
+            };
+            bound.insert((i + 1).to_string(), value);
+        }
+        Ok(bound)
+    }
+}
+
+/// Get the key a placeholder is bound under, e.g. `$1` -> `1` and `:name` -> `name`.
+pub(crate) fn placeholder_key(placeholder: &str) -> &str {
+    match placeholder.chars().next() {
+        Some('$' | '?' | ':' | '@') => &placeholder[1..],
+        _ => placeholder,
+    }
+}
+
+fn check_param_type(
+    name: &str,
+    value: LiteralValue,
+    dtype: &DataType,
+) -> PolarsResult<LiteralValue> {
+    let value_dtype = value.get_datatype();
+    if matches!(value, LiteralValue::Null) || &value_dtype == dtype {
+        return Ok(value);
+    }
+    // numeric values may be cast if they fit in the declared type
+    if value_dtype.is_numeric() && dtype.is_numeric() {
+        if let Ok(value) = cast_literal(&value, dtype) {
+            return Ok(value);
+        }
+    }
+    polars_bail!(
+        ComputeError: "SQL parameter {} expects a value of type {}, got {}",
+        name, dtype, value_dtype
+    )
+}
+
+/// Check a parameter that is compared with a value of type `dtype`, e.g. `$1` in
+/// `a > $1`. Numbers compare with any numeric type and strings are parsed if they are
+/// compared with a temporal type, other values must be of the same type.
+pub(crate) fn check_compared_param(
+    name: &str,
+    value: LiteralValue,
+    dtype: &DataType,
+) -> PolarsResult<LiteralValue> {
+    let value_dtype = value.get_datatype();
+    if matches!(value, LiteralValue::Null) || &value_dtype == dtype {
+        return Ok(value);
+    }
+    let compatible = match (&value_dtype, dtype) {
+        (l, r) if l.is_numeric() && r.is_numeric() => true,
+        (DataType::Utf8, r) if r.is_temporal() => {
+            return cast_literal(&value, dtype).map_err(|_| {
+                polars_err!(
+                    ComputeError: "SQL parameter {} is compared with a value of type {}, \
+                    but {:?} can't be parsed as such",
+                    name, dtype, value
+                )
+            })
+        }
+        // strings also compare with string-like types, such as Categorical
+        (DataType::Utf8, r) => !r.is_numeric() && r != &DataType::Boolean,
+        _ => false,
+    };
+    polars_ensure!(
+        compatible,
+        ComputeError: "SQL parameter {} is compared with a value of type {}, got {}",
+        name, dtype, value_dtype
+    );
+    Ok(value)
+}
+
+/// Cast a parameter to `dtype`, as in `CAST($1 AS INT)`, failing if it can't be cast.
+pub(crate) fn cast_param(
+    name: &str,
+    value: LiteralValue,
+    dtype: &DataType,
+) -> PolarsResult<LiteralValue> {
+    if matches!(value, LiteralValue::Null) {
+        return Ok(value);
+    }
+    cast_literal(&value, dtype).map_err(|_| {
+        polars_err!(
+            ComputeError: "SQL parameter {} of type {} can't be cast to {}",
+            name, value.get_datatype(), dtype
+        )
+    })
+}
+
+fn cast_literal(value: &LiteralValue, dtype: &DataType) -> PolarsResult<LiteralValue> {
+    let av = value
+        .to_anyvalue()
+        .ok_or_else(|| polars_err!(ComputeError: "SQL parameter {:?} is not a scalar", value))?;
+    let s = Series::from_any_values("", &[av], true)?.strict_cast(dtype)?;
+    let av = s.get(0)?;
+    LiteralValue::try_from(av)
+}
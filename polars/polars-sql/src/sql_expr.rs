@@ -2,16 +2,18 @@ use polars_arrow::error::to_compute_err;
 use polars_core::prelude::*;
 use polars_lazy::dsl::Expr;
 use polars_lazy::prelude::*;
-use polars_plan::prelude::{col, lit, when};
+use polars_plan::prelude::{col, count, lit, when, Context};
 use sqlparser::ast::{
     ArrayAgg, BinaryOperator as SQLBinaryOperator, BinaryOperator, DataType as SQLDataType,
-    Expr as SqlExpr, Function as SQLFunction, JoinConstraint, OrderByExpr, SelectItem,
-    TrimWhereField, UnaryOperator, Value as SqlValue,
+    DateTimeField, Expr as SqlExpr, Function as SQLFunction, JoinConstraint, OrderByExpr, Query,
+    SelectItem, SetExpr, TrimWhereField, UnaryOperator, Value as SqlValue,
 };
 use sqlparser::dialect::GenericDialect;
 use sqlparser::parser::{Parser, ParserOptions};
 
-use crate::functions::SqlFunctionVisitor;
+use crate::context::subquery_column;
+use crate::functions::{date_part, SqlFunctionVisitor};
+use crate::params::{cast_param, check_compared_param};
 use crate::SQLContext;
 
 pub(crate) fn map_sql_polars_datatype(data_type: &SQLDataType) -> PolarsResult<DataType> {
@@ -73,6 +75,10 @@ impl SqlExprVisitor<'_> {
             SqlExpr::Cast { expr, data_type } => self.visit_cast(expr, data_type),
             SqlExpr::Ceil { expr, .. } => Ok(self.visit_expr(expr)?.ceil()),
             SqlExpr::CompoundIdentifier(idents) => self.visit_compound_identifier(idents),
+            SqlExpr::Exists { subquery, negated } => self.visit_exists(subquery, *negated),
+            SqlExpr::Extract { field, expr } => {
+                date_part(self.visit_expr(expr)?, &field.to_string())
+            }
             SqlExpr::Floor { expr, .. } => Ok(self.visit_expr(expr)?.floor()),
             SqlExpr::Function(function) => self.visit_function(function),
             SqlExpr::Identifier(ident) => self.visit_identifier(ident),
@@ -81,6 +87,16 @@ impl SqlExprVisitor<'_> {
                 list,
                 negated,
             } => self.visit_is_in(expr, list, *negated),
+            SqlExpr::InSubquery {
+                expr,
+                subquery,
+                negated,
+            } => self.visit_in_subquery(expr, subquery, *negated),
+            SqlExpr::Interval {
+                value,
+                leading_field,
+                ..
+            } => self.visit_interval(value, leading_field),
             SqlExpr::IsDistinctFrom(e1, e2) => {
                 Ok(self.visit_expr(e1)?.neq_missing(self.visit_expr(e2)?))
             }
@@ -94,11 +110,13 @@ impl SqlExprVisitor<'_> {
             SqlExpr::IsNull(expr) => Ok(self.visit_expr(expr)?.is_null()),
             SqlExpr::IsTrue(expr) => Ok(self.visit_expr(expr)?.eq(lit(true))),
             SqlExpr::Nested(expr) => self.visit_expr(expr),
+            SqlExpr::Subquery(subquery) => self.visit_scalar_subquery(subquery),
             SqlExpr::Trim {
                 expr,
                 trim_where,
                 trim_what,
             } => self.visit_trim(expr, trim_where, trim_what),
+            SqlExpr::TypedString { data_type, value } => self.visit_typed_string(data_type, value),
             SqlExpr::UnaryOp { op, expr } => self.visit_unary_op(op, expr),
             SqlExpr::Value(value) => self.visit_literal(value),
             e @ SqlExpr::Case { .. } => self.visit_when_then(e),
@@ -165,8 +183,58 @@ impl SqlExprVisitor<'_> {
         op: &BinaryOperator,
         right: &SqlExpr,
     ) -> PolarsResult<Expr> {
-        let left = self.visit_expr(left)?;
-        let right = self.visit_expr(right)?;
+        // date/datetime +/- INTERVAL is an offset by a (calendar) duration
+        match (left, op, right) {
+            (
+                _,
+                SQLBinaryOperator::Plus | SQLBinaryOperator::Minus,
+                SqlExpr::Interval {
+                    value,
+                    leading_field,
+                    ..
+                },
+            ) => {
+                let negate = matches!(op, SQLBinaryOperator::Minus);
+                let duration = interval_to_duration(value, leading_field, negate)?;
+                return Ok(self.visit_expr(left)?.dt().offset_by(duration));
+            }
+            (
+                SqlExpr::Interval {
+                    value,
+                    leading_field,
+                    ..
+                },
+                SQLBinaryOperator::Plus,
+                _,
+            ) => {
+                let duration = interval_to_duration(value, leading_field, false)?;
+                return Ok(self.visit_expr(right)?.dt().offset_by(duration));
+            }
+            _ => {}
+        }
+        let is_comparison = matches!(
+            op,
+            SQLBinaryOperator::Eq
+                | SQLBinaryOperator::NotEq
+                | SQLBinaryOperator::Gt
+                | SQLBinaryOperator::GtEq
+                | SQLBinaryOperator::Lt
+                | SQLBinaryOperator::LtEq
+                | SQLBinaryOperator::Spaceship
+        );
+        let (left, right) = match (left, right) {
+            (other, SqlExpr::Value(SqlValue::Placeholder(placeholder))) if is_comparison => {
+                let other = self.visit_expr(other)?;
+                let param = self.visit_compared_param(placeholder, &other)?;
+                (other, param)
+            }
+            (SqlExpr::Value(SqlValue::Placeholder(placeholder)), other) if is_comparison => {
+                let other = self.visit_expr(other)?;
+                let param = self.visit_compared_param(placeholder, &other)?;
+                (param, other)
+            }
+            _ => (self.visit_expr(left)?, self.visit_expr(right)?),
+        };
         Ok(match op {
             SQLBinaryOperator::And => left.and(right),
             SQLBinaryOperator::Divide => left / right,
@@ -214,6 +282,22 @@ impl SqlExprVisitor<'_> {
         })
     }
 
+    /// Visit a parameter that is compared with `other`, checking that its value can be
+    /// compared with the type of `other` if that is known.
+    fn visit_compared_param(&self, placeholder: &str, other: &Expr) -> PolarsResult<Expr> {
+        let value = self.ctx.get_param(placeholder)?;
+        let dtype = self.ctx.input_schema.as_ref().and_then(|schema| {
+            other
+                .to_field(schema, Context::Default)
+                .ok()
+                .map(|field| field.dtype)
+        });
+        Ok(Expr::Literal(match dtype {
+            Some(dtype) => check_compared_param(placeholder, value, &dtype)?,
+            None => value,
+        }))
+    }
+
     /// Visit a SQL function
     ///
     /// e.g. SUM(column) or COUNT(*)
@@ -232,11 +316,54 @@ impl SqlExprVisitor<'_> {
     /// e.g. `CAST(column AS INT)` or `column::INT`
     fn visit_cast(&self, expr: &SqlExpr, data_type: &SQLDataType) -> PolarsResult<Expr> {
         let polars_type = map_sql_polars_datatype(data_type)?;
-        let expr = self.visit_expr(expr)?;
+        // a parameter that can't be cast is an error, rather than a null
+        let expr = match expr {
+            SqlExpr::Value(SqlValue::Placeholder(placeholder)) => {
+                let value = self.ctx.get_param(placeholder)?;
+                Expr::Literal(cast_param(placeholder, value, &polars_type)?)
+            }
+            _ => self.visit_expr(expr)?,
+        };
 
         Ok(expr.cast(polars_type))
     }
 
+    /// Visit a standalone SQL interval
+    ///
+    /// e.g. INTERVAL '3 days', INTERVAL '2' HOUR
+    ///
+    /// Intervals with months or years have no fixed length, so they are only
+    /// supported in date arithmetic.
+    fn visit_interval(
+        &self,
+        value: &SqlExpr,
+        leading_field: &Option<DateTimeField>,
+    ) -> PolarsResult<Expr> {
+        let duration = interval_to_duration(value, leading_field, false)?;
+        polars_ensure!(
+            duration.months() == 0,
+            ComputeError: "INTERVAL with months or years is only supported in date arithmetic"
+        );
+        let ns = duration.duration_ns();
+        Ok(Expr::Literal(LiteralValue::Duration(
+            if duration.negative() { -ns } else { ns },
+            TimeUnit::Nanoseconds,
+        )))
+    }
+
+    /// Visit a typed string literal
+    ///
+    /// e.g. DATE '2023-01-01', TIMESTAMP '2023-01-01 12:00:00'
+    fn visit_typed_string(&self, data_type: &SQLDataType, value: &str) -> PolarsResult<Expr> {
+        let dtype = map_sql_polars_datatype(data_type)?;
+        Ok(match dtype {
+            DataType::Date | DataType::Datetime(_, _) | DataType::Time => {
+                lit(value).str().strptime(dtype, StrptimeOptions::default())
+            }
+            _ => lit(value).cast(dtype),
+        })
+    }
+
     /// Visit a SQL literal
     ///
     /// e.g. 1, 'foo', 1.0, NULL
@@ -259,6 +386,7 @@ impl SqlExprVisitor<'_> {
                 .map_err(|_| polars_err!(ComputeError: "cannot parse literal: {:?}", s))?
             }
             SqlValue::SingleQuotedString(s) => lit(s.clone()),
+            SqlValue::Placeholder(placeholder) => Expr::Literal(self.ctx.get_param(placeholder)?),
             other => polars_bail!(ComputeError: "SQL value {:?} is not yet supported", other),
         })
     }
@@ -281,6 +409,15 @@ impl SqlExprVisitor<'_> {
             | SqlValue::NationalStringLiteral(s)
             | SqlValue::HexStringLiteral(s)
             | SqlValue::DoubleQuotedString(s) => AnyValue::Utf8Owned(s.into()),
+            SqlValue::Placeholder(placeholder) => {
+                let value = self.ctx.get_param(placeholder)?;
+                value
+                    .to_anyvalue()
+                    .ok_or_else(|| {
+                        polars_err!(ComputeError: "SQL parameter {} is not a scalar", placeholder)
+                    })?
+                    .into_static()?
+            }
             other => polars_bail!(ComputeError: "SQL value {:?} is not yet supported", other),
         })
     }
@@ -385,6 +522,94 @@ impl SqlExprVisitor<'_> {
         }
     }
 
+    /// Visit a SQL `IN` expression with a subquery that is not a top-level
+    /// predicate (those are lowered to semi/anti joins by the SQLContext).
+    ///
+    /// The subquery result is imploded into a single list value. As in SQL, the result
+    /// is NULL if `expr` is NULL, or if it is not found and the subquery returns a NULL.
+    fn visit_in_subquery(
+        &self,
+        expr: &SqlExpr,
+        subquery: &Query,
+        negated: bool,
+    ) -> PolarsResult<Expr> {
+        let expr = self.visit_expr(expr)?;
+        let lf = self.ctx.execute_subquery(subquery)?;
+        let name = subquery_column(&lf)?;
+        let values = self.ctx.register_subquery(lf.clone(), col(&name).implode());
+        let has_nulls = self
+            .ctx
+            .register_subquery(lf.clone(), col(&name).null_count().gt(lit(0)));
+        let n_values = self.ctx.register_subquery(lf, count());
+
+        let null = Expr::Literal(LiteralValue::Null).cast(DataType::Boolean);
+        let is_in = when(col(&n_values).eq(lit(0)))
+            .then(lit(false))
+            .when(expr.clone().is_null())
+            .then(null.clone())
+            .when(expr.is_in(col(&values)))
+            .then(lit(true))
+            .when(col(&has_nulls))
+            .then(null)
+            .otherwise(lit(false));
+
+        if negated {
+            Ok(is_in.not())
+        } else {
+            Ok(is_in)
+        }
+    }
+
+    /// Visit a SQL `EXISTS` expression
+    fn visit_exists(&self, subquery: &Query, negated: bool) -> PolarsResult<Expr> {
+        // a projection of only literals would always produce a single row,
+        // but `EXISTS (SELECT 1 FROM ...)` only cares about the matching rows.
+        let mut subquery = subquery.clone();
+        if let SetExpr::Select(select) = subquery.body.as_mut() {
+            let only_literals = select.projection.iter().all(|item| {
+                matches!(
+                    item,
+                    SelectItem::UnnamedExpr(SqlExpr::Value(_))
+                        | SelectItem::ExprWithAlias {
+                            expr: SqlExpr::Value(_),
+                            ..
+                        }
+                )
+            });
+            if only_literals {
+                select.projection = vec![SelectItem::Wildcard(Default::default())];
+            }
+        }
+        let lf = self.ctx.execute_subquery(&subquery)?;
+        let exists = self.ctx.register_subquery(lf, count().gt(lit(0)));
+
+        if negated {
+            Ok(col(&exists).not())
+        } else {
+            Ok(col(&exists))
+        }
+    }
+
+    /// Visit a scalar subquery
+    ///
+    /// e.g. `SELECT a - (SELECT AVG(a) FROM df) FROM df`
+    fn visit_scalar_subquery(&self, subquery: &Query) -> PolarsResult<Expr> {
+        let lf = self.ctx.execute_subquery(subquery)?;
+        let name = subquery_column(&lf)?;
+        let single_row = col(&name).map(
+            |s| {
+                polars_ensure!(
+                    s.len() <= 1,
+                    ComputeError: "scalar subquery must return at most one row, got {}", s.len()
+                );
+                Ok(Some(s))
+            },
+            GetOutput::same_type(),
+        );
+        let value = self.ctx.register_subquery(lf, single_row.first());
+        Ok(col(&value))
+    }
+
     fn visit_order_by(&self, order_by: &[OrderByExpr]) -> PolarsResult<(Vec<Expr>, Vec<bool>)> {
         let mut expr = Vec::with_capacity(order_by.len());
         let mut descending = Vec::with_capacity(order_by.len());
@@ -468,42 +693,157 @@ pub(crate) fn parse_sql_expr(expr: &SqlExpr, ctx: &SQLContext) -> PolarsResult<E
     visitor.visit_expr(expr)
 }
 
+/// Convert a SQL interval to a polars [`Duration`], e.g. '1 day 2 hours' -> `1d2h`.
+fn interval_to_duration(
+    value: &SqlExpr,
+    leading_field: &Option<DateTimeField>,
+    negate: bool,
+) -> PolarsResult<Duration> {
+    let value = match value {
+        SqlExpr::Value(SqlValue::SingleQuotedString(s)) => s.as_str(),
+        _ => polars_bail!(ComputeError: "invalid INTERVAL value: {}", value),
+    };
+    let value = match leading_field {
+        Some(field) => format!("{} {}", value, field),
+        None => value.to_string(),
+    };
+
+    // split into (number, unit) pairs, allowing both '3 days' and '3days'
+    let mut tokens = vec![];
+    for token in value.split_whitespace() {
+        match token.find(|c: char| c.is_ascii_alphabetic()) {
+            Some(idx) if idx > 0 => {
+                tokens.push(&token[..idx]);
+                tokens.push(&token[idx..]);
+            }
+            _ => tokens.push(token),
+        }
+    }
+    polars_ensure!(
+        !tokens.is_empty() && tokens.len() % 2 == 0,
+        ComputeError: "invalid INTERVAL value: '{}'", value
+    );
+
+    let mut negative = None;
+    let mut duration = String::new();
+    for pair in tokens.chunks(2) {
+        let (n, unit) = (pair[0], pair[1]);
+        let n = n
+            .parse::<i64>()
+            .map_err(|_| polars_err!(ComputeError: "invalid INTERVAL value: '{}'", value))?;
+        polars_ensure!(
+            negative.map_or(true, |negative| negative == (n < 0)),
+            ComputeError: "INTERVAL with mixed signs is not supported: '{}'", value
+        );
+        negative = Some(n < 0);
+        let unit = match unit.to_lowercase().as_str() {
+            "y" | "year" | "years" => "y",
+            "q" | "quarter" | "quarters" => "q",
+            "mo" | "month" | "months" => "mo",
+            "w" | "week" | "weeks" => "w",
+            "d" | "day" | "days" => "d",
+            "h" | "hour" | "hours" => "h",
+            "m" | "min" | "mins" | "minute" | "minutes" => "m",
+            "s" | "sec" | "secs" | "second" | "seconds" => "s",
+            "ms" | "millisecond" | "milliseconds" => "ms",
+            "us" | "microsecond" | "microseconds" => "us",
+            "ns" | "nanosecond" | "nanoseconds" => "ns",
+            unit => polars_bail!(ComputeError: "invalid INTERVAL unit: '{}'", unit),
+        };
+        duration.push_str(&format!("{}{}", n.abs(), unit));
+    }
+    if (negative == Some(true)) != negate {
+        duration.insert(0, '-');
+    }
+    Ok(Duration::parse(&duration))
+}
+
+/// Split a predicate on its top-level `AND`s.
+pub(crate) fn split_conjunction(expr: &SqlExpr) -> Vec<&SqlExpr> {
+    match expr {
+        SqlExpr::BinaryOp {
+            left,
+            op: BinaryOperator::And,
+            right,
+        } => {
+            let mut out = split_conjunction(left);
+            out.extend(split_conjunction(right));
+            out
+        }
+        SqlExpr::Nested(expr) => split_conjunction(expr),
+        _ => vec![expr],
+    }
+}
+
+/// Get the left and right join keys of a join constraint.
+///
+/// The `right_name` is the name (or alias) of the table that is joined;
+/// qualified columns of any other table are taken from the left side.
 pub(super) fn process_join_constraint(
     constraint: &JoinConstraint,
-    left_name: &str,
     right_name: &str,
-) -> PolarsResult<(Expr, Expr)> {
-    if let JoinConstraint::On(SqlExpr::BinaryOp { left, op, right }) = constraint {
-        match (left.as_ref(), right.as_ref()) {
-            (SqlExpr::CompoundIdentifier(left), SqlExpr::CompoundIdentifier(right)) => {
-                if left.len() == 2 && right.len() == 2 {
-                    let tbl_a = &left[0].value;
-                    let col_a = &left[1].value;
-                    let tbl_b = &right[0].value;
-                    let col_b = &right[1].value;
-
-                    if let BinaryOperator::Eq = op {
-                        if left_name == tbl_a && right_name == tbl_b {
-                            return Ok((col(col_a), col(col_b)));
-                        } else if left_name == tbl_b && right_name == tbl_a {
-                            return Ok((col(col_b), col(col_a)));
+) -> PolarsResult<(Vec<Expr>, Vec<Expr>)> {
+    let mut left_on = vec![];
+    let mut right_on = vec![];
+    match constraint {
+        JoinConstraint::On(expr) => {
+            for predicate in split_conjunction(expr) {
+                let (left, right) = match predicate {
+                    SqlExpr::BinaryOp {
+                        left,
+                        op: BinaryOperator::Eq,
+                        right,
+                    } => (left.as_ref(), right.as_ref()),
+                    _ => polars_bail!(
+                        InvalidOperation:
+                        "SQL join constraint {:?} is not yet supported", constraint
+                    ),
+                };
+                match (left, right) {
+                    (SqlExpr::CompoundIdentifier(left), SqlExpr::CompoundIdentifier(right))
+                        if left.len() == 2 && right.len() == 2 =>
+                    {
+                        let tbl_a = &left[0].value;
+                        let col_a = &left[1].value;
+                        let tbl_b = &right[0].value;
+                        let col_b = &right[1].value;
+
+                        if tbl_b == right_name && tbl_a != right_name {
+                            left_on.push(col(col_a));
+                            right_on.push(col(col_b));
+                        } else if tbl_a == right_name && tbl_b != right_name {
+                            left_on.push(col(col_b));
+                            right_on.push(col(col_a));
+                        } else {
+                            polars_bail!(
+                                InvalidOperation:
+                                "SQL join constraint {:?} must compare columns of both tables",
+                                predicate
+                            );
                         }
                     }
+                    (SqlExpr::Identifier(left), SqlExpr::Identifier(right)) => {
+                        left_on.push(col(&left.value));
+                        right_on.push(col(&right.value));
+                    }
+                    _ => polars_bail!(
+                        InvalidOperation:
+                        "SQL join constraint {:?} is not yet supported", predicate
+                    ),
                 }
             }
-            (SqlExpr::Identifier(left), SqlExpr::Identifier(right)) => {
-                return Ok((col(&left.value), col(&right.value)))
-            }
-            _ => {}
         }
-    }
-    if let JoinConstraint::Using(idents) = constraint {
-        if !idents.is_empty() {
-            let cols = &idents[0].value;
-            return Ok((col(cols), col(cols)));
+        JoinConstraint::Using(idents) if !idents.is_empty() => {
+            for ident in idents {
+                left_on.push(col(&ident.value));
+                right_on.push(col(&ident.value));
+            }
         }
+        _ => polars_bail!(
+            InvalidOperation: "SQL join constraint {:?} is not yet supported", constraint
+        ),
     }
-    polars_bail!(InvalidOperation: "SQL join constraint {:?} is not yet supported", constraint);
+    Ok((left_on, right_on))
 }
 
 /// parse a SQL expression to a polars expression
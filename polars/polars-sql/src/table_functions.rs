@@ -1,10 +1,14 @@
 use std::str::FromStr;
 
-use polars_core::prelude::{polars_bail, PolarsError, PolarsResult};
+use polars_core::prelude::{
+    polars_bail, polars_ensure, polars_err, DataFrame, PolarsError, PolarsResult,
+};
 #[cfg(feature = "csv")]
 use polars_lazy::prelude::LazyCsvReader;
-use polars_lazy::prelude::LazyFrame;
-use sqlparser::ast::{FunctionArg, FunctionArgExpr};
+use polars_lazy::prelude::{int_range, lit, IntoLazy, LazyFrame};
+use sqlparser::ast::{
+    Expr as SqlExpr, FunctionArg, FunctionArgExpr, UnaryOperator, Value as SqlValue,
+};
 
 /// Table functions that are supported by Polars
 #[allow(clippy::enum_variant_names)]
@@ -12,6 +16,7 @@ pub(crate) enum PolarsTableFunctions {
     /// SQL 'read_csv' function
     /// ```sql
     /// SELECT * FROM read_csv('path/to/file.csv')
+    /// SELECT * FROM read_csv('path/to/*.csv', delimiter => ';', has_header => false)
     /// ```
     #[cfg(feature = "csv")]
     ReadCsv,
@@ -27,12 +32,36 @@ pub(crate) enum PolarsTableFunctions {
     /// ```
     #[cfg(feature = "ipc")]
     ReadIpc,
+    /// SQL 'read_ipc_stream' function
+    /// ```sql
+    /// SELECT * FROM read_ipc_stream('path/to/file.arrows')
+    /// ```
+    #[cfg(feature = "ipc_streaming")]
+    ReadIpcStream,
     /// SQL 'read_json' function. *Only ndjson is currently supported.*
     /// ```sql
     /// SELECT * FROM read_json('path/to/file.json')
     /// ```
     #[cfg(feature = "json")]
     ReadJson,
+    /// SQL 'read_ndjson' function
+    /// ```sql
+    /// SELECT * FROM read_ndjson('path/to/file.ndjson')
+    /// ```
+    #[cfg(feature = "json")]
+    ReadNdJson,
+    /// SQL 'read_avro' function
+    /// ```sql
+    /// SELECT * FROM read_avro('path/to/file.avro')
+    /// ```
+    #[cfg(feature = "avro")]
+    ReadAvro,
+    /// SQL 'generate_series' function.
+    /// Generates the integers from `start` to `stop` (inclusive), with an optional `step`.
+    /// ```sql
+    /// SELECT * FROM generate_series(1, 10, 2)
+    /// ```
+    GenerateSeries,
 }
 
 impl FromStr for PolarsTableFunctions {
@@ -47,8 +76,15 @@ impl FromStr for PolarsTableFunctions {
             "read_parquet" => PolarsTableFunctions::ReadParquet,
             #[cfg(feature = "ipc")]
             "read_ipc" => PolarsTableFunctions::ReadIpc,
+            #[cfg(feature = "ipc_streaming")]
+            "read_ipc_stream" => PolarsTableFunctions::ReadIpcStream,
             #[cfg(feature = "json")]
             "read_json" => PolarsTableFunctions::ReadJson,
+            #[cfg(feature = "json")]
+            "read_ndjson" => PolarsTableFunctions::ReadNdJson,
+            #[cfg(feature = "avro")]
+            "read_avro" => PolarsTableFunctions::ReadAvro,
+            "generate_series" => PolarsTableFunctions::GenerateSeries,
             _ => polars_bail!(ComputeError: "'{}' is not a supported table function", s),
         })
     }
@@ -64,46 +100,303 @@ impl PolarsTableFunctions {
             PolarsTableFunctions::ReadParquet => self.read_parquet(args),
             #[cfg(feature = "ipc")]
             PolarsTableFunctions::ReadIpc => self.read_ipc(args),
+            #[cfg(feature = "ipc_streaming")]
+            PolarsTableFunctions::ReadIpcStream => self.read_ipc_stream(args),
             #[cfg(feature = "json")]
-            PolarsTableFunctions::ReadJson => self.read_ndjson(args),
+            PolarsTableFunctions::ReadJson | PolarsTableFunctions::ReadNdJson => {
+                self.read_ndjson(args)
+            }
+            #[cfg(feature = "avro")]
+            PolarsTableFunctions::ReadAvro => self.read_avro(args),
+            PolarsTableFunctions::GenerateSeries => self.generate_series(args),
             _ => unreachable!(),
         }
     }
 
     #[cfg(feature = "csv")]
     fn read_csv(&self, args: &[FunctionArg]) -> PolarsResult<(String, LazyFrame)> {
+        use polars_io::csv::NullValues;
         use polars_lazy::frame::LazyFileListReader;
-        let path = self.get_file_path_from_arg(&args[0])?;
-        let lf = LazyCsvReader::new(&path).finish()?;
-        Ok((path, lf))
+
+        let args = file_args::TableFunctionArgs::parse(
+            args,
+            &[
+                "delimiter",
+                "has_header",
+                "skip_rows",
+                "n_rows",
+                "infer_schema_length",
+                "ignore_errors",
+                "null_values",
+                "quote_char",
+                "comment_char",
+                "try_parse_dates",
+                "low_memory",
+                "rechunk",
+                "dtypes",
+            ],
+        )?;
+        let dtypes = args
+            .get_str("dtypes")?
+            .map(|dtypes| parse_dtypes(&dtypes))
+            .transpose()?;
+        let mut reader = LazyCsvReader::new(&args.path)
+            .has_header(args.get_bool("has_header")?.unwrap_or(true))
+            .with_skip_rows(args.get_usize("skip_rows")?.unwrap_or(0))
+            .with_n_rows(args.get_usize("n_rows")?)
+            .with_ignore_errors(args.get_bool("ignore_errors")?.unwrap_or(false))
+            .with_null_values(
+                args.get_str("null_values")?
+                    .map(NullValues::AllColumnsSingle),
+            )
+            .with_quote_char(args.get_char("quote_char")?.or(Some(b'"')))
+            .with_comment_char(args.get_char("comment_char")?)
+            .with_try_parse_dates(args.get_bool("try_parse_dates")?.unwrap_or(false))
+            .low_memory(args.get_bool("low_memory")?.unwrap_or(false))
+            .with_rechunk(args.get_bool("rechunk")?.unwrap_or(true))
+            .with_dtype_overwrite(dtypes.as_ref());
+        if let Some(delimiter) = args.get_char("delimiter")? {
+            reader = reader.with_delimiter(delimiter);
+        }
+        if args.options.contains_key("infer_schema_length") {
+            reader = reader.with_infer_schema_length(args.get_usize("infer_schema_length")?);
+        }
+        let lf = reader.finish()?;
+        Ok((args.path, lf))
     }
 
     #[cfg(feature = "parquet")]
     fn read_parquet(&self, args: &[FunctionArg]) -> PolarsResult<(String, LazyFrame)> {
-        let path = self.get_file_path_from_arg(&args[0])?;
-        let lf = LazyFrame::scan_parquet(&path, Default::default())?;
-        Ok((path, lf))
+        use polars_lazy::prelude::ScanArgsParquet;
+
+        let args = file_args::TableFunctionArgs::parse(
+            args,
+            &["n_rows", "rechunk", "low_memory", "use_statistics", "cache"],
+        )?;
+        let defaults = ScanArgsParquet::default();
+        let scan_args = ScanArgsParquet {
+            n_rows: args.get_usize("n_rows")?,
+            rechunk: args.get_bool("rechunk")?.unwrap_or(defaults.rechunk),
+            low_memory: args.get_bool("low_memory")?.unwrap_or(defaults.low_memory),
+            use_statistics: args
+                .get_bool("use_statistics")?
+                .unwrap_or(defaults.use_statistics),
+            cache: args.get_bool("cache")?.unwrap_or(defaults.cache),
+            ..defaults
+        };
+        let lf = LazyFrame::scan_parquet(&args.path, scan_args)?;
+        Ok((args.path, lf))
     }
 
     #[cfg(feature = "ipc")]
     fn read_ipc(&self, args: &[FunctionArg]) -> PolarsResult<(String, LazyFrame)> {
-        let path = self.get_file_path_from_arg(&args[0])?;
-        let lf = LazyFrame::scan_ipc(&path, Default::default())?;
-        Ok((path, lf))
+        use polars_lazy::prelude::ScanArgsIpc;
+
+        let args =
+            file_args::TableFunctionArgs::parse(args, &["n_rows", "rechunk", "memmap", "cache"])?;
+        let defaults = ScanArgsIpc::default();
+        let scan_args = ScanArgsIpc {
+            n_rows: args.get_usize("n_rows")?,
+            rechunk: args.get_bool("rechunk")?.unwrap_or(defaults.rechunk),
+            memmap: args.get_bool("memmap")?.unwrap_or(defaults.memmap),
+            cache: args.get_bool("cache")?.unwrap_or(defaults.cache),
+            ..defaults
+        };
+        let lf = LazyFrame::scan_ipc(&args.path, scan_args)?;
+        Ok((args.path, lf))
     }
+
+    #[cfg(feature = "ipc_streaming")]
+    fn read_ipc_stream(&self, args: &[FunctionArg]) -> PolarsResult<(String, LazyFrame)> {
+        use polars_lazy::frame::LazyFileListReader;
+        use polars_lazy::prelude::LazyIpcStreamReader;
+
+        let args = file_args::TableFunctionArgs::parse(args, &["n_rows"])?;
+        let lf = LazyIpcStreamReader::new(&args.path)
+            .with_n_rows(args.get_usize("n_rows")?)
+            .finish()?;
+        Ok((args.path, lf))
+    }
+
     #[cfg(feature = "json")]
     fn read_ndjson(&self, args: &[FunctionArg]) -> PolarsResult<(String, LazyFrame)> {
         use polars_lazy::frame::LazyFileListReader;
         use polars_lazy::prelude::LazyJsonLineReader;
 
-        let path = self.get_file_path_from_arg(&args[0])?;
-        let lf = LazyJsonLineReader::new(path.clone()).finish()?;
-        Ok((path, lf))
+        let args = file_args::TableFunctionArgs::parse(
+            args,
+            &[
+                "n_rows",
+                "infer_schema_length",
+                "batch_size",
+                "low_memory",
+                "rechunk",
+            ],
+        )?;
+        let mut reader = LazyJsonLineReader::new(args.path.clone())
+            .with_n_rows(args.get_usize("n_rows")?)
+            .with_batch_size(args.get_usize("batch_size")?)
+            .low_memory(args.get_bool("low_memory")?.unwrap_or(false))
+            .with_rechunk(args.get_bool("rechunk")?.unwrap_or(true));
+        if args.options.contains_key("infer_schema_length") {
+            reader = reader.with_infer_schema_length(args.get_usize("infer_schema_length")?);
+        }
+        let lf = reader.finish()?;
+        Ok((args.path, lf))
+    }
+
+    #[cfg(feature = "avro")]
+    fn read_avro(&self, args: &[FunctionArg]) -> PolarsResult<(String, LazyFrame)> {
+        use polars_lazy::frame::LazyFileListReader;
+        use polars_lazy::prelude::LazyAvroReader;
+
+        let args = file_args::TableFunctionArgs::parse(args, &["n_rows"])?;
+        let lf = LazyAvroReader::new(&args.path)
+            .with_n_rows(args.get_usize("n_rows")?)
+            .finish()?;
+        Ok((args.path, lf))
+    }
+
+    fn generate_series(&self, args: &[FunctionArg]) -> PolarsResult<(String, LazyFrame)> {
+        let values = args
+            .iter()
+            .map(|arg| match arg {
+                FunctionArg::Unnamed(FunctionArgExpr::Expr(e)) => get_int(e),
+                _ => polars_bail!(
+                    ComputeError: "generate_series only accepts integer arguments; received: {}", arg
+                ),
+            })
+            .collect::<PolarsResult<Vec<_>>>()?;
+        let (start, stop, step) = match values.as_slice() {
+            [start, stop] => (*start, *stop, 1),
+            [start, stop, step] => (*start, *stop, *step),
+            _ => polars_bail!(
+                ComputeError: "generate_series expects 2 or 3 arguments; received: {}", args.len()
+            ),
+        };
+        polars_ensure!(step != 0, ComputeError: "generate_series step must not be zero");
+        // the range is exclusive, while `stop` is included in the series
+        let end = if step > 0 { stop + 1 } else { stop - 1 };
+        let name = "generate_series";
+        let lf = DataFrame::empty()
+            .lazy()
+            .select([int_range(lit(start), lit(end), step).alias(name)]);
+        Ok((name.to_string(), lf))
+    }
+}
+
+/// The arguments of the table functions that read files.
+#[cfg(any(
+    feature = "csv",
+    feature = "parquet",
+    feature = "ipc",
+    feature = "ipc_streaming",
+    feature = "json",
+    feature = "avro"
+))]
+mod file_args {
+    use polars_core::prelude::PlHashMap;
+
+    use super::*;
+
+    /// The path and named options passed to a table function,
+    /// e.g. `read_csv('file.csv', has_header => false)`.
+    pub(super) struct TableFunctionArgs {
+        pub(super) path: String,
+        pub(super) options: PlHashMap<String, SqlExpr>,
     }
 
-    #[allow(dead_code)]
-    fn get_file_path_from_arg(&self, arg: &FunctionArg) -> PolarsResult<String> {
-        use sqlparser::ast::{Expr as SqlExpr, Value as SqlValue};
+    impl TableFunctionArgs {
+        pub(super) fn parse(args: &[FunctionArg], allowed: &[&str]) -> PolarsResult<Self> {
+            let (path, options) = match args {
+                [path, options @ ..] => (get_file_path_from_arg(path)?, options),
+                [] => polars_bail!(ComputeError: "a file path is required as the first parameter"),
+            };
+            let mut out = PlHashMap::new();
+            for option in options {
+                match option {
+                    FunctionArg::Named {
+                        name,
+                        arg: FunctionArgExpr::Expr(value),
+                        ..
+                    } => {
+                        let name = name.value.to_lowercase();
+                        polars_ensure!(
+                            allowed.contains(&name.as_str()),
+                            ComputeError: "unknown option '{}'; supported options are: {}",
+                            name, allowed.join(", ")
+                        );
+                        out.insert(name, value.clone());
+                    }
+                    _ => polars_bail!(
+                        ComputeError:
+                        "options must be given as `name => value`; received: {}", option
+                    ),
+                }
+            }
+            Ok(Self { path, options: out })
+        }
+
+        #[cfg(any(
+            feature = "csv",
+            feature = "parquet",
+            feature = "ipc",
+            feature = "json"
+        ))]
+        pub(super) fn get_bool(&self, name: &str) -> PolarsResult<Option<bool>> {
+            self.options
+                .get(name)
+                .map(|e| match e {
+                    SqlExpr::Value(SqlValue::Boolean(b)) => Ok(*b),
+                    _ => polars_bail!(
+                        ComputeError: "option '{}' must be a boolean; received: {}", name, e
+                    ),
+                })
+                .transpose()
+        }
+
+        pub(super) fn get_usize(&self, name: &str) -> PolarsResult<Option<usize>> {
+            match self.options.get(name) {
+                None | Some(SqlExpr::Value(SqlValue::Null)) => Ok(None),
+                Some(e) => {
+                    let n = get_int(e)?;
+                    polars_ensure!(
+                        n >= 0,
+                        ComputeError: "option '{}' must not be negative; received: {}", name, e
+                    );
+                    Ok(Some(n as usize))
+                }
+            }
+        }
+
+        #[cfg(feature = "csv")]
+        pub(super) fn get_str(&self, name: &str) -> PolarsResult<Option<String>> {
+            self.options
+                .get(name)
+                .map(|e| match e {
+                    SqlExpr::Value(SqlValue::SingleQuotedString(s)) => Ok(s.clone()),
+                    _ => polars_bail!(
+                        ComputeError: "option '{}' must be a string; received: {}", name, e
+                    ),
+                })
+                .transpose()
+        }
+
+        #[cfg(feature = "csv")]
+        pub(super) fn get_char(&self, name: &str) -> PolarsResult<Option<u8>> {
+            self.get_str(name)?
+                .map(|s| match s.as_bytes() {
+                    [c] => Ok(*c),
+                    _ => polars_bail!(
+                        ComputeError:
+                        "option '{}' must be a single character; received: '{}'", name, s
+                    ),
+                })
+                .transpose()
+        }
+    }
+
+    fn get_file_path_from_arg(arg: &FunctionArg) -> PolarsResult<String> {
         match arg {
             FunctionArg::Unnamed(FunctionArgExpr::Expr(SqlExpr::Value(
                 SqlValue::SingleQuotedString(s),
@@ -116,6 +409,40 @@ impl PolarsTableFunctions {
     }
 }
 
+fn get_int(expr: &SqlExpr) -> PolarsResult<i64> {
+    match expr {
+        SqlExpr::Value(SqlValue::Number(n, _)) => n
+            .parse::<i64>()
+            .map_err(|_| polars_err!(ComputeError: "expected an integer; received: {}", n)),
+        SqlExpr::UnaryOp {
+            op: UnaryOperator::Minus,
+            expr,
+        } => Ok(-get_int(expr)?),
+        _ => polars_bail!(ComputeError: "expected an integer; received: {}", expr),
+    }
+}
+
+/// Parse a schema given as `'a INT, b VARCHAR'`.
+#[cfg(feature = "csv")]
+fn parse_dtypes(dtypes: &str) -> PolarsResult<polars_core::prelude::Schema> {
+    use polars_core::prelude::Field;
+    use sqlparser::dialect::GenericDialect;
+    use sqlparser::parser::Parser;
+
+    use crate::sql_expr::map_sql_polars_datatype;
+
+    let mut parser = Parser::new(&GenericDialect)
+        .try_with_sql(dtypes)
+        .map_err(|e| polars_err!(ComputeError: "invalid dtypes '{}': {}", dtypes, e))?;
+    let columns = parser
+        .parse_comma_separated(|p| Ok((p.parse_identifier()?, p.parse_data_type()?)))
+        .map_err(|e| polars_err!(ComputeError: "invalid dtypes '{}': {}", dtypes, e))?;
+    columns
+        .iter()
+        .map(|(name, dtype)| Ok(Field::new(&name.value, map_sql_polars_datatype(dtype)?)))
+        .collect()
+}
+
 impl PolarsTableFunctions {
     // list sql names of all table functions
     pub(crate) fn keywords() -> &'static [&'static str] {
@@ -126,8 +453,15 @@ impl PolarsTableFunctions {
             "read_parquet",
             #[cfg(feature = "ipc")]
             "read_ipc",
+            #[cfg(feature = "ipc_streaming")]
+            "read_ipc_stream",
             #[cfg(feature = "json")]
             "read_json",
+            #[cfg(feature = "json")]
+            "read_ndjson",
+            #[cfg(feature = "avro")]
+            "read_avro",
+            "generate_series",
         ]
     }
 }
@@ -0,0 +1,108 @@
+use polars_core::prelude::*;
+use polars_lazy::prelude::*;
+use polars_sql::*;
+
+fn create_ctx() -> SQLContext {
+    let df = df! {
+        "id" => [1, 2, 3, 4, 5, 6],
+        "grp" => [Some("a"), Some("a"), Some("b"), Some("b"), Some("b"), None],
+        "value" => [Some(10i64), None, Some(30), Some(20), None, Some(5)],
+    }
+    .unwrap();
+    let mut ctx = SQLContext::new();
+    ctx.register("df", df.lazy());
+    ctx
+}
+
+#[test]
+fn test_having_without_projection() {
+    let mut ctx = create_ctx();
+    let sql = r#"
+    SELECT grp
+    FROM df
+    GROUP BY grp
+    HAVING COUNT(*) > 1 AND MAX(value) >= 20
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df! {
+        "grp" => ["b"],
+    }
+    .unwrap();
+    assert!(actual.frame_equal(&expected));
+
+    let sql = r#"
+    SELECT grp, SUM(value) AS total
+    FROM df
+    GROUP BY grp
+    HAVING total > 5
+    ORDER BY grp
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df! {
+        "grp" => ["a", "b"],
+        "total" => [10i64, 50],
+    }
+    .unwrap();
+    assert!(actual.frame_equal(&expected));
+
+    let sql = "SELECT COUNT(*) AS n FROM df HAVING COUNT(*) > 10";
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    assert_eq!(actual.height(), 0);
+}
+
+#[test]
+fn test_qualify() {
+    let mut ctx = create_ctx();
+    let sql = r#"
+    SELECT id, grp
+    FROM df
+    WHERE grp IS NOT NULL
+    QUALIFY ROW_NUMBER() OVER (PARTITION BY grp ORDER BY id DESC) = 1
+    ORDER BY id
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df! {
+        "id" => [2, 5],
+        "grp" => ["a", "b"],
+    }
+    .unwrap();
+    assert!(actual.frame_equal(&expected));
+
+    let sql = r#"
+    SELECT id, SUM(id) OVER (PARTITION BY grp) AS total
+    FROM df
+    QUALIFY total > 3 AND id < 4
+    ORDER BY id
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    assert_eq!(actual.shape(), (1, 2));
+    assert_eq!(
+        actual.column("id").unwrap().get(0).unwrap(),
+        AnyValue::Int32(3)
+    );
+}
+
+#[test]
+fn test_order_by_nulls_first_last() {
+    let mut ctx = create_ctx();
+    let sql = r#"
+    SELECT grp, value
+    FROM df
+    ORDER BY grp NULLS LAST, value DESC NULLS FIRST
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df! {
+        "grp" => [Some("a"), Some("a"), Some("b"), Some("b"), Some("b"), None],
+        "value" => [None, Some(10i64), None, Some(30), Some(20), Some(5)],
+    }
+    .unwrap();
+    assert!(actual.frame_equal_missing(&expected));
+
+    let sql = "SELECT value FROM df ORDER BY value NULLS LAST";
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df! {
+        "value" => [Some(5i64), Some(10), Some(20), Some(30), None, None],
+    }
+    .unwrap();
+    assert!(actual.frame_equal_missing(&expected));
+}
@@ -0,0 +1,113 @@
+use polars_core::prelude::*;
+use polars_lazy::prelude::*;
+use polars_sql::*;
+
+fn create_ctx() -> SQLContext {
+    let df = df! {
+        "a" => [1i64, 2, 3],
+        "b" => ["x", "y", "z"],
+    }
+    .unwrap();
+    let mut ctx = SQLContext::new();
+    ctx.register("df", df.lazy());
+    ctx
+}
+
+fn select_all(ctx: &mut SQLContext, table: &str) -> DataFrame {
+    ctx.execute(&format!("SELECT * FROM {table} ORDER BY a"))
+        .unwrap()
+        .collect()
+        .unwrap()
+}
+
+#[test]
+fn test_insert_values() {
+    let mut ctx = create_ctx();
+    ctx.execute("INSERT INTO df VALUES (4, 'w'), (5, NULL)")
+        .unwrap();
+    let expected = df! {
+        "a" => [1i64, 2, 3, 4, 5],
+        "b" => [Some("x"), Some("y"), Some("z"), Some("w"), None],
+    }
+    .unwrap();
+    assert!(select_all(&mut ctx, "df").frame_equal_missing(&expected));
+}
+
+#[test]
+fn test_insert_select_with_columns() {
+    let mut ctx = create_ctx();
+    ctx.execute("INSERT INTO df (a) SELECT a + 10 FROM df WHERE a > 1")
+        .unwrap();
+    let expected = df! {
+        "a" => [1i64, 2, 3, 12, 13],
+        "b" => [Some("x"), Some("y"), Some("z"), None, None],
+    }
+    .unwrap();
+    assert!(select_all(&mut ctx, "df").frame_equal_missing(&expected));
+
+    assert!(ctx.execute("INSERT INTO df VALUES (1)").is_err());
+    assert!(ctx.execute("INSERT INTO df (c) VALUES (1)").is_err());
+}
+
+#[test]
+fn test_delete() {
+    let mut ctx = create_ctx();
+    ctx.execute("DELETE FROM df WHERE a >= 2 AND b <> 'z'")
+        .unwrap();
+    let expected = df! {
+        "a" => [1i64, 3],
+        "b" => ["x", "z"],
+    }
+    .unwrap();
+    assert!(select_all(&mut ctx, "df").frame_equal(&expected));
+
+    ctx.execute("DELETE FROM df").unwrap();
+    assert_eq!(select_all(&mut ctx, "df").height(), 0);
+}
+
+#[test]
+fn test_update() {
+    let mut ctx = create_ctx();
+    ctx.execute("UPDATE df SET a = a * 10, b = 'updated' WHERE a > 1")
+        .unwrap();
+    let expected = df! {
+        "a" => [1i64, 20, 30],
+        "b" => ["x", "updated", "updated"],
+    }
+    .unwrap();
+    assert!(select_all(&mut ctx, "df").frame_equal(&expected));
+
+    assert!(ctx.execute("UPDATE df SET c = 1").is_err());
+}
+
+#[test]
+fn test_truncate() {
+    let mut ctx = create_ctx();
+    ctx.execute("TRUNCATE TABLE df").unwrap();
+    let actual = select_all(&mut ctx, "df");
+    assert_eq!(actual.height(), 0);
+    assert_eq!(actual.get_column_names(), &["a", "b"]);
+}
+
+#[test]
+fn test_alter_table() {
+    let mut ctx = create_ctx();
+    ctx.execute("ALTER TABLE df RENAME TO tbl").unwrap();
+    assert_eq!(ctx.get_tables(), vec!["tbl"]);
+
+    ctx.execute("ALTER TABLE tbl RENAME COLUMN b TO c").unwrap();
+    ctx.execute("ALTER TABLE tbl ADD COLUMN d INT DEFAULT 0")
+        .unwrap();
+    ctx.execute("ALTER TABLE tbl ADD COLUMN e VARCHAR").unwrap();
+    ctx.execute("ALTER TABLE tbl DROP COLUMN c").unwrap();
+    let expected = df! {
+        "a" => [1i64, 2, 3],
+        "d" => [0i32, 0, 0],
+        "e" => [None::<&str>, None, None],
+    }
+    .unwrap();
+    assert!(select_all(&mut ctx, "tbl").frame_equal_missing(&expected));
+
+    assert!(ctx.execute("ALTER TABLE tbl ADD COLUMN a INT").is_err());
+    assert!(ctx.execute("ALTER TABLE df RENAME TO other").is_err());
+}
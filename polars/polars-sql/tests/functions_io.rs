@@ -106,3 +106,167 @@ fn read_ipc_tbl() {
     assert_eq!(df_2.height(), 27);
     assert_eq!(df_2.width(), 4);
 }
+
+#[test]
+#[cfg(feature = "csv")]
+fn read_csv_tbl_func_options() {
+    let mut context = SQLContext::new();
+    let sql = r#"
+            SELECT *
+            FROM read_csv(
+                '../../examples/datasets/foods1.csv',
+                has_header => false,
+                skip_rows => 1,
+                n_rows => 5,
+                dtypes => 'column_2 DOUBLE'
+            )"#;
+    let df = context.execute(sql).unwrap().collect().unwrap();
+    assert_eq!(df.height(), 5);
+    assert_eq!(
+        df.get_column_names(),
+        &["column_1", "column_2", "column_3", "column_4"]
+    );
+    assert_eq!(df.column("column_2").unwrap().dtype(), &DataType::Float64);
+
+    let sql = r#"
+            SELECT * FROM read_csv('../../examples/datasets/foods1.csv', header => true)"#;
+    assert!(context.execute(sql).is_err());
+}
+
+#[test]
+#[cfg(feature = "csv")]
+fn read_csv_tbl_func_glob() {
+    let mut context = SQLContext::new();
+    let sql = r#"
+            SELECT *
+            FROM read_csv('../../examples/datasets/foods[12].csv')"#;
+    let df = context.execute(sql).unwrap().collect().unwrap();
+    assert_eq!(df.height(), 54);
+    assert_eq!(df.width(), 4);
+}
+
+#[test]
+fn generate_series_tbl_func() {
+    let mut context = SQLContext::new();
+    let df = context
+        .execute("SELECT * FROM generate_series(1, 10, 3)")
+        .unwrap()
+        .collect()
+        .unwrap();
+    let expected = df! {
+        "generate_series" => [1i64, 4, 7, 10],
+    }
+    .unwrap();
+    assert!(df.frame_equal(&expected));
+
+    let df = context
+        .execute("SELECT generate_series AS n FROM generate_series(3, 1, -1)")
+        .unwrap()
+        .collect()
+        .unwrap();
+    let expected = df! {
+        "n" => [3i64, 2, 1],
+    }
+    .unwrap();
+    assert!(df.frame_equal(&expected));
+
+    assert!(context
+        .execute("SELECT * FROM generate_series(1, 10, 0)")
+        .is_err());
+}
+
+#[test]
+#[cfg(feature = "parquet")]
+fn copy_to_parquet() {
+    let tmp = tempdir::TempDir::new("polars-sql-copy").unwrap();
+    let path = tmp.path().join("foods.parquet");
+    let mut context = SQLContext::new();
+    let sql = format!(
+        r#"
+            COPY (SELECT category, calories
+                  FROM read_csv('../../examples/datasets/foods1.csv')
+                  WHERE calories > 100)
+            TO '{}' (FORMAT parquet, COMPRESSION snappy)"#,
+        path.display()
+    );
+    context.execute(&sql).unwrap().collect().unwrap();
+
+    let expected = LazyCsvReader::new("../../examples/datasets/foods1.csv")
+        .finish()
+        .unwrap()
+        .filter(col("calories").gt(lit(100)))
+        .select([col("category"), col("calories")])
+        .collect()
+        .unwrap();
+    let df = LazyFrame::scan_parquet(&path, Default::default())
+        .unwrap()
+        .collect()
+        .unwrap();
+    assert!(df.frame_equal(&expected));
+}
+
+#[test]
+#[cfg(feature = "parquet")]
+fn copy_to_parquet_partitioned() {
+    let tmp = tempdir::TempDir::new("polars-sql-copy-partitioned").unwrap();
+    let root = tmp.path().join("foods");
+    let mut context = SQLContext::new();
+    context.register(
+        "foods",
+        LazyCsvReader::new("../../examples/datasets/foods1.csv")
+            .finish()
+            .unwrap(),
+    );
+    let sql = format!(
+        "COPY foods TO '{}' (FORMAT parquet, PARTITION_BY (category))",
+        root.display()
+    );
+    context.execute(&sql).unwrap().collect().unwrap();
+
+    let df = LazyFrame::scan_parquet(
+        root.join("category=fruit").join("part-0.parquet"),
+        Default::default(),
+    )
+    .unwrap()
+    .collect()
+    .unwrap();
+    assert_eq!(df.height(), 7);
+
+    // partitions are only written as parquet
+    let sql = format!(
+        "COPY foods TO '{}' (FORMAT csv, PARTITION_BY (category))",
+        root.display()
+    );
+    assert!(context.execute(&sql).is_err());
+}
+
+#[test]
+#[cfg(feature = "ipc_streaming")]
+fn read_ipc_stream_tbl_func_glob() {
+    use polars_io::ipc::IpcStreamWriter;
+    use polars_io::SerWriter;
+
+    let tmp = tempdir::TempDir::new("polars-sql-read-ipc-stream").unwrap();
+    let dir = tmp.path();
+    for (i, values) in [[1, 2, 3], [4, 5, 6]].iter().enumerate() {
+        let mut df = df! {
+            "a" => values,
+            "b" => ["x", "y", "z"],
+        }
+        .unwrap();
+        let file = std::fs::File::create(dir.join(format!("part-{i}.arrows"))).unwrap();
+        IpcStreamWriter::new(file).finish(&mut df).unwrap();
+    }
+
+    let mut context = SQLContext::new();
+    let sql = format!(
+        "SELECT a FROM read_ipc_stream('{}', n_rows => 4) ORDER BY a",
+        dir.join("*.arrows").display()
+    );
+    let df = context.execute(&sql).unwrap().collect().unwrap();
+    let expected = df! {
+        "a" => [1, 2, 3, 4],
+    }
+    .unwrap();
+    assert!(df.frame_equal(&expected));
+}
@@ -30,3 +30,73 @@ fn test_describe() {
 
     assert_eq!(actual, expected);
 }
+
+#[test]
+fn test_explain_analyze() {
+    let lf = df! {
+      "year"=> [2018, 2019, 2018],
+      "country"=> ["US", "US", "NL"],
+      "sales"=> [1000.0, 2000.0, 3000.0]
+    }
+    .unwrap()
+    .lazy();
+    let mut context = SQLContext::new();
+    context.register("df", lf.clone());
+    let sql = r#"EXPLAIN ANALYZE SELECT year, SUM(sales) FROM df GROUP BY year ORDER BY year"#;
+    let df = context.execute(sql).unwrap().collect().unwrap();
+    assert_eq!(
+        df.get_column_names(),
+        &["Logical Plan", "rows", "time (µs)"]
+    );
+
+    let plan = df.column("Logical Plan").unwrap().utf8().unwrap();
+    let rows = df.column("rows").unwrap().idx().unwrap();
+    let line = |prefix: &str| {
+        plan.into_iter()
+            .position(|line| line.unwrap().trim_start().starts_with(prefix))
+            .unwrap()
+    };
+    assert_eq!(rows.get(line("SORT BY")), Some(2));
+    assert_eq!(rows.get(line("AGGREGATE")), Some(2));
+    assert_eq!(rows.get(line("DF ")), None);
+}
+
+#[test]
+fn test_explain_analyze_join_of_filtered_inputs() {
+    // the slices keep the filters from being pushed down into the scans
+    let x = df! {
+        "a" => [1, 2, 3, 4],
+        "b" => ["p", "q", "r", "s"],
+    }
+    .unwrap()
+    .lazy()
+    .limit(10)
+    .filter(col("a").gt(lit(1)));
+    let y = df! {
+        "a" => [1, 2, 3, 4, 5],
+        "c" => [10, 20, 30, 40, 50],
+    }
+    .unwrap()
+    .lazy()
+    .limit(10)
+    .filter(col("a").lt(lit(3)));
+    let mut context = SQLContext::new();
+    context.register("x", x);
+    context.register("y", y);
+    let sql = "EXPLAIN ANALYZE SELECT x.a, b, c FROM x INNER JOIN y ON x.a = y.a";
+    let df = context.execute(sql).unwrap().collect().unwrap();
+
+    let plan = df.column("Logical Plan").unwrap().utf8().unwrap();
+    let rows = df.column("rows").unwrap().idx().unwrap();
+    let lines = |prefix: &str| {
+        plan.into_iter()
+            .enumerate()
+            .filter(|(_, line)| line.unwrap().trim_start().starts_with(prefix))
+            .map(|(i, _)| rows.get(i))
+            .collect::<Vec<_>>()
+    };
+    // the filters of both sides of the join are run in parallel, but each line gets the
+    // rows of its own filter
+    assert_eq!(lines("FILTER"), [Some(3), Some(2)]);
+    assert_eq!(lines("INNER JOIN"), [Some(1)]);
+}
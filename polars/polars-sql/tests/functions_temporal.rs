@@ -0,0 +1,142 @@
+use polars_core::prelude::*;
+use polars_lazy::prelude::*;
+use polars_sql::*;
+
+fn create_ctx() -> SQLContext {
+    let df = df! {
+        "dt" => ["2023-01-15 10:30:00", "2024-02-29 23:59:59"],
+    }
+    .unwrap()
+    .lazy()
+    .with_column(col("dt").str().strptime(
+        DataType::Datetime(TimeUnit::Microseconds, None),
+        StrptimeOptions {
+            format: Some("%Y-%m-%d %H:%M:%S".into()),
+            ..Default::default()
+        },
+    ));
+    let mut ctx = SQLContext::new();
+    ctx.register("df", df);
+    ctx
+}
+
+fn assert_int_column(df: &DataFrame, name: &str, expected: &[i64]) {
+    let actual = df.column(name).unwrap().cast(&DataType::Int64).unwrap();
+    let actual = actual
+        .i64()
+        .unwrap()
+        .into_no_null_iter()
+        .collect::<Vec<_>>();
+    assert_eq!(actual, expected, "{name}");
+}
+
+fn assert_str_column(df: &DataFrame, name: &str, expected: &[&str]) {
+    let actual = df.column(name).unwrap();
+    let actual = actual
+        .utf8()
+        .unwrap()
+        .into_no_null_iter()
+        .collect::<Vec<_>>();
+    assert_eq!(actual, expected, "{name}");
+}
+
+#[test]
+fn test_extract_date_part() {
+    let mut ctx = create_ctx();
+    let sql = r#"
+    SELECT
+        EXTRACT(YEAR FROM dt) AS year,
+        DATE_PART('month', dt) AS month,
+        EXTRACT(DOY FROM dt) AS doy,
+        EXTRACT(DOW FROM dt) AS dow,
+        DATE_PART('hour', dt) AS hour
+    FROM df
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    assert_int_column(&actual, "year", &[2023, 2024]);
+    assert_int_column(&actual, "month", &[1, 2]);
+    assert_int_column(&actual, "doy", &[15, 60]);
+    assert_int_column(&actual, "dow", &[0, 4]);
+    assert_int_column(&actual, "hour", &[10, 23]);
+
+    assert!(ctx
+        .execute("SELECT DATE_PART('fortnight', dt) FROM df")
+        .is_err());
+}
+
+#[test]
+fn test_date_trunc_strftime() {
+    let mut ctx = create_ctx();
+    let sql = r#"
+    SELECT
+        STRFTIME(DATE_TRUNC('month', dt), '%Y-%m-%d %H:%M:%S') AS month,
+        STRFTIME(DATE_TRUNC('hour', dt), '%Y-%m-%d %H:%M:%S') AS hour
+    FROM df
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    assert_str_column(
+        &actual,
+        "month",
+        &["2023-01-01 00:00:00", "2024-02-01 00:00:00"],
+    );
+    assert_str_column(
+        &actual,
+        "hour",
+        &["2023-01-15 10:00:00", "2024-02-29 23:00:00"],
+    );
+}
+
+#[test]
+fn test_interval_arithmetic() {
+    let mut ctx = create_ctx();
+    let sql = r#"
+    SELECT
+        STRFTIME(dt + INTERVAL '1 month 2 days', '%Y-%m-%d') AS later,
+        STRFTIME(dt - INTERVAL '1' DAY, '%Y-%m-%d') AS earlier,
+        STRFTIME(INTERVAL '30 minutes' + dt, '%H:%M') AS time
+    FROM df
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    assert_str_column(&actual, "later", &["2023-02-17", "2024-03-31"]);
+    assert_str_column(&actual, "earlier", &["2023-01-14", "2024-02-28"]);
+    assert_str_column(&actual, "time", &["11:00", "00:29"]);
+
+    assert!(ctx
+        .execute("SELECT dt + INTERVAL '3 fortnights' FROM df")
+        .is_err());
+}
+
+#[test]
+fn test_datediff_typed_string() {
+    let mut ctx = create_ctx();
+    let sql = r#"
+    SELECT
+        DATEDIFF('day', DATE '2023-01-01', dt) AS days,
+        DATEDIFF(month, DATE '2023-01-01', dt) AS months,
+        DATEDIFF('year', TIMESTAMP '2023-12-31 00:00:00', dt) AS years
+    FROM df
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    assert_int_column(&actual, "days", &[14, 424]);
+    assert_int_column(&actual, "months", &[0, 13]);
+    assert_int_column(&actual, "years", &[0, 1]);
+}
+
+#[test]
+fn test_strptime_now() {
+    let mut ctx = create_ctx();
+    let sql = r#"
+    SELECT
+        EXTRACT(MINUTE FROM STRPTIME('2023-05-06 07:08:09', '%Y-%m-%d %H:%M:%S')) AS minute,
+        NOW() AS now,
+        CURRENT_DATE AS today
+    FROM df
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    assert_int_column(&actual, "minute", &[8]);
+    assert!(matches!(
+        actual.column("now").unwrap().dtype(),
+        DataType::Datetime(_, _)
+    ));
+    assert_eq!(actual.column("today").unwrap().dtype(), &DataType::Date);
+}
@@ -0,0 +1,210 @@
+use polars_core::prelude::*;
+use polars_lazy::prelude::*;
+use polars_sql::*;
+
+fn create_ctx() -> SQLContext {
+    let df = df! {
+        "id" => [1, 2, 3, 4, 5, 6],
+        "grp" => ["a", "a", "b", "a", "b", "b"],
+        "value" => [10i64, 30, 20, 20, 50, 15],
+    }
+    .unwrap();
+    let mut ctx = SQLContext::new();
+    ctx.register("df", df.lazy());
+    ctx
+}
+
+#[test]
+fn test_row_number() {
+    let mut ctx = create_ctx();
+    let sql = r#"
+    SELECT id, ROW_NUMBER() OVER (PARTITION BY grp ORDER BY value DESC) AS rn
+    FROM df
+    ORDER BY id
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df! {
+        "id" => [1, 2, 3, 4, 5, 6],
+        "rn" => [3 as IdxSize, 1, 2, 2, 1, 3],
+    }
+    .unwrap();
+    assert!(actual.frame_equal(&expected));
+}
+
+#[test]
+fn test_rank_dense_rank() {
+    let mut ctx = create_ctx();
+    let sql = r#"
+    SELECT
+        id,
+        RANK() OVER (ORDER BY value) AS rank,
+        DENSE_RANK() OVER (ORDER BY value) AS dense_rank
+    FROM df
+    ORDER BY id
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df! {
+        "id" => [1, 2, 3, 4, 5, 6],
+        "rank" => [1 as IdxSize, 5, 3, 3, 6, 2],
+        "dense_rank" => [1 as IdxSize, 4, 3, 3, 5, 2],
+    }
+    .unwrap();
+    assert!(actual.frame_equal(&expected));
+}
+
+#[test]
+fn test_lag_lead() {
+    let mut ctx = create_ctx();
+    let sql = r#"
+    SELECT
+        id,
+        LAG(value) OVER (PARTITION BY grp ORDER BY id) AS prev,
+        LEAD(value, 1, 0) OVER (PARTITION BY grp ORDER BY id) AS next
+    FROM df
+    ORDER BY id
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df! {
+        "id" => [1, 2, 3, 4, 5, 6],
+        "prev" => [None, Some(10i64), None, Some(30), Some(20), Some(50)],
+        "next" => [30i64, 20, 50, 0, 15, 0],
+    }
+    .unwrap();
+    assert!(actual.frame_equal_missing(&expected));
+}
+
+#[test]
+fn test_ntile() {
+    let mut ctx = create_ctx();
+    let sql = r#"
+    SELECT id, NTILE(4) OVER (ORDER BY id) AS bucket
+    FROM df
+    ORDER BY id
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df! {
+        "id" => [1, 2, 3, 4, 5, 6],
+        "bucket" => [1i64, 1, 2, 2, 3, 4],
+    }
+    .unwrap();
+    assert!(actual.frame_equal(&expected));
+}
+
+#[test]
+fn test_rows_frame() {
+    let mut ctx = create_ctx();
+    let sql = r#"
+    SELECT
+        id,
+        SUM(value) OVER (ORDER BY id ROWS BETWEEN 1 PRECEDING AND CURRENT ROW) AS sum_prev,
+        SUM(value) OVER (ORDER BY id ROWS BETWEEN CURRENT ROW AND 1 FOLLOWING) AS sum_next,
+        MAX(value) OVER (ORDER BY id ROWS BETWEEN 1 PRECEDING AND 1 FOLLOWING) AS max_around,
+        MIN(value) OVER (PARTITION BY grp ORDER BY id ROWS UNBOUNDED PRECEDING) AS running_min
+    FROM df
+    ORDER BY id
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df! {
+        "id" => [1, 2, 3, 4, 5, 6],
+        "sum_prev" => [10i64, 40, 50, 40, 70, 65],
+        "sum_next" => [40i64, 50, 40, 70, 65, 15],
+        "max_around" => [30i64, 30, 30, 50, 50, 50],
+        "running_min" => [10i64, 10, 20, 10, 20, 15],
+    }
+    .unwrap();
+    assert!(actual.frame_equal(&expected));
+}
+
+#[test]
+fn test_named_window() {
+    let mut ctx = create_ctx();
+    let sql = r#"
+    SELECT
+        id,
+        ROW_NUMBER() OVER w AS rn,
+        SUM(value) OVER w AS total
+    FROM df
+    WINDOW w AS (PARTITION BY grp ORDER BY id)
+    ORDER BY id
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df! {
+        "id" => [1, 2, 3, 4, 5, 6],
+        "rn" => [1 as IdxSize, 2, 1, 3, 2, 3],
+        "total" => [60i64, 60, 85, 60, 85, 85],
+    }
+    .unwrap();
+    assert!(actual.frame_equal(&expected));
+
+    let sql = "SELECT ROW_NUMBER() OVER unknown FROM df";
+    assert!(ctx.execute(sql).is_err());
+}
+
+#[test]
+fn test_asymmetric_rows_frame() {
+    let mut ctx = create_ctx();
+    let sql = r#"
+    SELECT
+        id,
+        SUM(value) OVER (ORDER BY id ROWS BETWEEN 2 PRECEDING AND 1 FOLLOWING) AS sum_around,
+        MAX(value) OVER (ORDER BY id ROWS BETWEEN 1 PRECEDING AND 2 FOLLOWING) AS max_around,
+        SUM(value) OVER (ORDER BY id ROWS BETWEEN 5 PRECEDING AND 10 FOLLOWING) AS total
+    FROM df
+    ORDER BY id
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df! {
+        "id" => [1, 2, 3, 4, 5, 6],
+        "sum_around" => [40i64, 60, 80, 120, 105, 85],
+        "max_around" => [30i64, 30, 50, 50, 50, 50],
+        "total" => [145i64, 145, 145, 145, 145, 145],
+    }
+    .unwrap();
+    assert!(actual.frame_equal(&expected));
+}
+
+#[test]
+fn test_range_frame_peers() {
+    let mut ctx = create_ctx();
+    let sql = r#"
+    SELECT
+        id,
+        SUM(value) OVER (
+            ORDER BY grp RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW
+        ) AS running,
+        SUM(value) OVER (
+            ORDER BY grp RANGE BETWEEN CURRENT ROW AND UNBOUNDED FOLLOWING
+        ) AS remaining
+    FROM df
+    ORDER BY id
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df! {
+        "id" => [1, 2, 3, 4, 5, 6],
+        "running" => [60i64, 60, 145, 60, 145, 145],
+        "remaining" => [145i64, 145, 85, 145, 85, 85],
+    }
+    .unwrap();
+    assert!(actual.frame_equal(&expected));
+
+    // the default frame is the same RANGE frame
+    let sql = "SELECT SUM(value) OVER (ORDER BY grp) AS running FROM df";
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df! {
+        "running" => [60i64, 60, 60, 145, 145, 145],
+    }
+    .unwrap();
+    assert!(actual.frame_equal(&expected));
+}
+
+#[test]
+fn test_unsupported_frame() {
+    let mut ctx = create_ctx();
+    for frame in [
+        "RANGE BETWEEN 1 PRECEDING AND CURRENT ROW",
+        "GROUPS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW",
+    ] {
+        let sql = format!("SELECT SUM(value) OVER (ORDER BY id {frame}) FROM df");
+        assert!(ctx.execute(&sql).is_err());
+    }
+}
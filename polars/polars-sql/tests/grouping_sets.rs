@@ -0,0 +1,82 @@
+use polars_core::prelude::*;
+use polars_lazy::prelude::*;
+use polars_sql::*;
+
+fn create_ctx() -> SQLContext {
+    let df = df! {
+        "region" => ["eu", "eu", "us", "us"],
+        "product" => ["a", "b", "a", "a"],
+        "sales" => [1i64, 2, 4, 8],
+    }
+    .unwrap();
+    let mut ctx = SQLContext::new();
+    ctx.register("df", df.lazy());
+    ctx
+}
+
+#[test]
+fn test_rollup() {
+    let mut ctx = create_ctx();
+    let sql = r#"
+    SELECT region, product, SUM(sales) AS total
+    FROM df
+    GROUP BY ROLLUP(region, product)
+    ORDER BY region, product
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df! {
+        "region" => [None, Some("eu"), Some("eu"), Some("eu"), Some("us"), Some("us")],
+        "product" => [None, None, Some("a"), Some("b"), None, Some("a")],
+        "total" => [15i64, 3, 1, 2, 12, 12],
+    }
+    .unwrap();
+    assert!(actual.frame_equal_missing(&expected));
+}
+
+#[test]
+fn test_cube_grouping() {
+    let mut ctx = create_ctx();
+    let sql = r#"
+    SELECT
+        region,
+        product,
+        GROUPING(region) AS g_region,
+        GROUPING(region, product) AS g_both,
+        COUNT(sales) AS n
+    FROM df
+    GROUP BY CUBE(region, product)
+    ORDER BY g_both, region, product
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df! {
+        "region" => [Some("eu"), Some("eu"), Some("us"), Some("eu"), Some("us"), None, None, None],
+        "product" => [Some("a"), Some("b"), Some("a"), None, None, Some("a"), Some("b"), None],
+        "g_region" => [0i32, 0, 0, 0, 0, 1, 1, 1],
+        "g_both" => [0i32, 0, 0, 1, 1, 2, 2, 3],
+        "n" => [1 as IdxSize, 1, 2, 2, 2, 3, 1, 4],
+    }
+    .unwrap();
+    assert!(actual.frame_equal_missing(&expected));
+}
+
+#[test]
+fn test_grouping_sets() {
+    let mut ctx = create_ctx();
+    let sql = r#"
+    SELECT region, product, SUM(sales) AS total
+    FROM df
+    GROUP BY GROUPING SETS ((region), (product), ())
+    ORDER BY region, product
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df! {
+        "region" => [None, None, None, Some("eu"), Some("us")],
+        "product" => [None, Some("a"), Some("b"), None, None],
+        "total" => [15i64, 13, 2, 3, 12],
+    }
+    .unwrap();
+    assert!(actual.frame_equal_missing(&expected));
+
+    let sql = "SELECT GROUPING(sales) FROM df GROUP BY ROLLUP(region)";
+    assert!(ctx.execute(sql).is_err());
+}
@@ -0,0 +1,154 @@
+use polars_core::prelude::*;
+use polars_lazy::prelude::*;
+use polars_sql::*;
+
+fn create_ctx() -> SQLContext {
+    let df1 = df! {
+        "a" => [1, 2, 3],
+        "b" => [1, 1, 2],
+        "c" => ["x", "y", "z"],
+    }
+    .unwrap();
+    let df2 = df! {
+        "a" => [2, 3, 4],
+        "b" => [1, 1, 1],
+        "d" => [10, 20, 30],
+    }
+    .unwrap();
+    let mut ctx = SQLContext::new();
+    ctx.register("df1", df1.lazy());
+    ctx.register("df2", df2.lazy());
+    ctx
+}
+
+#[test]
+fn test_right_join() {
+    let mut ctx = create_ctx();
+    let sql = r#"
+    SELECT df2.a, c, d FROM df1 RIGHT JOIN df2 ON df1.a = df2.a
+    ORDER BY d
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df! {
+        "a" => [2, 3, 4],
+        "c" => [Some("y"), Some("z"), None],
+        "d" => [10, 20, 30],
+    }
+    .unwrap();
+    assert!(actual.frame_equal_missing(&expected));
+
+    // the columns of the left table come first, the overlapping column of the right
+    // table is suffixed
+    let sql = r#"
+    SELECT * FROM df1 RIGHT JOIN df2 ON df1.a = df2.a
+    ORDER BY d
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df! {
+        "a" => [2, 3, 4],
+        "b" => [Some(1), Some(2), None],
+        "c" => [Some("y"), Some("z"), None],
+        "b_right" => [1, 1, 1],
+        "d" => [10, 20, 30],
+    }
+    .unwrap();
+    assert!(actual.frame_equal_missing(&expected));
+}
+
+#[test]
+fn test_right_join_on_differently_named_keys() {
+    let mut ctx = create_ctx();
+    let df3 = df! {
+        "k" => [1, 2, 5],
+        "e" => ["p", "q", "r"],
+    }
+    .unwrap();
+    ctx.register("df3", df3.lazy());
+
+    // the key of the left table is kept, and is null where no row of the left table matched
+    let sql = r#"
+    SELECT * FROM df1 RIGHT JOIN df3 ON df1.a = df3.k
+    ORDER BY k
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df! {
+        "a" => [Some(1), Some(2), None],
+        "b" => [Some(1), Some(1), None],
+        "c" => [Some("x"), Some("y"), None],
+        "k" => [1, 2, 5],
+        "e" => ["p", "q", "r"],
+    }
+    .unwrap();
+    assert!(actual.frame_equal_missing(&expected));
+
+    let sql = r#"
+    SELECT df1.a, df3.k FROM df1 RIGHT JOIN df3 ON df1.a = df3.k
+    ORDER BY k
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df! {
+        "a" => [Some(1), Some(2), None],
+        "k" => [1, 2, 5],
+    }
+    .unwrap();
+    assert!(actual.frame_equal_missing(&expected));
+}
+
+#[test]
+fn test_semi_anti_join() {
+    let mut ctx = create_ctx();
+    let sql = r#"
+    SELECT * FROM df1 LEFT SEMI JOIN df2 ON df1.a = df2.a
+    ORDER BY a
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df! {
+        "a" => [2, 3],
+        "b" => [1, 2],
+        "c" => ["y", "z"],
+    }
+    .unwrap();
+    assert!(actual.frame_equal(&expected));
+
+    let sql = r#"
+    SELECT * FROM df1 LEFT ANTI JOIN df2 ON df1.a = df2.a
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df! {
+        "a" => [1],
+        "b" => [1],
+        "c" => ["x"],
+    }
+    .unwrap();
+    assert!(actual.frame_equal(&expected));
+}
+
+#[test]
+fn test_join_multiple_keys() {
+    let mut ctx = create_ctx();
+    let expected = df! {
+        "a" => [2],
+        "b" => [1],
+        "c" => ["y"],
+        "d" => [10],
+    }
+    .unwrap();
+
+    for sql in [
+        "SELECT * FROM df1 JOIN df2 ON df1.a = df2.a AND df2.b = df1.b",
+        "SELECT * FROM df1 JOIN df2 USING (a, b)",
+        "SELECT * FROM df1 NATURAL JOIN df2",
+    ] {
+        let actual = ctx.execute(sql).unwrap().collect().unwrap();
+        assert!(actual.frame_equal(&expected), "{sql}");
+    }
+}
+
+#[test]
+fn test_join_invalid_constraint() {
+    let mut ctx = create_ctx();
+    let sql = r#"
+    SELECT * FROM df1 JOIN df2 ON df1.a > df2.a
+    "#;
+    assert!(ctx.execute(sql).is_err());
+}
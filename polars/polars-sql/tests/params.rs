@@ -0,0 +1,136 @@
+use polars_core::prelude::*;
+use polars_lazy::prelude::*;
+use polars_plan::prelude::LiteralValue;
+use polars_sql::*;
+
+fn create_ctx() -> SQLContext {
+    let df = df! {
+        "a" => [1, 2, 3, 4],
+        "b" => ["w", "x", "y", "z"],
+    }
+    .unwrap();
+    let mut ctx = SQLContext::new();
+    ctx.register("df", df.lazy());
+    ctx
+}
+
+#[test]
+fn test_positional_params() {
+    let mut ctx = create_ctx();
+    for sql in [
+        "SELECT a FROM df WHERE a > $1 AND b <> $2",
+        "SELECT a FROM df WHERE a > ? AND b <> ?",
+    ] {
+        let params = SQLParams::new()
+            .with(LiteralValue::Int32(1))
+            .with(LiteralValue::Utf8("z".into()));
+        let actual = ctx
+            .execute_with_params(sql, &params)
+            .unwrap()
+            .collect()
+            .unwrap();
+        let expected = df! {
+            "a" => [2, 3],
+        }
+        .unwrap();
+        assert!(actual.frame_equal(&expected), "{sql}");
+    }
+}
+
+#[test]
+fn test_named_params() {
+    let mut ctx = create_ctx();
+    let params = SQLParams::new().with_named("name", LiteralValue::Utf8("x".into()));
+    let actual = ctx
+        .execute_with_params(
+            "SELECT a FROM df WHERE b = :name OR b IN ($name, 'y')",
+            &params,
+        )
+        .unwrap()
+        .collect()
+        .unwrap();
+    let expected = df! {
+        "a" => [2, 3],
+    }
+    .unwrap();
+    assert!(actual.frame_equal(&expected));
+}
+
+#[test]
+fn test_prepared_statement_reuse() {
+    let mut ctx = create_ctx();
+    let stmt = ctx
+        .prepare("PREPARE q (INT) AS SELECT a FROM df WHERE a >= $1")
+        .unwrap();
+    for min in [1i32, 3] {
+        let params = SQLParams::new().with(LiteralValue::Int32(min));
+        let actual = ctx
+            .execute_prepared(&stmt, &params)
+            .unwrap()
+            .collect()
+            .unwrap();
+        assert_eq!(actual.height() as i32, 5 - min);
+    }
+
+    // numeric parameters are cast to the declared type
+    let params = SQLParams::new().with(LiteralValue::Int64(2));
+    assert!(ctx.execute_prepared(&stmt, &params).is_ok());
+}
+
+#[test]
+fn test_invalid_params() {
+    let mut ctx = create_ctx();
+    let stmt = ctx
+        .prepare("PREPARE q (INT) AS SELECT a FROM df WHERE a >= $1")
+        .unwrap();
+
+    // wrong type
+    let params = SQLParams::new().with(LiteralValue::Utf8("1".into()));
+    assert!(ctx.execute_prepared(&stmt, &params).is_err());
+    // wrong number of parameters
+    assert!(ctx.execute_prepared(&stmt, &SQLParams::new()).is_err());
+    // unbound named parameter
+    assert!(ctx
+        .execute_with_params("SELECT a FROM df WHERE b = :name", &SQLParams::new())
+        .is_err());
+}
+
+#[test]
+fn test_param_types_from_context() {
+    let mut ctx = create_ctx();
+    // a parameter that is compared with a column must fit the type of that column
+    let params = SQLParams::new().with(LiteralValue::Utf8("x".into()));
+    assert!(ctx
+        .execute_with_params("SELECT a FROM df WHERE a > $1", &params)
+        .is_err());
+    let stmt = ctx.prepare("SELECT a FROM df WHERE $1 = b").unwrap();
+    let params = SQLParams::new().with(LiteralValue::Int32(1));
+    assert!(ctx.execute_prepared(&stmt, &params).is_err());
+    let params = SQLParams::new().with(LiteralValue::Utf8("x".into()));
+    let actual = ctx
+        .execute_prepared(&stmt, &params)
+        .unwrap()
+        .collect()
+        .unwrap();
+    assert_eq!(actual.height(), 1);
+
+    // a parameter that is cast must be castable
+    let sql = "SELECT a FROM df WHERE a = CAST($1 AS INT)";
+    let params = SQLParams::new().with(LiteralValue::Utf8("abc".into()));
+    assert!(ctx.execute_with_params(sql, &params).is_err());
+    let params = SQLParams::new().with(LiteralValue::Utf8("3".into()));
+    let actual = ctx
+        .execute_with_params(sql, &params)
+        .unwrap()
+        .collect()
+        .unwrap();
+    assert_eq!(actual.height(), 1);
+}
+
+#[test]
+fn test_mixed_placeholders() {
+    let ctx = create_ctx();
+    assert!(ctx
+        .prepare("SELECT a FROM df WHERE a > ? AND a < $2")
+        .is_err());
+}
@@ -148,6 +148,97 @@ fn test_union_all() {
     assert!(actual.frame_equal(&expected));
 }
 
+fn create_set_ctx() -> SQLContext {
+    let df1 = df![
+        "a" => [Some(1), Some(1), Some(1), Some(2), None],
+        "b" => ["x", "x", "x", "y", "z"]
+    ]
+    .unwrap();
+    let df2 = df![
+        "c" => [Some(1), Some(1), Some(3), None],
+        "d" => ["x", "x", "w", "z"]
+    ]
+    .unwrap();
+    let mut ctx = SQLContext::new();
+    ctx.register("test", df1.lazy());
+    ctx.register("test2", df2.lazy());
+    ctx
+}
+
+#[test]
+fn test_intersect() {
+    let mut ctx = create_set_ctx();
+    let sql = r#"
+    SELECT * FROM test
+    INTERSECT
+    SELECT * FROM test2
+    ORDER BY a
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df![
+        "a" => [None, Some(1)],
+        "b" => ["z", "x"]
+    ]
+    .unwrap();
+    assert!(actual.frame_equal_missing(&expected));
+
+    let sql = r#"
+    SELECT * FROM test
+    INTERSECT ALL
+    SELECT * FROM test2
+    ORDER BY a
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df![
+        "a" => [None, Some(1), Some(1)],
+        "b" => ["z", "x", "x"]
+    ]
+    .unwrap();
+    assert!(actual.frame_equal_missing(&expected));
+}
+
+#[test]
+fn test_except() {
+    let mut ctx = create_set_ctx();
+    let sql = r#"
+    SELECT * FROM test
+    EXCEPT
+    SELECT * FROM test2
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df![
+        "a" => [2],
+        "b" => ["y"]
+    ]
+    .unwrap();
+    assert!(actual.frame_equal(&expected));
+
+    let sql = r#"
+    SELECT * FROM test
+    EXCEPT ALL
+    SELECT * FROM test2
+    ORDER BY a
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df![
+        "a" => [1, 2],
+        "b" => ["x", "y"]
+    ]
+    .unwrap();
+    assert!(actual.frame_equal(&expected));
+}
+
+#[test]
+fn test_except_column_count_mismatch() {
+    let mut ctx = create_set_ctx();
+    let sql = r#"
+    SELECT a FROM test
+    EXCEPT
+    SELECT * FROM test2
+    "#;
+    assert!(ctx.execute(sql).is_err());
+}
+
 #[test]
 fn test_drop_table() {
     let mut ctx = create_ctx();
@@ -0,0 +1,202 @@
+use polars_core::prelude::*;
+use polars_lazy::prelude::*;
+use polars_sql::*;
+
+fn create_ctx() -> SQLContext {
+    let df = df! {
+        "a" => [1, 2, 3, 4],
+        "b" => ["w", "x", "y", "z"],
+    }
+    .unwrap();
+    let other = df! {
+        "c" => [2, 4, 5],
+    }
+    .unwrap();
+    let with_nulls = df! {
+        "x" => [Some(1), None, Some(2), Some(3)],
+    }
+    .unwrap();
+    let other_with_nulls = df! {
+        "c" => [Some(2), None],
+    }
+    .unwrap();
+    let mut ctx = SQLContext::new();
+    ctx.register("df", df.lazy());
+    ctx.register("other", other.lazy());
+    ctx.register("with_nulls", with_nulls.lazy());
+    ctx.register("other_with_nulls", other_with_nulls.lazy());
+    ctx
+}
+
+#[test]
+fn test_in_subquery() {
+    let mut ctx = create_ctx();
+    let sql = r#"
+    SELECT a, b FROM df
+    WHERE a IN (SELECT c FROM other) AND b <> 'z'
+    ORDER BY a
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df! {
+        "a" => [2],
+        "b" => ["x"],
+    }
+    .unwrap();
+    assert!(actual.frame_equal(&expected));
+}
+
+#[test]
+fn test_not_in_subquery() {
+    let mut ctx = create_ctx();
+    let sql = r#"
+    SELECT a FROM df
+    WHERE a NOT IN (SELECT c FROM other)
+    ORDER BY a
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df! {
+        "a" => [1, 3],
+    }
+    .unwrap();
+    assert!(actual.frame_equal(&expected));
+}
+
+#[test]
+fn test_not_in_subquery_with_nulls() {
+    let mut ctx = create_ctx();
+    // a NULL in the subquery makes every NOT IN unknown
+    let sql = r#"
+    SELECT a FROM df
+    WHERE a NOT IN (SELECT c FROM other_with_nulls)
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    assert_eq!(actual.height(), 0);
+
+    // a NULL is never NOT IN a set
+    let sql = r#"
+    SELECT x FROM with_nulls
+    WHERE x NOT IN (SELECT c FROM other)
+    ORDER BY x
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df! {
+        "x" => [1, 3],
+    }
+    .unwrap();
+    assert!(actual.frame_equal(&expected));
+
+    // unless the subquery is empty
+    let sql = r#"
+    SELECT x FROM with_nulls
+    WHERE x NOT IN (SELECT c FROM other WHERE c > 5)
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    assert_eq!(actual.height(), 4);
+}
+
+#[test]
+fn test_in_subquery_expression_with_nulls() {
+    let mut ctx = create_ctx();
+    let sql = r#"
+    SELECT
+        x,
+        x NOT IN (SELECT c FROM other) AS not_in_other,
+        x NOT IN (SELECT c FROM other_with_nulls) AS not_in_nulls,
+        x IN (SELECT c FROM other_with_nulls) AS in_nulls
+    FROM with_nulls
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df! {
+        "x" => [Some(1), None, Some(2), Some(3)],
+        "not_in_other" => [Some(true), None, Some(false), Some(true)],
+        "not_in_nulls" => [None, None, Some(false), None],
+        "in_nulls" => [None, None, Some(true), None],
+    }
+    .unwrap();
+    assert!(actual.frame_equal_missing(&expected));
+}
+
+#[test]
+fn test_nested_in_subquery() {
+    let mut ctx = create_ctx();
+    let sql = r#"
+    SELECT a FROM df
+    WHERE a = 1 OR a IN (SELECT c FROM other)
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df! {
+        "a" => [1, 2, 4],
+    }
+    .unwrap();
+    assert!(actual.frame_equal(&expected));
+}
+
+#[test]
+fn test_exists() {
+    let mut ctx = create_ctx();
+    let sql = r#"
+    SELECT a FROM df
+    WHERE EXISTS (SELECT 1 FROM other WHERE c > 4)
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    assert_eq!(actual.height(), 4);
+
+    let sql = r#"
+    SELECT a FROM df
+    WHERE EXISTS (SELECT 1 FROM other WHERE c > 5)
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    assert_eq!(actual.height(), 0);
+}
+
+#[test]
+fn test_scalar_subquery() {
+    let mut ctx = create_ctx();
+    let sql = r#"
+    SELECT a, a - (SELECT MIN(c) FROM other) AS diff FROM df
+    WHERE a > (SELECT MIN(c) FROM other)
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df! {
+        "a" => [3, 4],
+        "diff" => [1, 2],
+    }
+    .unwrap();
+    assert!(actual.frame_equal(&expected));
+}
+
+#[test]
+fn test_scalar_subquery_must_return_single_row() {
+    let mut ctx = create_ctx();
+    let sql = r#"
+    SELECT a - (SELECT c FROM other) AS diff FROM df
+    "#;
+    let res = ctx.execute(sql).and_then(|lf| lf.collect());
+    assert!(res.is_err());
+}
+
+#[test]
+fn test_scalar_subquery_with_wildcard() {
+    let mut ctx = create_ctx();
+    let sql = r#"
+    SELECT * FROM df
+    WHERE a = (SELECT MAX(c) FROM other WHERE c < 5)
+    "#;
+    let actual = ctx.execute(sql).unwrap().collect().unwrap();
+    let expected = df! {
+        "a" => [4],
+        "b" => ["z"],
+    }
+    .unwrap();
+    assert!(actual.frame_equal(&expected));
+}
+
+#[test]
+fn test_subquery_must_return_single_column() {
+    let mut ctx = create_ctx();
+    let sql = r#"
+    SELECT a FROM df
+    WHERE a IN (SELECT a, b FROM df)
+    "#;
+    assert!(ctx.execute(sql).is_err());
+}
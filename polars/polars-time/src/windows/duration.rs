@@ -322,6 +322,11 @@ impl Duration {
         self.months
     }
 
+    /// `true` if this is a negative duration.
+    pub fn negative(&self) -> bool {
+        self.negative
+    }
+
     pub fn weeks_only(&self) -> bool {
         self.months == 0 && self.weeks != 0 && self.days == 0 && self.nsecs == 0
     }